@@ -1,21 +1,35 @@
 use crate::Result;
 
 pub fn expand<I, S>(args: I) -> Result<Vec<String>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    expand_with_files(args).map(|(args, _)| args)
+}
+
+// Also returns the paths of any `--etc` files that the arguments were read from.
+pub fn expand_with_files<I, S>(args: I) -> Result<(Vec<String>, Vec<String>)>
 where
     I: IntoIterator<Item = S>,
     S: AsRef<str>,
 {
     let mut result = vec![];
-    from_iter(&mut result, args)?;
-    Ok(result)
+    let mut files = vec![];
+    from_iter(&mut result, &mut files, args)?;
+    Ok((result, files))
 }
 
 // This function is needed to avoid a recursion limit in the Rust compiler.
-fn from_string(result: &mut Vec<String>, value: &str) -> Result<()> {
-    from_iter(result, value.split_whitespace().map(|arg| arg.to_string()))
+fn from_string(result: &mut Vec<String>, files: &mut Vec<String>, value: &str) -> Result<()> {
+    from_iter(
+        result,
+        files,
+        value.split_whitespace().map(|arg| arg.to_string()),
+    )
 }
 
-fn from_iter<I, S>(result: &mut Vec<String>, args: I) -> Result<()>
+fn from_iter<I, S>(result: &mut Vec<String>, files: &mut Vec<String>, args: I) -> Result<()>
 where
     I: IntoIterator<Item = S>,
     S: AsRef<str>,
@@ -27,9 +41,16 @@ where
             expand = false;
         }
         if expand {
+            files.push(arg.clone());
+
+            if crate::extension(&arg) == "toml" {
+                result.extend(crate::config_file::expand(&arg)?);
+                continue;
+            }
+
             for args in crate::read_file_lines(&arg)? {
                 if !args.starts_with("//") {
-                    from_string(result, &args)?;
+                    from_string(result, files, &args)?;
                 }
             }
         } else if arg == "--etc" {
@@ -0,0 +1,209 @@
+use super::*;
+
+/// A typed alternative to the command line options accepted by [`bindgen`].
+///
+/// Bindings may either be written to a file, as with `--out`, or generated in memory:
+///
+/// ```no_run
+/// let bindings = windows_bindgen::Bindgen::new()
+///     .input("metadata/Component.winmd")
+///     .filter("Component")
+///     .sys(true)
+///     .generate()
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Bindgen {
+    input: Vec<String>,
+    output: Option<String>,
+    filter: Vec<String>,
+    config: Vec<String>,
+}
+
+impl Bindgen {
+    /// Creates a builder with no inputs, filters, or configuration values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a .winmd or .rdl file, or a directory containing them, to read metadata from.
+    pub fn input<S: AsRef<str>>(mut self, path: S) -> Self {
+        self.input.push(path.as_ref().to_string());
+        self
+    }
+
+    /// Sets the file to write when calling [`Bindgen::write`].
+    pub fn output<S: AsRef<str>>(mut self, path: S) -> Self {
+        self.output = Some(path.as_ref().to_string());
+        self
+    }
+
+    /// Includes a namespace or fully qualified type name.
+    pub fn filter<S: AsRef<str>>(mut self, name: S) -> Self {
+        self.filter.push(name.as_ref().to_string());
+        self
+    }
+
+    /// Excludes a namespace or fully qualified type name.
+    pub fn exclude<S: AsRef<str>>(mut self, name: S) -> Self {
+        self.filter.push(format!("!{}", name.as_ref()));
+        self
+    }
+
+    /// Adds a configuration value such as `prepend:Name=code`, exactly as it would be passed to `--config`.
+    pub fn config<S: AsRef<str>>(mut self, value: S) -> Self {
+        self.config.push(value.as_ref().to_string());
+        self
+    }
+
+    /// Generates a crate package rather than a single file.
+    pub fn package(self, enabled: bool) -> Self {
+        self.flag("package", enabled)
+    }
+
+    /// Generates all items in a single module rather than a module per namespace.
+    pub fn flatten(self, enabled: bool) -> Self {
+        self.flag("flatten", enabled)
+    }
+
+    /// Generates raw bindings in the style of the `windows-sys` crate.
+    pub fn sys(self, enabled: bool) -> Self {
+        self.flag("sys", enabled)
+    }
+
+    /// Generates the traits needed to implement interfaces.
+    pub fn implement(self, enabled: bool) -> Self {
+        self.flag("implement", enabled)
+    }
+
+    /// Omits helpers such as enumerators.
+    pub fn minimal(self, enabled: bool) -> Self {
+        self.flag("minimal", enabled)
+    }
+
+    /// Omits the `#![allow(..)]` attribute at the top of the generated bindings.
+    pub fn no_inner_attributes(self, enabled: bool) -> Self {
+        self.flag("no-inner-attributes", enabled)
+    }
+
+    /// Omits the comment identifying the version of `windows-bindgen` that generated the bindings.
+    pub fn no_bindgen_comment(self, enabled: bool) -> Self {
+        self.flag("no-bindgen-comment", enabled)
+    }
+
+    /// Includes the vtable layouts of interfaces when generating standalone bindings.
+    pub fn vtbl(self, enabled: bool) -> Self {
+        self.flag("vtbl", enabled)
+    }
+
+    /// Includes documentation comments from the metadata.
+    pub fn docs(self, enabled: bool) -> Self {
+        self.flag("docs", enabled)
+    }
+
+    /// Formats flags as the names of the bits that are set and adds `iter_names` and `from_name` helpers.
+    pub fn decode_flags(self, enabled: bool) -> Self {
+        self.flag("decode-flags", enabled)
+    }
+
+    /// Adds a Rust enum, with conversions to and from the generated type, for each enum that isn't a set of flags.
+    pub fn rust_enums(self, enabled: bool) -> Self {
+        self.flag("rust-enums", enabled)
+    }
+
+    /// Links functions with `raw-dylib` extern blocks so that flattened sys bindings have no dependencies.
+    pub fn raw_dylib(self, enabled: bool) -> Self {
+        self.flag("raw-dylib", enabled)
+    }
+
+    /// Sets the value passed to `rustfmt --config` when formatting the generated bindings.
+    pub fn rustfmt_config<S: AsRef<str>>(mut self, value: S) -> Self {
+        self.config
+            .retain(|config| !config.starts_with("rustfmt-config="));
+        self.config
+            .push(format!("rustfmt-config={}", value.as_ref()));
+        self
+    }
+
+    /// Writes a report to the given path, as text or JSON, explaining why each item was included in flattened bindings.
+    pub fn explain<S: AsRef<str>>(mut self, path: S) -> Self {
+        self.config.retain(|config| !config.starts_with("explain="));
+        self.config.push(format!("explain={}", path.as_ref()));
+        self
+    }
+
+    /// Returns the equivalent command line options.
+    pub fn args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        for (option, values) in [
+            ("--in", &self.input),
+            ("--filter", &self.filter),
+            ("--config", &self.config),
+        ] {
+            if !values.is_empty() {
+                args.push(option.to_string());
+                args.extend(values.iter().cloned());
+            }
+        }
+
+        if let Some(output) = &self.output {
+            args.push("--out".to_string());
+            args.push(output.clone());
+        }
+
+        args
+    }
+
+    /// Writes the bindings to the output file, just like [`bindgen`].
+    pub fn write(&self) -> Result<String> {
+        if self.output.is_none() {
+            return Err(Error::new("no output"));
+        }
+
+        bindgen(self.args())
+    }
+
+    /// Generates Rust bindings in memory rather than writing them to a file. Any output is ignored.
+    pub fn generate(&self) -> Result<String> {
+        let input: Vec<&str> = self.input.iter().map(String::as_str).collect();
+        let mut include = vec![];
+        let mut exclude = vec![];
+
+        for filter in &self.filter {
+            if let Some(rest) = filter.strip_prefix('!') {
+                exclude.push(rest);
+            } else {
+                include.push(filter.as_str());
+            }
+        }
+
+        let config = self
+            .config
+            .iter()
+            .map(|config| config.split_once('=').unwrap_or((config, "")))
+            .collect();
+
+        generate(&input, &include, &exclude, config)
+    }
+
+    /// Generates Rust bindings in memory and parses them into a token stream.
+    ///
+    /// Inner attributes cannot be expanded in most positions, so consider combining this with
+    /// [`Bindgen::no_inner_attributes`].
+    pub fn tokens(&self) -> Result<proc_macro2::TokenStream> {
+        self.generate()?
+            .parse()
+            .map_err(|error: proc_macro2::LexError| Error::new(&error.to_string()))
+    }
+
+    fn flag(mut self, key: &str, enabled: bool) -> Self {
+        self.config.retain(|config| config != key);
+
+        if enabled {
+            self.config.push(key.to_string());
+        }
+
+        self
+    }
+}
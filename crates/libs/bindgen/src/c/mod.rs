@@ -0,0 +1,1034 @@
+use super::*;
+use metadata::HasAttributes;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+pub fn from_reader(
+    reader: &metadata::Reader<'_>,
+    mut config: BTreeMap<&str, &str>,
+    output: &str,
+) -> Result<()> {
+    let mut writer = Writer::new(reader);
+    writer.no_bindgen_comment = config.remove("no-bindgen-comment").is_some();
+
+    if let Some((key, _)) = config.first_key_value() {
+        return Err(Error::new(&format!("invalid configuration value `{key}`")));
+    }
+
+    writer.file(output)?;
+    write_to_file(output, writer.out).map_err(|err| err.with_path(output))
+}
+
+// The C header is written as plain text rather than via `TokenStream` since C declarators don't map onto Rust
+// token trees and there's no equivalent of `rustfmt` to tidy things up afterwards.
+struct Writer<'a> {
+    reader: &'a metadata::Reader<'a>,
+    no_bindgen_comment: bool,
+    out: String,
+}
+
+// C requires types to be defined before they are used by value so the items are first collected and then
+// written out in a fixed order: forward declarations, enums, callbacks, structs, interfaces, functions, and constants.
+#[derive(Default)]
+struct Items<'a> {
+    enums: Vec<metadata::TypeDef<'a>>,
+    structs: Vec<metadata::TypeDef<'a>>,
+    callbacks: Vec<metadata::TypeDef<'a>>,
+    interfaces: Vec<metadata::TypeDef<'a>>,
+    // Interfaces that weren't included by the filter but are referred to by the items that were.
+    declared: Vec<metadata::TypeDef<'a>>,
+    classes: Vec<metadata::TypeDef<'a>>,
+    functions: Vec<metadata::MethodDef<'a>>,
+    constants: Vec<metadata::Field<'a>>,
+}
+
+impl<'a> Writer<'a> {
+    fn new(reader: &'a metadata::Reader<'a>) -> Self {
+        Self {
+            reader,
+            no_bindgen_comment: false,
+            out: String::new(),
+        }
+    }
+
+    fn line(&mut self, value: &str) {
+        self.out.push_str(value);
+        self.out.push('\n');
+    }
+
+    fn file(&mut self, output: &str) -> Result<()> {
+        let guard = header_guard(output);
+
+        if !self.no_bindgen_comment {
+            let name = std::env!("CARGO_PKG_NAME");
+            let version = std::env!("CARGO_PKG_VERSION");
+            self.line(&format!("// Bindings generated by `{name}` {version}\n"));
+        }
+
+        self.line(&format!("#ifndef {guard}"));
+        self.line(&format!("#define {guard}\n"));
+        self.line("#include <stdbool.h>");
+        self.line("#include <stdint.h>\n");
+        self.line("#ifdef __cplusplus");
+        self.line("extern \"C\" {");
+        self.line("#endif\n");
+        self.prelude();
+
+        let items = self.collect();
+        let mut declared = BTreeSet::new();
+
+        for def in items
+            .structs
+            .iter()
+            .chain(&items.interfaces)
+            .chain(&items.declared)
+            .filter(|def| !metadata::type_def_is_handle(**def))
+        {
+            let name = self.type_def_name(*def);
+
+            // Architecture-specific structs share a single declaration.
+            if declared.insert(name.clone()) {
+                self.line(&format!("typedef {} {name} {name};", struct_or_union(*def)));
+            }
+        }
+
+        self.line("");
+
+        for def in &items.enums {
+            self.enum_def(*def)?;
+        }
+
+        for def in &items.callbacks {
+            self.callback_def(*def)?;
+        }
+
+        let structs: BTreeSet<metadata::TypeDef<'a>> = items.structs.iter().copied().collect();
+        let mut written = BTreeSet::new();
+        for def in &items.structs {
+            self.struct_def(*def, &structs, &mut written)?;
+        }
+
+        for def in &items.interfaces {
+            self.interface_def(*def)?;
+        }
+
+        for def in &items.classes {
+            self.class_def(*def);
+        }
+
+        for def in &items.functions {
+            self.function(*def)?;
+        }
+
+        if !items.functions.is_empty() {
+            self.line("");
+        }
+
+        for def in &items.constants {
+            self.constant(*def)?;
+        }
+
+        self.line("\n#ifdef __cplusplus");
+        self.line("}");
+        self.line("#endif\n");
+        self.line(&format!("#endif // {guard}"));
+        Ok(())
+    }
+
+    fn prelude(&mut self) {
+        // These guards match those used by the Windows SDK headers so that the generated header may be included
+        // either on its own or alongside <windows.h>.
+        self.out.push_str(
+            r#"#ifndef GUID_DEFINED
+#define GUID_DEFINED
+typedef struct _GUID {
+    uint32_t Data1;
+    uint16_t Data2;
+    uint16_t Data3;
+    uint8_t Data4[8];
+} GUID;
+#endif
+
+#ifndef _HRESULT_DEFINED
+#define _HRESULT_DEFINED
+typedef int32_t HRESULT;
+#endif
+
+#ifndef __HSTRING__
+#define __HSTRING__
+typedef struct HSTRING__* HSTRING;
+#endif
+
+#ifndef RIDDLE_STRING_TYPES_DEFINED
+#define RIDDLE_STRING_TYPES_DEFINED
+typedef char* PSTR;
+typedef const char* PCSTR;
+typedef uint16_t* PWSTR;
+typedef const uint16_t* PCWSTR;
+typedef uint16_t* BSTR;
+#endif
+
+#ifndef __IUnknown_INTERFACE_DEFINED__
+#define __IUnknown_INTERFACE_DEFINED__
+typedef struct IUnknown IUnknown;
+typedef struct IUnknownVtbl {
+    HRESULT (__stdcall* QueryInterface)(IUnknown* This, const GUID* riid, void** ppvObject);
+    uint32_t (__stdcall* AddRef)(IUnknown* This);
+    uint32_t (__stdcall* Release)(IUnknown* This);
+} IUnknownVtbl;
+struct IUnknown {
+    const IUnknownVtbl* lpVtbl;
+};
+#endif
+
+#ifndef __IInspectable_INTERFACE_DEFINED__
+#define __IInspectable_INTERFACE_DEFINED__
+typedef struct IInspectable IInspectable;
+typedef struct IInspectableVtbl {
+    HRESULT (__stdcall* QueryInterface)(IInspectable* This, const GUID* riid, void** ppvObject);
+    uint32_t (__stdcall* AddRef)(IInspectable* This);
+    uint32_t (__stdcall* Release)(IInspectable* This);
+    HRESULT (__stdcall* GetIids)(IInspectable* This, uint32_t* iidCount, GUID** iids);
+    HRESULT (__stdcall* GetRuntimeClassName)(IInspectable* This, HSTRING* className);
+    HRESULT (__stdcall* GetTrustLevel)(IInspectable* This, int32_t* trustLevel);
+} IInspectableVtbl;
+struct IInspectable {
+    const IInspectableVtbl* lpVtbl;
+};
+#endif
+
+"#,
+        );
+    }
+
+    fn collect(&self) -> Items<'a> {
+        let mut items = Items::default();
+
+        for item in self.reader.items() {
+            match item {
+                metadata::Item::Type(def) => {
+                    let type_name = def.type_name();
+
+                    if self.reader.remap_type(type_name).is_some()
+                        || self.reader.core_type(type_name).is_some()
+                        || def.has_attribute("ApiContractAttribute")
+                    {
+                        continue;
+                    }
+
+                    // C has no way to express generic types so only closed types are written.
+                    if def.generics().next().is_some() {
+                        continue;
+                    }
+
+                    match def.kind() {
+                        metadata::TypeKind::Enum => items.enums.push(def),
+                        metadata::TypeKind::Struct => items.structs.push(def),
+                        metadata::TypeKind::Interface => items.interfaces.push(def),
+                        metadata::TypeKind::Class => items.classes.push(def),
+                        metadata::TypeKind::Delegate => {
+                            if def
+                                .flags()
+                                .contains(metadata::TypeAttributes::WindowsRuntime)
+                            {
+                                items.interfaces.push(def)
+                            } else {
+                                items.callbacks.push(def)
+                            }
+                        }
+                    }
+                }
+                metadata::Item::Fn(def, _) => items.functions.push(def),
+                metadata::Item::Const(def) => items.constants.push(def),
+            }
+        }
+
+        self.collect_dependencies(&mut items);
+        items
+    }
+
+    // The types that the filtered items depend on are collected as well so that the header is self-contained.
+    // Interfaces are only ever used through pointers so those that weren't included by the filter are declared but
+    // not defined.
+    fn collect_dependencies(&self, items: &mut Items<'a>) {
+        let mut collected: BTreeSet<metadata::TypeDef<'a>> = items
+            .enums
+            .iter()
+            .chain(&items.structs)
+            .chain(&items.callbacks)
+            .chain(&items.interfaces)
+            .chain(&items.classes)
+            .copied()
+            .collect();
+
+        let mut pending = vec![];
+
+        for def in &items.structs {
+            self.struct_types(*def, &mut pending);
+        }
+
+        for def in items.callbacks.iter().chain(&items.interfaces) {
+            for method in def.methods() {
+                method_types(method, &mut pending);
+            }
+
+            for base in metadata::type_def_vtables(*def) {
+                pending.push(base);
+            }
+        }
+
+        for def in &items.functions {
+            method_types(*def, &mut pending);
+        }
+
+        for def in &items.constants {
+            pending.push(def.ty(None));
+        }
+
+        while let Some(ty) = pending.pop() {
+            let def = match ty {
+                metadata::Type::TypeDef(def, generics) if generics.is_empty() => def,
+                // Types like `VARIANT` are referred to by name even when their definitions are available.
+                metadata::Type::Name(type_name) if !is_prelude(type_name) => {
+                    match self
+                        .reader
+                        .get_type_def(type_name.namespace(), type_name.name())
+                        .next()
+                    {
+                        Some(def) => def,
+                        None => continue,
+                    }
+                }
+                metadata::Type::MutPtr(ty, _)
+                | metadata::Type::ConstPtr(ty, _)
+                | metadata::Type::Win32Array(ty, _)
+                | metadata::Type::WinrtArray(ty)
+                | metadata::Type::WinrtArrayRef(ty)
+                | metadata::Type::ConstRef(ty)
+                | metadata::Type::PrimitiveOrEnum(_, ty) => {
+                    pending.push(*ty);
+                    continue;
+                }
+                _ => continue,
+            };
+
+            // Nested types are written along with the type that encloses them.
+            if let Some(outer) = def.enclosing_type() {
+                pending.push(metadata::Type::TypeDef(outer, vec![]));
+                continue;
+            }
+
+            if !collected.insert(def) {
+                continue;
+            }
+
+            match def.kind() {
+                metadata::TypeKind::Enum => items.enums.push(def),
+                metadata::TypeKind::Struct => {
+                    items.structs.push(def);
+                    self.struct_types(def, &mut pending);
+                }
+                metadata::TypeKind::Delegate
+                    if !def
+                        .flags()
+                        .contains(metadata::TypeAttributes::WindowsRuntime) =>
+                {
+                    items.callbacks.push(def);
+                    method_types(metadata::type_def_invoke_method(def), &mut pending);
+                }
+                metadata::TypeKind::Class => {
+                    pending.extend(metadata::type_def_default_interface(def));
+                }
+                metadata::TypeKind::Interface | metadata::TypeKind::Delegate => {
+                    items.declared.push(def)
+                }
+            }
+        }
+    }
+
+    fn struct_types(&self, def: metadata::TypeDef<'a>, pending: &mut Vec<metadata::Type<'a>>) {
+        for field in def.fields() {
+            pending.push(field.ty(Some(def)));
+        }
+
+        for nested in self.reader.nested_types(def) {
+            self.struct_types(nested, pending);
+        }
+    }
+
+    fn enum_def(&mut self, def: metadata::TypeDef<'a>) -> Result<()> {
+        let name = self.type_def_name(def);
+        let underlying_type = self.type_name(&def.underlying_type())?;
+        self.line(&format!("typedef {underlying_type} {name};"));
+
+        // Unscoped enums already have their fields included as constants by the `Reader`.
+        let is_scoped = def
+            .flags()
+            .contains(metadata::TypeAttributes::WindowsRuntime)
+            || def.has_attribute("ScopedEnumAttribute");
+
+        if is_scoped {
+            for field in def.fields() {
+                if let Some(constant) = field.constant() {
+                    let value = self.value(&constant.value())?;
+                    self.line(&format!(
+                        "#define {name}_{} (({name}){value})",
+                        field.name()
+                    ));
+                }
+            }
+        }
+
+        self.line("");
+        Ok(())
+    }
+
+    fn callback_def(&mut self, def: metadata::TypeDef<'a>) -> Result<()> {
+        let name = self.type_def_name(def);
+        let method = metadata::type_def_invoke_method(def);
+        let signature = Signature::new(method);
+        let return_type = self.type_name(&signature.return_type)?;
+        let params = self.params(&signature)?;
+        self.line(&format!(
+            "typedef {return_type} (__stdcall* {name})({params});\n"
+        ));
+        Ok(())
+    }
+
+    fn struct_def(
+        &mut self,
+        def: metadata::TypeDef<'a>,
+        structs: &BTreeSet<metadata::TypeDef<'a>>,
+        written: &mut BTreeSet<metadata::TypeDef<'a>>,
+    ) -> Result<()> {
+        if !written.insert(def) {
+            return Ok(());
+        }
+
+        // Any struct used by value, including by nested types, must be defined first.
+        let mut dependencies = vec![];
+        self.struct_dependencies(def, structs, &mut dependencies);
+
+        for dependency in dependencies {
+            self.struct_def(dependency, structs, written)?;
+        }
+
+        let arch = arch(def);
+
+        if let Some(arch) = &arch {
+            self.line(&format!("#if {arch}"));
+        }
+
+        self.struct_def_imp(def)?;
+
+        if arch.is_some() {
+            self.line("#endif\n");
+        }
+
+        Ok(())
+    }
+
+    fn struct_dependencies(
+        &self,
+        def: metadata::TypeDef<'a>,
+        structs: &BTreeSet<metadata::TypeDef<'a>>,
+        dependencies: &mut Vec<metadata::TypeDef<'a>>,
+    ) {
+        for field in def.fields() {
+            let ty = field.ty(Some(def));
+            if let metadata::Type::TypeDef(field_def, _) = ty.to_underlying_type() {
+                if !ty.is_pointer() && structs.contains(&field_def) {
+                    dependencies.push(field_def);
+                }
+            }
+        }
+
+        for nested in self.reader.nested_types(def) {
+            self.struct_dependencies(nested, structs, dependencies);
+        }
+    }
+
+    fn struct_def_imp(&mut self, def: metadata::TypeDef<'a>) -> Result<()> {
+        if metadata::type_def_is_handle(def) {
+            let name = self.type_def_name(def);
+            let underlying_type = self.type_name(&def.underlying_type())?;
+            self.line(&format!("typedef {underlying_type} {name};\n"));
+            return Ok(());
+        }
+
+        for nested in self.reader.nested_types(def).collect::<Vec<_>>() {
+            self.line(&format!(
+                "typedef {} {} {};",
+                struct_or_union(nested),
+                self.type_def_name(nested),
+                self.type_def_name(nested)
+            ));
+            self.struct_def_imp(nested)?;
+        }
+
+        let name = self.type_def_name(def);
+
+        if def.fields().next().is_none() {
+            if let Some(guid) = metadata::type_def_guid(def) {
+                // Win32 metadata models CLSIDs as empty structs with a GUID.
+                let guid = guid_literal(&guid);
+                self.line(&format!("static const GUID CLSID_{name} = {guid};\n"));
+            } else {
+                self.line(&format!("struct {name} {{\n    uint8_t unused;\n}};\n"));
+            }
+            return Ok(());
+        }
+
+        if let Some(layout) = def.class_layout() {
+            self.line(&format!("#pragma pack(push, {})", layout.packing_size()));
+        }
+
+        self.line(&format!("{} {name} {{", struct_or_union(def)));
+
+        for field in def.fields() {
+            if field.flags().contains(metadata::FieldAttributes::Literal) {
+                continue;
+            }
+
+            let declaration = self.declaration(&field.ty(Some(def)), field.name())?;
+            self.line(&format!("    {declaration};"));
+        }
+
+        self.line("};");
+
+        if def.class_layout().is_some() {
+            self.line("#pragma pack(pop)");
+        }
+
+        self.line("");
+        Ok(())
+    }
+
+    fn interface_def(&mut self, def: metadata::TypeDef<'a>) -> Result<()> {
+        let name = self.type_def_name(def);
+
+        if let Some(guid) = metadata::type_def_guid(def) {
+            let guid = guid_literal(&guid);
+            self.line(&format!("static const GUID IID_{name} = {guid};"));
+        }
+
+        self.line(&format!("typedef struct {name}Vtbl {{"));
+
+        for base in metadata::type_def_vtables(def) {
+            match base {
+                metadata::Type::Name(metadata::TypeName::IUnknown) => {
+                    self.line(&format!("    HRESULT (__stdcall* QueryInterface)({name}* This, const GUID* riid, void** ppvObject);"));
+                    self.line(&format!("    uint32_t (__stdcall* AddRef)({name}* This);"));
+                    self.line(&format!("    uint32_t (__stdcall* Release)({name}* This);"));
+                }
+                metadata::Type::Object => {
+                    self.line(&format!("    HRESULT (__stdcall* GetIids)({name}* This, uint32_t* iidCount, GUID** iids);"));
+                    self.line(&format!("    HRESULT (__stdcall* GetRuntimeClassName)({name}* This, HSTRING* className);"));
+                    self.line(&format!("    HRESULT (__stdcall* GetTrustLevel)({name}* This, int32_t* trustLevel);"));
+                }
+                metadata::Type::TypeDef(base, _) => self.vtbl_methods(&name, base)?,
+                rest => {
+                    return Err(Error::new(&format!(
+                        "`{name}` has an unsupported base interface `{rest:?}`"
+                    )))
+                }
+            }
+        }
+
+        self.vtbl_methods(&name, def)?;
+        self.line(&format!("}} {name}Vtbl;"));
+        self.line(&format!("struct {name} {{"));
+        self.line(&format!("    const {name}Vtbl* lpVtbl;"));
+        self.line("};\n");
+        Ok(())
+    }
+
+    fn vtbl_methods(&mut self, this: &str, def: metadata::TypeDef<'a>) -> Result<()> {
+        let is_winrt = def
+            .flags()
+            .contains(metadata::TypeAttributes::WindowsRuntime);
+
+        for method in def.methods() {
+            if method.name() == ".ctor" {
+                continue;
+            }
+
+            let signature = Signature::new(method);
+            let name = method_def_abi_name(method);
+
+            let (return_type, params) = if is_winrt {
+                ("HRESULT".to_string(), self.winrt_params(&signature)?)
+            } else if metadata::type_is_struct(&signature.return_type) {
+                // Struct return values are returned through a hidden pointer parameter for COM methods.
+                let return_type = self.type_name(&signature.return_type)?;
+                let mut params = format!("{return_type}* result");
+                let rest = self.params(&signature)?;
+
+                if rest != "void" {
+                    params.push_str(", ");
+                    params.push_str(&rest);
+                }
+
+                (format!("{return_type}*"), params)
+            } else {
+                (
+                    self.type_name(&signature.return_type)?,
+                    self.params(&signature)?,
+                )
+            };
+
+            if params == "void" || params.is_empty() {
+                self.line(&format!(
+                    "    {return_type} (__stdcall* {name})({this}* This);"
+                ));
+            } else {
+                self.line(&format!(
+                    "    {return_type} (__stdcall* {name})({this}* This, {params});"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn class_def(&mut self, def: metadata::TypeDef<'a>) {
+        let name = self.type_def_name(def);
+        let type_name = def.type_name();
+        self.line(&format!(
+            "#define RuntimeClass_{name} L\"{}.{}\"\n",
+            type_name.namespace(),
+            type_name.name()
+        ));
+    }
+
+    fn function(&mut self, def: metadata::MethodDef<'a>) -> Result<()> {
+        // TODO: remove inline functions from metadata
+        if def.module_name() == "FORCEINLINE" {
+            return Ok(());
+        }
+
+        let Some(impl_map) = def.impl_map() else {
+            return Ok(());
+        };
+
+        // TODO: remove ordinal functions from metadata
+        if impl_map.import_name().starts_with('#') {
+            return Ok(());
+        }
+
+        let flags = impl_map.flags();
+        let abi = if flags.contains(metadata::PInvokeAttributes::CallConvCdecl)
+            && !flags.contains(metadata::PInvokeAttributes::CallConvPlatformapi)
+        {
+            "__cdecl"
+        } else {
+            "__stdcall"
+        };
+
+        let signature = Signature::new(def);
+        let return_type = self.type_name(&signature.return_type)?;
+        let mut params = self.params(&signature)?;
+
+        if signature
+            .call_flags
+            .contains(metadata::MethodCallAttributes::VARARG)
+        {
+            params.push_str(", ...");
+        }
+
+        let arch = arch(def);
+
+        if let Some(arch) = &arch {
+            self.line(&format!("#if {arch}"));
+        }
+
+        self.line(&format!(
+            "{return_type} {abi} {}({params});",
+            impl_map.import_name()
+        ));
+
+        if arch.is_some() {
+            self.line("#endif");
+        }
+
+        Ok(())
+    }
+
+    fn constant(&mut self, def: metadata::Field<'a>) -> Result<()> {
+        let name = def.name();
+
+        if let Some(constant) = def.constant() {
+            let value = constant.value();
+            let ty = def.ty(None).to_const_type();
+
+            let value = match (&value, &ty) {
+                (metadata::Value::String(_), _) if field_is_ansi(def) => self.value(&value)?,
+                (metadata::Value::String(_), _) => format!("L{}", self.value(&value)?),
+                (_, metadata::Type::TypeDef(..)) => {
+                    format!("(({}){})", self.type_name(&ty)?, self.value(&value)?)
+                }
+                _ => self.value(&value)?,
+            };
+
+            self.line(&format!("#define {name} {value}"));
+        } else if let Some(guid) = def
+            .find_attribute("GuidAttribute")
+            .map(|attribute| metadata::Guid::from_args(&attribute.args()))
+        {
+            let guid = guid_literal(&guid);
+            self.line(&format!("static const GUID {name} = {guid};"));
+        }
+
+        Ok(())
+    }
+
+    fn params(&self, signature: &Signature<'a>) -> Result<String> {
+        if signature.params.is_empty() {
+            return Ok("void".to_string());
+        }
+
+        let params = signature
+            .params
+            .iter()
+            .map(|(param, ty)| self.declaration(ty, param.name()))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(params.join(", "))
+    }
+
+    // WinRT methods always return an `HRESULT` with the logical return value passed as a trailing out parameter and
+    // arrays passed as a separate length and pointer pair.
+    fn winrt_params(&self, signature: &Signature<'a>) -> Result<String> {
+        let mut params = vec![];
+
+        for (param, ty) in &signature.params {
+            let name = param.name();
+            let is_input = !param.flags().contains(metadata::ParamAttributes::Out);
+
+            if ty.is_winrt_array() {
+                let ty = self.type_name(ty)?;
+
+                if is_input {
+                    params.push(format!("uint32_t {name}Length, const {ty}* {name}"));
+                } else {
+                    params.push(format!("uint32_t {name}Length, {ty}* {name}"));
+                }
+            } else if ty.is_winrt_array_ref() {
+                let ty = self.type_name(ty)?;
+                params.push(format!("uint32_t* {name}Length, {ty}** {name}"));
+            } else if ty.is_const_ref() {
+                let ty = self.type_name(ty)?;
+                params.push(format!("const {ty}* {name}"));
+            } else if is_input {
+                params.push(format!("{} {name}", self.type_name(ty)?));
+            } else {
+                params.push(format!("{}* {name}", self.type_name(ty)?));
+            }
+        }
+
+        match &signature.return_type {
+            metadata::Type::Void => {}
+            metadata::Type::WinrtArray(ty) => {
+                let ty = self.type_name(ty)?;
+                params.push(format!("uint32_t* resultLength, {ty}** result"));
+            }
+            ty => params.push(format!("{}* result", self.type_name(ty)?)),
+        }
+
+        Ok(params.join(", "))
+    }
+
+    fn declaration(&self, ty: &metadata::Type<'a>, name: &str) -> Result<String> {
+        Ok(match ty {
+            metadata::Type::Win32Array(ty, len) => {
+                format!("{}[{len}]", self.declaration(ty, name)?)
+            }
+            _ => format!("{} {name}", self.type_name(ty)?),
+        })
+    }
+
+    fn type_name(&self, ty: &metadata::Type<'a>) -> Result<String> {
+        Ok(match ty {
+            metadata::Type::Void => "void".to_string(),
+            metadata::Type::Bool => "bool".to_string(),
+            metadata::Type::Char => "uint16_t".to_string(),
+            metadata::Type::I8 => "int8_t".to_string(),
+            metadata::Type::U8 => "uint8_t".to_string(),
+            metadata::Type::I16 => "int16_t".to_string(),
+            metadata::Type::U16 => "uint16_t".to_string(),
+            metadata::Type::I32 => "int32_t".to_string(),
+            metadata::Type::U32 => "uint32_t".to_string(),
+            metadata::Type::I64 => "int64_t".to_string(),
+            metadata::Type::U64 => "uint64_t".to_string(),
+            metadata::Type::F32 => "float".to_string(),
+            metadata::Type::F64 => "double".to_string(),
+            metadata::Type::ISize => "intptr_t".to_string(),
+            metadata::Type::USize => "uintptr_t".to_string(),
+            metadata::Type::String => "HSTRING".to_string(),
+            metadata::Type::Object => "IInspectable*".to_string(),
+            metadata::Type::Name(metadata::TypeName::GUID) => "GUID".to_string(),
+            metadata::Type::Name(metadata::TypeName::IUnknown) => "IUnknown*".to_string(),
+            metadata::Type::Name(metadata::TypeName::HResult) => "HRESULT".to_string(),
+            metadata::Type::Const(metadata::TypeName::PSTR) => "PCSTR".to_string(),
+            metadata::Type::Const(metadata::TypeName::PWSTR) => "PCWSTR".to_string(),
+            metadata::Type::Name(
+                type_name @ (metadata::TypeName::PSTR
+                | metadata::TypeName::PWSTR
+                | metadata::TypeName::BSTR),
+            ) => type_name.name().to_string(),
+            metadata::Type::Name(type_name) | metadata::Type::Const(type_name) => {
+                match self
+                    .reader
+                    .get_type_def(type_name.namespace(), type_name.name())
+                    .next()
+                {
+                    Some(def) => self.type_def_name(def),
+                    None => {
+                        return Err(Error::new(&format!(
+                            "type `{type_name}` is not defined by the input metadata"
+                        )))
+                    }
+                }
+            }
+            metadata::Type::TypeDef(def, generics) => match def.kind() {
+                // Generic interface instances have no name in C but any WinRT interface is ABI-compatible with
+                // `IInspectable` and may be queried for the specific interface as needed.
+                metadata::TypeKind::Interface | metadata::TypeKind::Delegate
+                    if !generics.is_empty() =>
+                {
+                    "IInspectable*".to_string()
+                }
+                metadata::TypeKind::Interface => format!("{}*", self.type_def_name(*def)),
+                metadata::TypeKind::Delegate
+                    if def
+                        .flags()
+                        .contains(metadata::TypeAttributes::WindowsRuntime) =>
+                {
+                    format!("{}*", self.type_def_name(*def))
+                }
+                metadata::TypeKind::Class => match metadata::type_def_default_interface(*def) {
+                    Some(default) => self.type_name(&default)?,
+                    None => "IInspectable*".to_string(),
+                },
+                _ => self.type_def_name(*def),
+            },
+            metadata::Type::MutPtr(ty, pointers) => {
+                format!("{}{}", self.type_name(ty)?, "*".repeat(*pointers))
+            }
+            metadata::Type::ConstPtr(ty, pointers) => {
+                format!("const {}{}", self.type_name(ty)?, "*".repeat(*pointers))
+            }
+            metadata::Type::Win32Array(ty, _) => format!("{}*", self.type_name(ty)?),
+            metadata::Type::WinrtArray(ty)
+            | metadata::Type::WinrtArrayRef(ty)
+            | metadata::Type::ConstRef(ty) => self.type_name(ty)?,
+            metadata::Type::PrimitiveOrEnum(_, ty) => self.type_name(ty)?,
+            metadata::Type::GenericParam(generic) => {
+                return Err(Error::new(&format!(
+                    "generic parameter `{}` is not supported in C headers",
+                    generic.name()
+                )))
+            }
+        })
+    }
+
+    // WinRT type names are qualified by namespace to avoid collisions in C's flat namespace whereas Win32 type names
+    // are already unique and match the names used by the Windows SDK headers.
+    fn type_def_name(&self, def: metadata::TypeDef<'a>) -> String {
+        if let Some(enclosing_type) = def.enclosing_type() {
+            for (index, nested_type) in self.reader.nested_types(enclosing_type).enumerate() {
+                if nested_type.name() == def.name() {
+                    return format!("{}_{index}", self.type_def_name(enclosing_type));
+                }
+            }
+        }
+
+        if def
+            .flags()
+            .contains(metadata::TypeAttributes::WindowsRuntime)
+        {
+            format!("{}_{}", def.namespace().replace('.', "_"), def.name())
+        } else {
+            def.name().to_string()
+        }
+    }
+
+    fn value(&self, value: &metadata::Value<'a>) -> Result<String> {
+        Ok(match value {
+            metadata::Value::Bool(value) => value.to_string(),
+            metadata::Value::U8(value) => value.to_string(),
+            metadata::Value::I8(value) => value.to_string(),
+            metadata::Value::U16(value) => value.to_string(),
+            metadata::Value::I16(value) => value.to_string(),
+            metadata::Value::U32(value) => format!("{value}U"),
+            metadata::Value::I32(value) if *value == i32::MIN => "(-2147483647 - 1)".to_string(),
+            metadata::Value::I32(value) => value.to_string(),
+            metadata::Value::U64(value) => format!("{value}ULL"),
+            metadata::Value::I64(value) if *value == i64::MIN => {
+                "(-9223372036854775807LL - 1)".to_string()
+            }
+            metadata::Value::I64(value) => format!("{value}LL"),
+            metadata::Value::F32(value) => format!("{value:?}f"),
+            metadata::Value::F64(value) => format!("{value:?}"),
+            metadata::Value::String(value) => {
+                let mut literal = "\"".to_string();
+
+                for c in value.chars() {
+                    match c {
+                        '"' => literal.push_str("\\\""),
+                        '\\' => literal.push_str("\\\\"),
+                        ' '..='~' => literal.push(c),
+                        _ => write!(literal, "\\x{:x}", c as u32).unwrap(),
+                    }
+                }
+
+                literal.push('"');
+                literal
+            }
+            metadata::Value::EnumDef(_, value) => self.value(value)?,
+            metadata::Value::TypeName(_) => {
+                return Err(Error::new(&format!(
+                    "value `{value:?}` is not supported in C headers"
+                )))
+            }
+        })
+    }
+}
+
+// The C header mirrors the signature as it appears in metadata rather than the Rust-specific projection produced by
+// `metadata::method_def_signature`.
+struct Signature<'a> {
+    return_type: metadata::Type<'a>,
+    params: Vec<(metadata::Param<'a>, metadata::Type<'a>)>,
+    call_flags: metadata::MethodCallAttributes,
+}
+
+impl<'a> Signature<'a> {
+    fn new(method: metadata::MethodDef<'a>) -> Self {
+        let signature = method.signature(&[]);
+        let mut return_type = signature.return_type;
+        let mut types = signature.params.into_iter();
+        let mut params = vec![];
+
+        for param in method.params() {
+            let is_const = param.has_attribute("ConstAttribute");
+
+            if param.sequence() == 0 {
+                if is_const {
+                    return_type = return_type.to_const_type();
+                }
+            } else if let Some(ty) = types.next() {
+                if is_const {
+                    params.push((param, ty.to_const_type().to_const_ptr()));
+                } else {
+                    params.push((param, ty));
+                }
+            }
+        }
+
+        Self {
+            return_type,
+            params,
+            call_flags: signature.call_flags,
+        }
+    }
+}
+
+fn struct_or_union<'a>(def: metadata::TypeDef<'a>) -> &'static str {
+    if def
+        .flags()
+        .contains(metadata::TypeAttributes::ExplicitLayout)
+    {
+        "union"
+    } else {
+        "struct"
+    }
+}
+
+// The types that are defined by the prelude written at the top of every header.
+fn is_prelude(type_name: metadata::TypeName<'_>) -> bool {
+    matches!(
+        type_name,
+        metadata::TypeName::GUID
+            | metadata::TypeName::IUnknown
+            | metadata::TypeName::HResult
+            | metadata::TypeName::BSTR
+            | metadata::TypeName::PSTR
+            | metadata::TypeName::PWSTR
+    )
+}
+
+fn method_types<'a>(method: metadata::MethodDef<'a>, pending: &mut Vec<metadata::Type<'a>>) {
+    let signature = method.signature(&[]);
+    pending.push(signature.return_type);
+    pending.extend(signature.params);
+}
+
+// Win32 metadata includes some types and functions that are only defined for specific architectures.
+fn arch<'a, R: metadata::AsRow<'a> + Into<metadata::HasAttribute<'a>>>(row: R) -> Option<String> {
+    let attribute = row.find_attribute("SupportedArchitectureAttribute")?;
+    let args = attribute.args();
+    let Some((_, metadata::Value::EnumDef(_, value))) = args.first() else {
+        return None;
+    };
+    let metadata::Value::I32(value) = **value else {
+        return None;
+    };
+
+    let mut arches = vec![];
+
+    if value & 1 == 1 {
+        arches.push("defined(_M_IX86)");
+    }
+    if value & 2 == 2 {
+        arches.push("defined(_M_AMD64)");
+    }
+    if value & 4 == 4 {
+        arches.push("defined(_M_ARM64)");
+    }
+
+    Some(arches.join(" || "))
+}
+
+fn method_def_abi_name<'a>(method: metadata::MethodDef<'a>) -> String {
+    if let Some(attribute) = method.find_attribute("OverloadAttribute") {
+        for (_, arg) in attribute.args() {
+            if let metadata::Value::String(name) = arg {
+                return name;
+            }
+        }
+    }
+
+    method.name().to_string()
+}
+
+fn field_is_ansi<'a>(row: metadata::Field<'a>) -> bool {
+    row.find_attribute("NativeEncodingAttribute")
+        .is_some_and(|attribute| {
+            matches!(attribute.args().first(), Some((_, metadata::Value::String(encoding))) if encoding == "ansi")
+        })
+}
+
+fn guid_literal(value: &metadata::Guid) -> String {
+    format!(
+        "{{ 0x{:08x}, 0x{:04x}, 0x{:04x}, {{ 0x{:02x}, 0x{:02x}, 0x{:02x}, 0x{:02x}, 0x{:02x}, 0x{:02x}, 0x{:02x}, 0x{:02x} }} }}",
+        value.0, value.1, value.2, value.3, value.4, value.5, value.6, value.7, value.8, value.9, value.10
+    )
+}
+
+fn header_guard(output: &str) -> String {
+    let name = output
+        .rsplit_once(['/', '\\'])
+        .map_or(output, |(_, name)| name);
+
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+        + "_"
+}
@@ -0,0 +1,55 @@
+use super::*;
+
+// Identifies everything that the generated output depends on: the version of bindgen, the arguments, and the
+// contents of the input files. FNV-1a is used rather than `DefaultHasher` since the latter isn't guaranteed to
+// produce the same hash across Rust releases.
+pub fn hash(args: &[String], inputs: &[String]) -> Result<String> {
+    let mut hasher = Hasher::default();
+    hasher.write(std::env!("CARGO_PKG_VERSION").as_bytes());
+    hasher.write(&[cfg!(feature = "metadata") as u8]);
+
+    for arg in args {
+        hasher.write(arg.as_bytes());
+    }
+
+    // Directories are listed in no particular order so the paths are sorted to keep the hash stable.
+    let mut inputs = inputs.to_vec();
+    inputs.sort();
+
+    for input in &inputs {
+        hasher.write(input.as_bytes());
+        hasher.write(&read_file_bytes(input).map_err(|error| error.with_path(input))?);
+    }
+
+    Ok(format!("{:016x}", hasher.0))
+}
+
+pub fn is_current(path: &str, output: &str, hash: &str) -> bool {
+    std::path::Path::new(output).exists()
+        && std::fs::read_to_string(path).is_ok_and(|previous| previous.trim() == hash)
+}
+
+pub fn write(path: &str, hash: &str) -> Result<()> {
+    write_to_file(path, hash)
+}
+
+struct Hasher(u64);
+
+impl Default for Hasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+
+        // Separates consecutive writes so that "ab" + "c" and "a" + "bc" hash differently.
+        self.0 ^= 0xff;
+        self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+    }
+}
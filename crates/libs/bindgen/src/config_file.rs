@@ -0,0 +1,272 @@
+use super::*;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use toml::Spanned;
+
+// A structured alternative to a file of command line options. The file is validated and then expanded into the
+// equivalent command line options so that both are interpreted in exactly the same way:
+//
+// ```toml
+// input = ["metadata/Component.winmd"]
+// output = "src/bindings.rs"
+// include = ["Windows.Foundation.Uri"]
+//
+// [namespaces."Windows.Win32.Foundation"]
+// include = ["CloseHandle", "GetLastError"]
+//
+// [options]
+// flatten = true
+// rustfmt-config = "max_width=120"
+//
+// [types."Windows.Win32.Foundation.POINT"]
+// derive = ["Hash"]
+//
+// [types."Windows.Win32.Foundation.GetLastError"]
+// rename = "last_error"
+// must-use = true
+// ```
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    // Paths to .winmd and .rdl files and directories containing them.
+    #[serde(default)]
+    input: Vec<String>,
+    output: Option<String>,
+    // Fully qualified namespaces or type names to include or exclude.
+    #[serde(default)]
+    include: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+    #[serde(default)]
+    namespaces: BTreeMap<Spanned<String>, NamespaceFilter>,
+    #[serde(default)]
+    options: Options,
+    #[serde(default)]
+    types: BTreeMap<Spanned<String>, TypeOverride>,
+}
+
+// Filters the types within a namespace. The whole namespace is included if no types are listed.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NamespaceFilter {
+    #[serde(default)]
+    include: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+// The same options as `--config` but with typed values.
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+struct Options {
+    package: bool,
+    flatten: bool,
+    sys: bool,
+    implement: bool,
+    minimal: bool,
+    no_inner_attributes: bool,
+    no_bindgen_comment: bool,
+    vtbl: bool,
+    docs: bool,
+    decode_flags: bool,
+    rust_enums: bool,
+    raw_dylib: bool,
+    split: bool,
+    classify: bool,
+    json: bool,
+    rustfmt_config: Option<String>,
+    // The path of a report explaining why each item was included in standalone bindings.
+    explain: Option<String>,
+    #[serde(rename = "type")]
+    dialect: Option<Dialect>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Dialect {
+    Winrt,
+    Win32,
+}
+
+// Overrides for a type, function, or field. Apart from `prepend`, these only apply to structs, enums, functions,
+// and fields.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct TypeOverride {
+    // Code to insert before the type's generated bindings.
+    prepend: Option<String>,
+    rename: Option<String>,
+    #[serde(default)]
+    derive: Vec<String>,
+    #[serde(default)]
+    attributes: Vec<String>,
+    #[serde(default)]
+    must_use: bool,
+    #[serde(default)]
+    hidden: bool,
+    // Standalone bindings may replace a struct that is only used through pointers with `c_void`.
+    #[serde(default)]
+    opaque: bool,
+    #[serde(default)]
+    exclude: bool,
+}
+
+/// Reads a config file and expands it into the equivalent command line options.
+pub fn expand(path: &str) -> Result<Vec<String>> {
+    let source = read_file_text(path).map_err(|error| error.with_path(path))?;
+
+    let file: ConfigFile = toml::from_str(&source).map_err(|error| {
+        match error.span() {
+            Some(range) => Error::with_range(error.message(), &source, range),
+            None => Error::new(error.message()),
+        }
+        .with_path(path)
+    })?;
+
+    let mut args = vec![];
+
+    if !file.input.is_empty() {
+        args.push("--in".to_string());
+        args.extend(file.input);
+    }
+
+    if let Some(output) = file.output {
+        args.push("--out".to_string());
+        args.push(output);
+    }
+
+    let mut filter = file.include;
+    filter.extend(file.exclude.into_iter().map(|name| format!("!{name}")));
+
+    for (namespace, namespace_filter) in &file.namespaces {
+        if namespace_filter.include.is_empty() {
+            filter.push(namespace.get_ref().clone());
+        }
+
+        for (names, prefix) in [
+            (&namespace_filter.include, ""),
+            (&namespace_filter.exclude, "!"),
+        ] {
+            filter.extend(
+                names
+                    .iter()
+                    .map(|name| format!("{prefix}{}.{name}", namespace.get_ref())),
+            );
+        }
+    }
+
+    if !filter.is_empty() {
+        args.push("--filter".to_string());
+        args.extend(filter);
+    }
+
+    let mut config = file.options.into_args();
+
+    for (name, type_override) in &file.types {
+        if !name.get_ref().contains('.') {
+            return Err(Error::with_range(
+                "overrides require a fully qualified name",
+                &source,
+                name.span(),
+            )
+            .with_path(path));
+        }
+
+        let name = name.get_ref();
+
+        if let Some(prepend) = &type_override.prepend {
+            config.push(format!("prepend:{name}={prepend}"));
+        }
+
+        if let Some(rename) = &type_override.rename {
+            config.push(format!("rename:{name}={rename}"));
+        }
+
+        if !type_override.derive.is_empty() {
+            config.push(format!("derive:{name}={}", type_override.derive.join(",")));
+        }
+
+        if !type_override.attributes.is_empty() {
+            let attributes: Vec<String> = type_override
+                .attributes
+                .iter()
+                .map(|attribute| {
+                    if attribute.starts_with('#') {
+                        attribute.clone()
+                    } else {
+                        format!("#[{attribute}]")
+                    }
+                })
+                .collect();
+
+            config.push(format!("attribute:{name}={}", attributes.join(" ")));
+        }
+
+        if type_override.must_use {
+            config.push(format!("must-use:{name}"));
+        }
+
+        if type_override.hidden {
+            config.push(format!("hidden:{name}"));
+        }
+
+        if type_override.opaque {
+            config.push(format!("opaque:{name}"));
+        }
+
+        if type_override.exclude {
+            config.push(format!("exclude:{name}"));
+        }
+    }
+
+    if !config.is_empty() {
+        args.push("--config".to_string());
+        args.extend(config);
+    }
+
+    Ok(args)
+}
+
+impl Options {
+    fn into_args(self) -> Vec<String> {
+        let flags = [
+            ("package", self.package),
+            ("flatten", self.flatten),
+            ("sys", self.sys),
+            ("implement", self.implement),
+            ("minimal", self.minimal),
+            ("no-inner-attributes", self.no_inner_attributes),
+            ("no-bindgen-comment", self.no_bindgen_comment),
+            ("vtbl", self.vtbl),
+            ("docs", self.docs),
+            ("decode-flags", self.decode_flags),
+            ("rust-enums", self.rust_enums),
+            ("raw-dylib", self.raw_dylib),
+            ("split", self.split),
+            ("classify", self.classify),
+            ("json", self.json),
+        ];
+
+        let mut args: Vec<String> = flags
+            .into_iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| name.to_string())
+            .collect();
+
+        if let Some(rustfmt_config) = self.rustfmt_config {
+            args.push(format!("rustfmt-config={rustfmt_config}"));
+        }
+
+        if let Some(explain) = self.explain {
+            args.push(format!("explain={explain}"));
+        }
+
+        match self.dialect {
+            Some(Dialect::Winrt) => args.push("type=winrt".to_string()),
+            Some(Dialect::Win32) => args.push("type=win32".to_string()),
+            None => {}
+        }
+
+        args
+    }
+}
@@ -0,0 +1,565 @@
+use super::*;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Serialize)]
+struct Report {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    breaking: Option<bool>,
+    namespaces: BTreeMap<String, Vec<Change>>,
+}
+
+#[derive(Serialize)]
+struct Change {
+    kind: &'static str,
+    change: &'static str,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    old: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    breaking: Option<bool>,
+}
+
+// The parts of an item that matter for compatibility, rendered as text so that items from different readers can be
+// compared directly.
+#[derive(Default)]
+struct Snapshot<'a> {
+    kind: &'static str,
+    // The underlying type of an enum, the signature of a function, or the type of a constant.
+    ty: String,
+    // The value of a constant.
+    value: Option<String>,
+    guid: Option<String>,
+    base: Option<String>,
+    interfaces: Vec<String>,
+    // Methods in vtable order along with their signatures.
+    methods: Vec<(&'a str, String)>,
+    // Struct fields along with their types or enum values along with their values.
+    fields: Vec<(&'a str, String)>,
+}
+
+/// Compares two sets of metadata and reports the added, removed, and changed items per namespace as JSON.
+pub fn from_readers(
+    old: &metadata::Reader<'_>,
+    new: &metadata::Reader<'_>,
+    mut config: BTreeMap<&str, &str>,
+) -> Result<String> {
+    let classify = config.remove("classify").is_some();
+
+    if let Some((key, _)) = config.first_key_value() {
+        return Err(Error::new(&format!("invalid configuration value `{key}`")));
+    }
+
+    let mut report = Report {
+        breaking: None,
+        namespaces: changes(old, new),
+    };
+
+    if classify {
+        report.breaking = Some(
+            report
+                .namespaces
+                .values()
+                .flatten()
+                .any(|change| change.breaking == Some(true)),
+        );
+    } else {
+        report
+            .namespaces
+            .values_mut()
+            .flatten()
+            .for_each(|change| change.breaking = None);
+    }
+
+    // Unwrapping is fine since the report only contains strings and maps with string keys.
+    Ok(serde_json::to_string_pretty(&report).unwrap())
+}
+
+/// Checks that the `new` metadata is a compatible version of the `old` metadata according to the WinRT versioning
+/// rules, returning an error for each violation.
+pub fn check_compat(
+    old: &metadata::Reader<'_>,
+    new: &metadata::Reader<'_>,
+    config: BTreeMap<&str, &str>,
+) -> Result<()> {
+    if let Some((key, _)) = config.first_key_value() {
+        return Err(Error::new(&format!("invalid configuration value `{key}`")));
+    }
+
+    // Beyond the changes that are breaking in general, a shipped interface is immutable so even appending a method
+    // requires a new interface.
+    let violations = changes(old, new)
+        .into_iter()
+        .flat_map(|(namespace, changes)| {
+            changes
+                .into_iter()
+                .filter(|change| {
+                    change.breaking == Some(true)
+                        || (change.kind == "method" && change.change == "added")
+                })
+                .map(move |change| Error::new(&change.describe(&namespace)))
+        });
+
+    Error::combine(violations)
+}
+
+fn changes(
+    old: &metadata::Reader<'_>,
+    new: &metadata::Reader<'_>,
+) -> BTreeMap<String, Vec<Change>> {
+    // Both readers share the same filter.
+    let namespaces: BTreeSet<&str> = old
+        .namespaces()
+        .chain(new.namespaces())
+        .filter(|namespace| old.includes_namespace(namespace))
+        .collect();
+
+    let mut results = BTreeMap::new();
+
+    for namespace in namespaces {
+        let mut changes = vec![];
+        let old_items = snapshots(old, namespace);
+        let mut new_items = snapshots(new, namespace);
+
+        for (name, old) in old_items {
+            if let Some(new) = new_items.remove(name) {
+                compare(&mut changes, name, &old, &new);
+            } else {
+                changes.push(Change::removed(old.kind, name, None));
+            }
+        }
+
+        for (name, new) in new_items {
+            changes.push(Change::added(new.kind, name, None, false));
+        }
+
+        if !changes.is_empty() {
+            results.insert(namespace.to_string(), changes);
+        }
+    }
+
+    results
+}
+
+impl Change {
+    fn describe(&self, namespace: &str) -> String {
+        let kind = self.kind;
+        let name = format!("{namespace}.{}", self.name);
+
+        match (self.change, &self.old, &self.new) {
+            ("added", _, Some(new)) if kind == "interface" => {
+                format!("interface `{new}` was added to `{name}`")
+            }
+            ("removed", Some(old), _) if kind == "interface" => {
+                format!("interface `{old}` was removed from `{name}`")
+            }
+            ("added", _, _) => format!("{kind} `{name}` was added"),
+            ("removed", _, _) => format!("{kind} `{name}` was removed"),
+            ("moved", Some(old), Some(new)) => {
+                format!("{kind} `{name}` moved from position {old} to {new}")
+            }
+            (_, old, new) => format!(
+                "{kind} `{name}` changed from `{}` to `{}`",
+                old.as_deref().unwrap_or("none"),
+                new.as_deref().unwrap_or("none")
+            ),
+        }
+    }
+
+    fn added(kind: &'static str, name: &str, new: Option<&str>, breaking: bool) -> Self {
+        Self {
+            kind,
+            change: "added",
+            name: name.to_string(),
+            old: None,
+            new: new.map(str::to_string),
+            breaking: Some(breaking),
+        }
+    }
+
+    fn removed(kind: &'static str, name: &str, old: Option<&str>) -> Self {
+        Self {
+            kind,
+            change: "removed",
+            name: name.to_string(),
+            old: old.map(str::to_string),
+            new: None,
+            breaking: Some(true),
+        }
+    }
+
+    fn changed(kind: &'static str, name: &str, old: &str, new: &str, breaking: bool) -> Self {
+        Self {
+            kind,
+            change: "changed",
+            name: name.to_string(),
+            old: Some(old.to_string()),
+            new: Some(new.to_string()),
+            breaking: Some(breaking),
+        }
+    }
+
+    fn moved(kind: &'static str, name: &str, old: usize, new: usize) -> Self {
+        Self {
+            kind,
+            change: "moved",
+            name: name.to_string(),
+            old: Some(old.to_string()),
+            new: Some(new.to_string()),
+            breaking: Some(true),
+        }
+    }
+}
+
+fn compare(changes: &mut Vec<Change>, name: &str, old: &Snapshot<'_>, new: &Snapshot<'_>) {
+    if old.kind != new.kind {
+        changes.push(Change::changed("type", name, old.kind, new.kind, true));
+        return;
+    }
+
+    let kind = old.kind;
+
+    if old.ty != new.ty {
+        changes.push(Change::changed(kind, name, &old.ty, &new.ty, true));
+    }
+
+    if old.value != new.value {
+        changes.push(Change {
+            kind,
+            change: "changed",
+            name: name.to_string(),
+            old: old.value.clone(),
+            new: new.value.clone(),
+            breaking: Some(true),
+        });
+    }
+
+    if old.guid != new.guid {
+        changes.push(Change {
+            kind: "guid",
+            change: "changed",
+            name: name.to_string(),
+            old: old.guid.clone(),
+            new: new.guid.clone(),
+            breaking: Some(true),
+        });
+    }
+
+    if old.base != new.base {
+        changes.push(Change {
+            kind: "base",
+            change: "changed",
+            name: name.to_string(),
+            old: old.base.clone(),
+            new: new.base.clone(),
+            breaking: Some(true),
+        });
+    }
+
+    for interface in &old.interfaces {
+        if !new.interfaces.contains(interface) {
+            changes.push(Change::removed("interface", name, Some(interface)));
+        }
+    }
+
+    for interface in &new.interfaces {
+        if !old.interfaces.contains(interface) {
+            // A class may implement additional interfaces but the interfaces required by an interface are fixed.
+            changes.push(Change::added(
+                "interface",
+                name,
+                Some(interface),
+                kind != "class",
+            ));
+        }
+    }
+
+    compare_methods(changes, name, &old.methods, &new.methods);
+
+    let field_kind = if kind == "enum" { "value" } else { "field" };
+    compare_fields(changes, field_kind, name, &old.fields, &new.fields);
+}
+
+// Methods are matched by name and, since overloads may share a name, then by order. Methods may only be appended to
+// the end of the vtable, so any other addition is breaking.
+fn compare_methods(
+    changes: &mut Vec<Change>,
+    name: &str,
+    old: &[(&str, String)],
+    new: &[(&str, String)],
+) {
+    let mut matched = vec![false; new.len()];
+
+    for (old_slot, (method, old_sig)) in old.iter().enumerate() {
+        let member = format!("{name}.{method}");
+
+        let Some(new_slot) = (0..new.len()).find(|slot| !matched[*slot] && new[*slot].0 == *method)
+        else {
+            changes.push(Change::removed("method", &member, Some(old_sig)));
+            continue;
+        };
+
+        matched[new_slot] = true;
+        let new_sig = &new[new_slot].1;
+
+        if old_sig != new_sig {
+            changes.push(Change::changed("method", &member, old_sig, new_sig, true));
+        }
+
+        if old_slot != new_slot {
+            changes.push(Change::moved("method", &member, old_slot, new_slot));
+        }
+    }
+
+    for (new_slot, (method, new_sig)) in new.iter().enumerate() {
+        if !matched[new_slot] {
+            changes.push(Change::added(
+                "method",
+                &format!("{name}.{method}"),
+                Some(new_sig),
+                new_slot < old.len(),
+            ));
+        }
+    }
+}
+
+// Any change to a struct's fields affects its layout, while enums may gain new values without breaking anything.
+fn compare_fields(
+    changes: &mut Vec<Change>,
+    kind: &'static str,
+    name: &str,
+    old: &[(&str, String)],
+    new: &[(&str, String)],
+) {
+    let is_struct = kind == "field";
+
+    for (old_position, (field, old_value)) in old.iter().enumerate() {
+        let member = format!("{name}.{field}");
+
+        let Some(new_position) = new.iter().position(|(new_field, _)| new_field == field) else {
+            changes.push(Change::removed(kind, &member, Some(old_value)));
+            continue;
+        };
+
+        let new_value = &new[new_position].1;
+
+        if old_value != new_value {
+            changes.push(Change::changed(kind, &member, old_value, new_value, true));
+        }
+
+        if is_struct && old_position != new_position {
+            changes.push(Change::moved(kind, &member, old_position, new_position));
+        }
+    }
+
+    for (field, new_value) in new {
+        if !old.iter().any(|(old_field, _)| old_field == field) {
+            changes.push(Change::added(
+                kind,
+                &format!("{name}.{field}"),
+                Some(new_value),
+                is_struct,
+            ));
+        }
+    }
+}
+
+fn snapshots<'a>(
+    reader: &'a metadata::Reader<'a>,
+    namespace: &str,
+) -> BTreeMap<&'a str, Snapshot<'a>> {
+    let mut snapshots = BTreeMap::new();
+
+    for item in reader.namespace_items(namespace) {
+        let (name, snapshot) = match item {
+            metadata::Item::Type(def) => (def.name(), type_def(def)),
+            metadata::Item::Fn(method, _) => (
+                method.name(),
+                Snapshot {
+                    kind: "function",
+                    ty: signature(method, &[], false),
+                    ..Default::default()
+                },
+            ),
+            metadata::Item::Const(field) => (
+                field.name(),
+                Snapshot {
+                    kind: "constant",
+                    ty: ty(&field.ty(None)),
+                    value: field.constant().map(|constant| value(&constant.value())),
+                    ..Default::default()
+                },
+            ),
+        };
+
+        // Multi-arch definitions share a name so only the first is compared.
+        snapshots.entry(name).or_insert(snapshot);
+    }
+
+    snapshots
+}
+
+fn type_def(def: metadata::TypeDef<'_>) -> Snapshot<'_> {
+    let generics = metadata::type_def_generics(def);
+
+    let guid = metadata::type_def_guid(def).map(|guid| format!("{guid:?}"));
+
+    let interfaces = def
+        .interface_impls()
+        .map(|imp| ty(&imp.ty(&generics)))
+        .collect();
+
+    match def.kind() {
+        metadata::TypeKind::Interface => Snapshot {
+            kind: "interface",
+            guid,
+            interfaces,
+            methods: methods(def, &generics),
+            ..Default::default()
+        },
+        metadata::TypeKind::Delegate => Snapshot {
+            kind: "delegate",
+            guid,
+            methods: methods(def, &generics),
+            ..Default::default()
+        },
+        metadata::TypeKind::Class => Snapshot {
+            kind: "class",
+            base: def.extends().map(|base| base.to_string()),
+            interfaces,
+            ..Default::default()
+        },
+        metadata::TypeKind::Enum => Snapshot {
+            kind: "enum",
+            ty: ty(&def.underlying_type()),
+            fields: def
+                .fields()
+                .filter_map(|field| {
+                    field
+                        .constant()
+                        .map(|constant| (field.name(), value(&constant.value())))
+                })
+                .collect(),
+            ..Default::default()
+        },
+        metadata::TypeKind::Struct => Snapshot {
+            kind: "struct",
+            guid,
+            fields: def
+                .fields()
+                .filter(|field| !field.flags().contains(metadata::FieldAttributes::Literal))
+                .map(|field| (field.name(), ty(&field.ty(Some(def)))))
+                .collect(),
+            ..Default::default()
+        },
+    }
+}
+
+fn methods<'a>(
+    def: metadata::TypeDef<'a>,
+    generics: &[metadata::Type<'a>],
+) -> Vec<(&'a str, String)> {
+    def.methods()
+        .map(|method| (method.name(), signature(method, generics, false)))
+        .collect()
+}
+
+// Signatures are compared without parameter names since renaming a parameter doesn't affect compatibility.
+pub fn signature(
+    method: metadata::MethodDef<'_>,
+    generics: &[metadata::Type<'_>],
+    names: bool,
+) -> String {
+    let sig = method.signature(generics);
+
+    let params: Vec<String> = method
+        .params()
+        .filter(|param| param.sequence() != 0)
+        .zip(&sig.params)
+        .map(|(param, param_ty)| {
+            let mut text = String::new();
+
+            if names {
+                text.push_str(param.name());
+                text.push_str(": ");
+            }
+
+            if param.flags().contains(metadata::ParamAttributes::Out) {
+                text.push_str("out ");
+            }
+
+            text.push_str(&ty(param_ty));
+            text
+        })
+        .collect();
+
+    if sig.return_type == metadata::Type::Void {
+        format!("({})", params.join(", "))
+    } else {
+        format!("({}) -> {}", params.join(", "), ty(&sig.return_type))
+    }
+}
+
+pub fn ty(ty: &metadata::Type<'_>) -> String {
+    use metadata::Type;
+
+    match ty {
+        Type::Void => "void".to_string(),
+        Type::Bool => "bool".to_string(),
+        Type::Char => "char".to_string(),
+        Type::I8 => "i8".to_string(),
+        Type::U8 => "u8".to_string(),
+        Type::I16 => "i16".to_string(),
+        Type::U16 => "u16".to_string(),
+        Type::I32 => "i32".to_string(),
+        Type::U32 => "u32".to_string(),
+        Type::I64 => "i64".to_string(),
+        Type::U64 => "u64".to_string(),
+        Type::F32 => "f32".to_string(),
+        Type::F64 => "f64".to_string(),
+        Type::ISize => "isize".to_string(),
+        Type::USize => "usize".to_string(),
+        Type::String => "String".to_string(),
+        Type::Object => "Object".to_string(),
+        Type::Name(name) | Type::Const(name) => name.to_string(),
+        Type::GenericParam(param) => param.name().to_string(),
+        Type::TypeDef(def, generics) if generics.is_empty() => def.type_name().to_string(),
+        Type::TypeDef(def, generics) => format!(
+            "{}<{}>",
+            def.type_name(),
+            generics.iter().map(self::ty).collect::<Vec<_>>().join(", ")
+        ),
+        Type::MutPtr(ty, pointers) => format!("{}{}", "*mut ".repeat(*pointers), self::ty(ty)),
+        Type::ConstPtr(ty, pointers) => {
+            format!("{}{}", "*const ".repeat(*pointers), self::ty(ty))
+        }
+        Type::Win32Array(ty, len) => format!("[{}; {len}]", self::ty(ty)),
+        Type::WinrtArray(ty) => format!("[{}]", self::ty(ty)),
+        Type::WinrtArrayRef(ty) => format!("&mut [{}]", self::ty(ty)),
+        Type::ConstRef(ty) => format!("&{}", self::ty(ty)),
+        Type::PrimitiveOrEnum(_, ty) => self::ty(ty),
+    }
+}
+
+pub fn value(value: &metadata::Value<'_>) -> String {
+    use metadata::Value;
+
+    match value {
+        Value::Bool(value) => value.to_string(),
+        Value::U8(value) => value.to_string(),
+        Value::I8(value) => value.to_string(),
+        Value::U16(value) => value.to_string(),
+        Value::I16(value) => value.to_string(),
+        Value::U32(value) => value.to_string(),
+        Value::I32(value) => value.to_string(),
+        Value::U64(value) => value.to_string(),
+        Value::I64(value) => value.to_string(),
+        Value::F32(value) => value.to_string(),
+        Value::F64(value) => value.to_string(),
+        Value::String(value) => format!("{value:?}"),
+        Value::TypeName(value) => value.to_string(),
+        Value::EnumDef(_, value) => self::value(value),
+    }
+}
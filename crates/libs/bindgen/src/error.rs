@@ -2,11 +2,25 @@
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// An error object consists of both an error message and file and line information.
+///
+/// Compiling a single file may produce more than one error, in which case the remaining errors are reported along
+/// with the first.
 #[derive(Default, Debug)]
 pub struct Error {
     message: String,
     path: String,
-    span: Option<(usize, usize)>,
+    span: Option<Span>,
+    errors: Vec<Error>,
+}
+
+#[derive(Default, Debug)]
+struct Span {
+    line: usize,
+    column: usize,
+    // A zero width underlines the remainder of the line for spans that cover multiple lines.
+    width: usize,
+    // The offending line of source text, if available.
+    source: String,
 }
 
 impl std::error::Error for Error {}
@@ -19,25 +33,44 @@ impl From<Error> for std::io::Error {
 
 impl From<syn::Error> for Error {
     fn from(error: syn::Error) -> Self {
-        let start = error.span().start();
-        Self {
-            message: error.to_string(),
-            span: Some((start.line, start.column)),
-            ..Self::default()
-        }
+        // A `syn::Error` may itself combine any number of errors.
+        let mut errors = error.into_iter().map(|error| {
+            let start = error.span().start();
+            let end = error.span().end();
+
+            Self {
+                message: error.to_string(),
+                span: Some(Span {
+                    line: start.line,
+                    // Lines are 1-based while columns are 0-based.
+                    column: start.column + 1,
+                    width: if start.line == end.line {
+                        end.column.saturating_sub(start.column)
+                    } else {
+                        0
+                    },
+                    source: String::new(),
+                }),
+                ..Self::default()
+            }
+        });
+
+        // Unwrapping is fine as a `syn::Error` always has at least one message.
+        let mut first = errors.next().unwrap();
+        first.errors.extend(errors);
+        first
     }
 }
 
 impl std::fmt::Display for Error {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(fmt, "error: {}", self.message)?;
-        if !self.path.is_empty() {
-            if let Some((line, column)) = self.span {
-                writeln!(fmt, "  --> {}:{line}:{column}", self.path)?;
-            } else {
-                writeln!(fmt, "  --> {}", self.path)?;
-            }
+        self.fmt_one(fmt)?;
+
+        for error in &self.errors {
+            writeln!(fmt)?;
+            error.fmt_one(fmt)?;
         }
+
         Ok(())
     }
 }
@@ -53,7 +86,107 @@ impl Error {
     pub(crate) fn with_path(self, path: &str) -> Self {
         Self {
             path: path.to_string(),
+            errors: self
+                .errors
+                .into_iter()
+                .map(|error| error.with_path(path))
+                .collect(),
             ..self
         }
     }
+
+    /// Creates an error that points at the given byte range of the source text.
+    pub(crate) fn with_range(message: &str, source: &str, range: std::ops::Range<usize>) -> Self {
+        let start = range.start.min(source.len());
+        let line_start = source[..start].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = source[start..]
+            .find('\n')
+            .map_or(source.len(), |index| start + index);
+        let end = range.end.clamp(start, line_end);
+
+        Self {
+            message: message.to_string(),
+            span: Some(Span {
+                line: source[..start].matches('\n').count() + 1,
+                column: source[line_start..start].chars().count() + 1,
+                width: source[start..end].chars().count(),
+                source: source[line_start..line_end].trim_end().to_string(),
+            }),
+            ..Self::default()
+        }
+    }
+
+    /// Combines any number of errors, such as those from different files, into a single error.
+    pub(crate) fn combine<I: IntoIterator<Item = Self>>(errors: I) -> Result<()> {
+        let mut errors = errors.into_iter();
+
+        let Some(mut first) = errors.next() else {
+            return Ok(());
+        };
+
+        for mut error in errors {
+            let rest = std::mem::take(&mut error.errors);
+            first.errors.push(error);
+            first.errors.extend(rest);
+        }
+
+        Err(first)
+    }
+
+    /// Attaches the offending line of source text so that it may be displayed along with the error.
+    pub(crate) fn with_source(self, source: &str) -> Self {
+        Self {
+            span: self.span.map(|span| Span {
+                source: source
+                    .lines()
+                    .nth(span.line.saturating_sub(1))
+                    .unwrap_or_default()
+                    .trim_end()
+                    .to_string(),
+                ..span
+            }),
+            errors: self
+                .errors
+                .into_iter()
+                .map(|error| error.with_source(source))
+                .collect(),
+            ..self
+        }
+    }
+
+    fn fmt_one(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(fmt, "error: {}", self.message)?;
+
+        if !self.path.is_empty() {
+            if let Some(span) = &self.span {
+                writeln!(fmt, "  --> {}:{}:{}", self.path, span.line, span.column)?;
+            } else {
+                writeln!(fmt, "  --> {}", self.path)?;
+            }
+        }
+
+        if let Some(span) = self.span.as_ref().filter(|span| !span.source.is_empty()) {
+            let gutter = " ".repeat(span.line.to_string().len());
+
+            // Tabs are preserved so that the caret lines up with the offending text.
+            let indent: String = span
+                .source
+                .chars()
+                .take(span.column - 1)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+
+            let width = if span.width == 0 {
+                span.source.chars().count().saturating_sub(span.column - 1)
+            } else {
+                span.width
+            };
+
+            writeln!(fmt, "{gutter} |")?;
+            writeln!(fmt, "{} | {}", span.line, span.source)?;
+            writeln!(fmt, "{gutter} | {indent}{}", "^".repeat(width.max(1)))?;
+        }
+
+        Ok(())
+    }
 }
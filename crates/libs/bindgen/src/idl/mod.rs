@@ -0,0 +1,814 @@
+use super::*;
+use metadata::HasAttributes;
+use std::collections::BTreeMap;
+
+pub fn from_reader(
+    reader: &metadata::Reader<'_>,
+    mut config: BTreeMap<&str, &str>,
+    output: &str,
+) -> Result<()> {
+    let mut writer = Writer::new(reader);
+    writer.no_bindgen_comment = config.remove("no-bindgen-comment").is_some();
+
+    if let Some((key, _)) = config.first_key_value() {
+        return Err(Error::new(&format!("invalid configuration value `{key}`")));
+    }
+
+    writer.file()?;
+    write_to_file(output, writer.out).map_err(|err| err.with_path(output))
+}
+
+// Like the C header, MIDL is written as plain text since its syntax doesn't round-trip through Rust token trees.
+// Only WinRT types are written as MIDL 3.0 has no way to express Win32 functions, constants, or unions.
+struct Writer<'a> {
+    reader: &'a metadata::Reader<'a>,
+    namespace: &'a str,
+    no_bindgen_comment: bool,
+    out: String,
+}
+
+// MIDL resolves names in declaration order within a file so the items in each namespace are written by kind,
+// with contracts and value types ahead of the delegates, interfaces, and classes that refer to them.
+#[derive(Default)]
+struct Items<'a> {
+    contracts: Vec<metadata::TypeDef<'a>>,
+    enums: Vec<metadata::TypeDef<'a>>,
+    structs: Vec<metadata::TypeDef<'a>>,
+    attributes: Vec<metadata::TypeDef<'a>>,
+    delegates: Vec<metadata::TypeDef<'a>>,
+    interfaces: Vec<metadata::TypeDef<'a>>,
+    classes: Vec<metadata::TypeDef<'a>>,
+}
+
+impl<'a> Items<'a> {
+    fn iter(&self) -> impl Iterator<Item = &metadata::TypeDef<'a>> {
+        self.contracts
+            .iter()
+            .chain(&self.enums)
+            .chain(&self.structs)
+            .chain(&self.attributes)
+            .chain(&self.delegates)
+            .chain(&self.interfaces)
+            .chain(&self.classes)
+    }
+}
+
+// The interfaces of a class that are folded into its body, with the static interfaces paired with their version.
+#[derive(Default)]
+struct Synthesized<'a> {
+    default: Option<metadata::TypeDef<'a>>,
+    statics: Vec<(metadata::TypeDef<'a>, String)>,
+}
+
+impl<'a> Writer<'a> {
+    fn new(reader: &'a metadata::Reader<'a>) -> Self {
+        Self {
+            reader,
+            namespace: "",
+            no_bindgen_comment: false,
+            out: String::new(),
+        }
+    }
+
+    fn line(&mut self, value: &str) {
+        self.out.push_str(value);
+        self.out.push('\n');
+    }
+
+    fn file(&mut self) -> Result<()> {
+        if !self.no_bindgen_comment {
+            let name = std::env!("CARGO_PKG_NAME");
+            let version = std::env!("CARGO_PKG_VERSION");
+            self.line(&format!("// Bindings generated by `{name}` {version}"));
+        }
+
+        let tree = Tree::new(self.reader);
+
+        for tree in tree.flatten() {
+            let items = self.collect(tree.namespace)?;
+
+            if items.iter().next().is_none() {
+                continue;
+            }
+
+            self.namespace = tree.namespace;
+
+            if !self.out.is_empty() {
+                self.line("");
+            }
+
+            self.line(&format!("namespace {}", tree.namespace));
+            self.line("{");
+
+            for (position, def) in items.iter().enumerate() {
+                if position > 0 {
+                    self.line("");
+                }
+
+                self.type_def(*def)?;
+            }
+
+            self.line("}");
+        }
+
+        Ok(())
+    }
+
+    fn collect(&self, namespace: &str) -> Result<Items<'a>> {
+        let mut items = Items::default();
+
+        for item in self.reader.namespace_items(namespace) {
+            let metadata::Item::Type(def) = item else {
+                continue;
+            };
+
+            if !def
+                .flags()
+                .contains(metadata::TypeAttributes::WindowsRuntime)
+                || self.is_synthesized(def)
+            {
+                continue;
+            }
+
+            // MIDL 3.0 has no syntax for declaring generic types. The generic interfaces and delegates in
+            // `Windows.Foundation` are declared by the SDK's own IDL and may only be referred to.
+            if def.generics().next().is_some() {
+                return Err(Error::new(&format!(
+                    "generic type `{}.{}` is not supported in MIDL",
+                    def.namespace(),
+                    def.name()
+                )));
+            }
+
+            match def.kind() {
+                metadata::TypeKind::Enum => items.enums.push(def),
+                metadata::TypeKind::Struct if def.has_attribute("ApiContractAttribute") => {
+                    items.contracts.push(def)
+                }
+                metadata::TypeKind::Struct => items.structs.push(def),
+                metadata::TypeKind::Delegate => items.delegates.push(def),
+                metadata::TypeKind::Interface => items.interfaces.push(def),
+                metadata::TypeKind::Class
+                    if def.extends() == Some(metadata::TypeName::Attribute) =>
+                {
+                    items.attributes.push(def)
+                }
+                metadata::TypeKind::Class => items.classes.push(def),
+            }
+        }
+
+        Ok(items)
+    }
+
+    fn type_def(&mut self, def: metadata::TypeDef<'a>) -> Result<()> {
+        self.attributes(def, "    ")?;
+
+        match def.kind() {
+            metadata::TypeKind::Enum => self.enum_def(def),
+            metadata::TypeKind::Struct if def.has_attribute("ApiContractAttribute") => {
+                self.line(&format!("    apicontract {}", def.name()));
+                self.line("    {");
+                self.line("    };");
+                Ok(())
+            }
+            metadata::TypeKind::Struct => self.struct_def(def, "struct"),
+            metadata::TypeKind::Delegate => self.delegate_def(def),
+            metadata::TypeKind::Interface => self.interface_def(def),
+            metadata::TypeKind::Class if def.extends() == Some(metadata::TypeName::Attribute) => {
+                self.attribute_def(def)
+            }
+            metadata::TypeKind::Class => self.class_def(def),
+        }
+    }
+
+    fn enum_def(&mut self, def: metadata::TypeDef<'a>) -> Result<()> {
+        let flags = def.has_attribute("FlagsAttribute");
+        self.line(&format!("    enum {}", def.name()));
+        self.line("    {");
+
+        for field in def.fields() {
+            if let Some(constant) = field.constant() {
+                let value = constant.value();
+
+                let value = match value {
+                    metadata::Value::U32(value) if flags => format!("0x{value:x}"),
+                    _ => self.value(&value)?,
+                };
+
+                self.attributes(field, "        ")?;
+                self.line(&format!("        {} = {value},", field.name()));
+            }
+        }
+
+        self.line("    };");
+        Ok(())
+    }
+
+    fn struct_def(&mut self, def: metadata::TypeDef<'a>, keyword: &str) -> Result<()> {
+        self.line(&format!("    {keyword} {}", def.name()));
+        self.line("    {");
+
+        for field in def.fields() {
+            let ty = self.type_name(&field.ty(Some(def)))?;
+            self.line(&format!("        {ty} {};", field.name()));
+        }
+
+        self.line("    };");
+        Ok(())
+    }
+
+    fn attribute_def(&mut self, def: metadata::TypeDef<'a>) -> Result<()> {
+        if def.fields().next().is_some() {
+            return self.struct_def(def, "attribute");
+        }
+
+        // Attributes that weren't authored in MIDL, like those in `Windows.Foundation.Metadata`, have constructors
+        // rather than fields so their parameters are declared as constructors.
+        self.line(&format!("    attribute {}", def.name()));
+        self.line("    {");
+
+        for method in def.methods() {
+            if method.name() == ".ctor" {
+                let params = self.params(method)?;
+                self.line(&format!("        {}({params});", def.name()));
+            }
+        }
+
+        self.line("    };");
+        Ok(())
+    }
+
+    fn delegate_def(&mut self, def: metadata::TypeDef<'a>) -> Result<()> {
+        let method = metadata::type_def_invoke_method(def);
+        let signature = self.signature(method, def.name())?;
+        self.line(&format!("    delegate {signature};"));
+        Ok(())
+    }
+
+    fn interface_def(&mut self, def: metadata::TypeDef<'a>) -> Result<()> {
+        let requires = def
+            .interface_impls()
+            .map(|imp| self.type_name(&imp.ty(&[])))
+            .collect::<Result<Vec<_>>>()?;
+
+        if requires.is_empty() {
+            self.line(&format!("    interface {}", def.name()));
+        } else {
+            self.line(&format!(
+                "    interface {} requires {}",
+                def.name(),
+                requires.join(", ")
+            ));
+        }
+
+        self.line("    {");
+        self.members(def, "", None)?;
+        self.line("    };");
+        Ok(())
+    }
+
+    fn class_def(&mut self, def: metadata::TypeDef<'a>) -> Result<()> {
+        let synthesized = self.synthesized(def);
+        let mut types = vec![];
+
+        for imp in def.interface_impls() {
+            let imp_ty = imp.ty(&[]);
+
+            if matches!(imp_ty, metadata::Type::TypeDef(ty, _) if Some(ty) == synthesized.default) {
+                continue;
+            }
+
+            let mut ty = self.type_name(&imp_ty)?;
+
+            for attribute in ["OverridableAttribute", "ProtectedAttribute"] {
+                if imp.has_attribute(attribute) {
+                    ty = format!("[{}] {ty}", attribute_name(attribute).unwrap_or_default());
+                }
+            }
+
+            // MIDL treats the first interface as the default interface.
+            if imp.has_attribute("DefaultAttribute") {
+                types.insert(0, ty);
+            } else {
+                types.push(ty);
+            }
+        }
+
+        if let Some(base) = def.extends() {
+            if base != metadata::TypeName::Object {
+                // MIDL only allows a class to derive from an unsealed class.
+                if self
+                    .reader
+                    .get_type_def(base.namespace(), base.name())
+                    .any(|base| base.flags().contains(metadata::TypeAttributes::Sealed))
+                {
+                    return Err(Error::new(&format!(
+                        "`{}.{}` cannot derive from sealed class `{base}`",
+                        def.namespace(),
+                        def.name()
+                    )));
+                }
+
+                types.insert(0, self.type_name(&metadata::Type::Name(base))?);
+            }
+        }
+
+        let keyword = if def.flags().contains(metadata::TypeAttributes::Sealed) {
+            "runtimeclass"
+        } else {
+            "unsealed runtimeclass"
+        };
+
+        if types.is_empty() {
+            self.line(&format!("    {keyword} {}", def.name()));
+        } else {
+            self.line(&format!(
+                "    {keyword} {} : {}",
+                def.name(),
+                types.join(", ")
+            ));
+        }
+
+        self.line("    {");
+
+        if let Some(default) = synthesized.default {
+            self.members(default, "", None)?;
+        }
+
+        for (statics, version) in &synthesized.statics {
+            self.members(*statics, "static ", Some(version))?;
+        }
+
+        self.line("    };");
+        Ok(())
+    }
+
+    // MIDL synthesizes a class's default and static interfaces from the members declared in the body of the
+    // runtimeclass, so interfaces that are exclusive to the class and named the way MIDL would name them are folded
+    // back into the class. Everything else is declared on its own and referred to by name.
+    fn synthesized(&self, def: metadata::TypeDef<'a>) -> Synthesized<'a> {
+        let mut synthesized = Synthesized::default();
+        let name = format!("I{}", def.name());
+
+        for imp in def.interface_impls() {
+            if !imp.has_attribute("DefaultAttribute") {
+                continue;
+            }
+
+            if let metadata::Type::TypeDef(interface, _) = imp.ty(&[]) {
+                if interface.name() == name && self.exclusive_to(interface) == Some(def) {
+                    synthesized.default = Some(interface);
+                }
+            }
+        }
+
+        for attribute in def.attributes() {
+            if attribute.name() != "StaticAttribute" {
+                continue;
+            }
+
+            // Statics that are only available on some platforms stay in the `static` attribute.
+            let version = match attribute.args().as_slice() {
+                [(_, metadata::Value::TypeName(_)), (_, metadata::Value::U32(version))] => {
+                    format!("version({version})")
+                }
+                [(_, metadata::Value::TypeName(_)), (_, metadata::Value::U32(version)), (_, metadata::Value::String(contract))] =>
+                {
+                    format!("contract({contract}, {})", contract_version(*version))
+                }
+                _ => continue,
+            };
+
+            let Some(interface) = self.type_arg(attribute) else {
+                continue;
+            };
+
+            let suffix = interface
+                .name()
+                .strip_prefix(&name)
+                .and_then(|name| name.strip_prefix("Statics"));
+
+            if suffix.is_some_and(|suffix| suffix.bytes().all(|byte| byte.is_ascii_digit()))
+                && self.exclusive_to(interface) == Some(def)
+            {
+                synthesized.statics.push((interface, version));
+            }
+        }
+
+        synthesized
+    }
+
+    fn is_synthesized(&self, interface: metadata::TypeDef<'a>) -> bool {
+        let Some(class) = self.exclusive_to(interface) else {
+            return false;
+        };
+
+        let synthesized = self.synthesized(class);
+
+        synthesized.default == Some(interface)
+            || synthesized
+                .statics
+                .iter()
+                .any(|(statics, _)| *statics == interface)
+    }
+
+    fn exclusive_to(&self, interface: metadata::TypeDef<'a>) -> Option<metadata::TypeDef<'a>> {
+        interface
+            .find_attribute("ExclusiveToAttribute")
+            .and_then(|attribute| self.type_arg(attribute))
+    }
+
+    // Returns the type definition named by the first argument of an attribute like `StaticAttribute`.
+    fn type_arg(&self, attribute: metadata::Attribute<'a>) -> Option<metadata::TypeDef<'a>> {
+        let Some((_, metadata::Value::TypeName(type_name))) = attribute.args().into_iter().next()
+        else {
+            return None;
+        };
+
+        self.reader
+            .get_type_def(type_name.namespace(), type_name.name())
+            .next()
+    }
+
+    fn members(
+        &mut self,
+        def: metadata::TypeDef<'a>,
+        modifier: &str,
+        version: Option<&str>,
+    ) -> Result<()> {
+        let methods: Vec<metadata::MethodDef<'a>> = def.methods().collect();
+        let mut methods = methods.iter().peekable();
+
+        while let Some(method) = methods.next() {
+            let name = method.name();
+
+            if !method
+                .flags()
+                .contains(metadata::MethodAttributes::SpecialName)
+            {
+                let declaration = self.signature(*method, name)?;
+                self.member(*method, modifier, version, &declaration)?;
+                continue;
+            }
+
+            let signature = method.signature(&[]);
+
+            // Property and event accessors are folded back into the MIDL declarations they came from while
+            // preserving the vtable order, which means a setter that doesn't follow its getter is declared on its own.
+            // Accessors without the expected parameter are declared as plain methods.
+            let declaration = if let Some(property) = name.strip_prefix("get_") {
+                let ty = self.type_name(&signature.return_type)?;
+
+                if methods
+                    .peek()
+                    .is_some_and(|next| next.name().strip_prefix("put_") == Some(property))
+                {
+                    methods.next();
+                    format!("{ty} {property}")
+                } else {
+                    format!("{ty} {property} {{ get; }}")
+                }
+            } else if let (Some(property), Some(ty)) =
+                (name.strip_prefix("put_"), signature.params.first())
+            {
+                format!("{} {property} {{ set; }}", self.type_name(ty)?)
+            } else if let (Some(event), Some(ty)) =
+                (name.strip_prefix("add_"), signature.params.first())
+            {
+                format!("event {} {event}", self.type_name(ty)?)
+            } else if name.starts_with("remove_") {
+                continue;
+            } else {
+                self.signature(*method, name)?
+            };
+
+            self.member(*method, modifier, version, &declaration)?;
+        }
+
+        Ok(())
+    }
+
+    // Members folded into a class from a static interface carry the version of that interface themselves.
+    fn member(
+        &mut self,
+        method: metadata::MethodDef<'a>,
+        modifier: &str,
+        version: Option<&str>,
+        declaration: &str,
+    ) -> Result<()> {
+        self.attributes(method, "        ")?;
+
+        if let Some(version) = version {
+            self.line(&format!("        [{version}]"));
+        }
+
+        self.line(&format!("        {modifier}{declaration};"));
+        Ok(())
+    }
+
+    fn signature(&self, method: metadata::MethodDef<'a>, name: &str) -> Result<String> {
+        let return_type = match &method.signature(&[]).return_type {
+            metadata::Type::WinrtArray(ty) => format!("{}[]", self.type_name(ty)?),
+            ty => self.type_name(ty)?,
+        };
+
+        Ok(format!("{return_type} {name}({})", self.params(method)?))
+    }
+
+    fn params(&self, method: metadata::MethodDef<'a>) -> Result<String> {
+        let signature = method.signature(&[]);
+        let mut types = signature.params.iter();
+        let mut params = vec![];
+
+        for param in method.params() {
+            if param.sequence() == 0 {
+                continue;
+            }
+
+            let Some(ty) = types.next() else {
+                break;
+            };
+
+            let name = param.name();
+            // Parameters without the `Out` flag are inputs, which includes those produced from RDL that have no
+            // flags at all.
+            let input = !param.flags().contains(metadata::ParamAttributes::Out);
+
+            params.push(match ty {
+                metadata::Type::WinrtArray(ty) if input => {
+                    format!("{}[] {name}", self.type_name(ty)?)
+                }
+                metadata::Type::WinrtArray(ty) => format!("ref {}[] {name}", self.type_name(ty)?),
+                metadata::Type::WinrtArrayRef(ty) => {
+                    format!("out {}[] {name}", self.type_name(ty)?)
+                }
+                metadata::Type::ConstRef(ty) => {
+                    format!("ref const {} {name}", self.type_name(ty)?)
+                }
+                _ if input => format!("{} {name}", self.type_name(ty)?),
+                _ => format!("out {} {name}", self.type_name(ty)?),
+            });
+        }
+
+        Ok(params.join(", "))
+    }
+
+    fn attributes<R: HasAttributes<'a> + Copy>(&mut self, row: R, indent: &str) -> Result<()> {
+        // MIDL has no notion of documentation so any docs carried over from RDL are written as comments.
+        if let Some(docs) = metadata::row_docs(row) {
+            for line in docs.lines() {
+                self.line(format!("{indent}// {line}").trim_end());
+            }
+        }
+
+        for attribute in row.attributes() {
+            if let Some(attribute) = self.attribute(attribute)? {
+                self.line(&format!("{indent}[{attribute}]"));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn attribute(&self, attribute: metadata::Attribute<'a>) -> Result<Option<String>> {
+        // Static interfaces that are folded into the class are implied by its `static` members.
+        if attribute.name() == "StaticAttribute"
+            && self
+                .type_arg(attribute)
+                .is_some_and(|def| self.is_synthesized(def))
+        {
+            return Ok(None);
+        }
+
+        let args = attribute.args();
+
+        let name = match attribute.name() {
+            "GuidAttribute" => {
+                return Ok(Some(format!(
+                    "uuid({:?})",
+                    metadata::Guid::from_args(&args)
+                )));
+            }
+            "ContractVersionAttribute" if args.len() == 1 => "contractversion".to_string(),
+            "ContractVersionAttribute" => "contract".to_string(),
+            name => match attribute_name(name) {
+                Some("") => return Ok(None),
+                Some(name) => name.to_string(),
+                None => {
+                    let type_name = attribute.type_name();
+                    let name = type_name.name();
+                    let name = name.strip_suffix("Attribute").unwrap_or(name);
+
+                    if type_name.namespace() == self.namespace {
+                        name.to_string()
+                    } else {
+                        format!("{}.{name}", type_name.namespace())
+                    }
+                }
+            },
+        };
+
+        if args.is_empty() {
+            return Ok(Some(name));
+        }
+
+        // Versions that follow a contract are encoded with the major version in the high word.
+        let mut contract = name == "contractversion";
+        let mut values = vec![];
+
+        for (arg_name, value) in &args {
+            let value = match value {
+                metadata::Value::U32(version) if contract => {
+                    contract = false;
+                    contract_version(*version)
+                }
+                metadata::Value::TypeName(type_name) => {
+                    contract = self.is_contract(type_name.namespace(), type_name.name());
+                    self.type_name(&metadata::Type::Name(*type_name))?
+                }
+                metadata::Value::String(value) if name == "contract" => {
+                    contract = true;
+                    value.clone()
+                }
+                metadata::Value::EnumDef(def, value) => enum_value(*def, value)?,
+                value => self.value(value)?,
+            };
+
+            if arg_name.is_empty() {
+                values.push(value);
+            } else {
+                values.push(format!("{arg_name} = {value}"));
+            }
+        }
+
+        Ok(Some(format!("{name}({})", values.join(", "))))
+    }
+
+    fn is_contract(&self, namespace: &str, name: &str) -> bool {
+        self.reader
+            .get_type_def(namespace, name)
+            .any(|def| def.has_attribute("ApiContractAttribute"))
+    }
+
+    fn type_name(&self, ty: &metadata::Type<'a>) -> Result<String> {
+        Ok(match ty {
+            metadata::Type::Void => "void".to_string(),
+            metadata::Type::Bool => "Boolean".to_string(),
+            metadata::Type::Char => "Char".to_string(),
+            metadata::Type::I8 => "Int8".to_string(),
+            metadata::Type::U8 => "UInt8".to_string(),
+            metadata::Type::I16 => "Int16".to_string(),
+            metadata::Type::U16 => "UInt16".to_string(),
+            metadata::Type::I32 => "Int32".to_string(),
+            metadata::Type::U32 => "UInt32".to_string(),
+            metadata::Type::I64 => "Int64".to_string(),
+            metadata::Type::U64 => "UInt64".to_string(),
+            metadata::Type::F32 => "Single".to_string(),
+            metadata::Type::F64 => "Double".to_string(),
+            metadata::Type::String => "String".to_string(),
+            metadata::Type::Object => "Object".to_string(),
+            metadata::Type::Name(metadata::TypeName::GUID) => "Guid".to_string(),
+            metadata::Type::Name(metadata::TypeName::Type) => "Type".to_string(),
+            metadata::Type::Name(type_name) => {
+                self.scoped_name(type_name.namespace(), type_name.name())
+            }
+            metadata::Type::TypeDef(def, generics) => {
+                let name = self.scoped_name(def.namespace(), def.name());
+
+                if generics.is_empty() {
+                    name
+                } else {
+                    let name = name.split_once('`').map_or(name.as_str(), |(name, _)| name);
+                    let generics = generics
+                        .iter()
+                        .map(|ty| self.type_name(ty))
+                        .collect::<Result<Vec<_>>>()?;
+                    format!("{name}<{}>", generics.join(", "))
+                }
+            }
+            metadata::Type::GenericParam(generic) => generic.name().to_string(),
+            metadata::Type::WinrtArray(ty)
+            | metadata::Type::WinrtArrayRef(ty)
+            | metadata::Type::ConstRef(ty)
+            | metadata::Type::PrimitiveOrEnum(_, ty) => self.type_name(ty)?,
+            rest => {
+                return Err(Error::new(&format!(
+                    "type `{rest:?}` is not supported in MIDL"
+                )))
+            }
+        })
+    }
+
+    fn scoped_name(&self, namespace: &str, name: &str) -> String {
+        if namespace == self.namespace {
+            name.to_string()
+        } else {
+            format!("{namespace}.{name}")
+        }
+    }
+
+    fn value(&self, value: &metadata::Value<'a>) -> Result<String> {
+        Ok(match value {
+            metadata::Value::Bool(value) => value.to_string(),
+            metadata::Value::U8(value) => value.to_string(),
+            metadata::Value::I8(value) => value.to_string(),
+            metadata::Value::U16(value) => value.to_string(),
+            metadata::Value::I16(value) => value.to_string(),
+            metadata::Value::U32(value) => value.to_string(),
+            metadata::Value::I32(value) => value.to_string(),
+            metadata::Value::U64(value) => value.to_string(),
+            metadata::Value::I64(value) => value.to_string(),
+            metadata::Value::F32(value) => value.to_string(),
+            metadata::Value::F64(value) => value.to_string(),
+            metadata::Value::String(value) => format!("{value:?}"),
+            metadata::Value::TypeName(type_name) => {
+                self.scoped_name(type_name.namespace(), type_name.name())
+            }
+            metadata::Value::EnumDef(def, value) => enum_value(*def, value)?,
+        })
+    }
+}
+
+// Returns the MIDL spelling of well-known attributes, or an empty string for those implied by MIDL syntax.
+fn attribute_name(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "ActivatableAttribute" => "activatable",
+        "AllowForWebAttribute" => "allowforweb",
+        "AllowMultipleAttribute" => "allowmultiple",
+        "ApiContractAttribute" => "",
+        "AttributeUsageAttribute" => "attributeusage",
+        "ComposableAttribute" => "composable",
+        "DefaultAttribute" => "default",
+        "DefaultOverloadAttribute" => "default_overload",
+        "DeprecatedAttribute" => "deprecated",
+        "DocumentationAttribute" => "",
+        "DualApiPartitionAttribute" => "dualapipartition",
+        "ExclusiveToAttribute" => "exclusiveto",
+        "ExperimentalAttribute" => "experimental",
+        "FlagsAttribute" => "flags",
+        "GCPressureAttribute" => "gc_pressure",
+        "HasVariantAttribute" => "hasvariant",
+        "InternalInterfaceAttribute" => "internal",
+        "LengthIsAttribute" => "length_is",
+        "MarshalingBehaviorAttribute" => "marshaling_behavior",
+        "MuseAttribute" => "muse",
+        "NoExceptionAttribute" => "noexcept",
+        "OverloadAttribute" => "method_name",
+        "OverridableAttribute" => "overridable",
+        "PlatformAttribute" => "platform",
+        "ProtectedAttribute" => "protected",
+        "RangeAttribute" => "range",
+        "RemoteAsyncAttribute" => "remote_async",
+        "SizeIsAttribute" => "size_is",
+        "StaticAttribute" => "static",
+        "ThreadingAttribute" => "threading",
+        "VariantAttribute" => "variant",
+        "VersionAttribute" => "version",
+        "WebHostHiddenAttribute" => "webhosthidden",
+        _ => return None,
+    })
+}
+
+// MIDL spells enum arguments like `ThreadingModel.Both` as the lowercase field name, e.g. `threading(both)`.
+fn enum_value<'a>(def: metadata::TypeDef<'a>, value: &metadata::Value<'a>) -> Result<String> {
+    let value = integer(value)?;
+
+    for field in def.fields() {
+        if let Some(constant) = field.constant() {
+            if integer(&constant.value())? == value {
+                return Ok(field.name().to_lowercase());
+            }
+        }
+    }
+
+    Ok(value.to_string())
+}
+
+fn integer<'a>(value: &metadata::Value<'a>) -> Result<i64> {
+    Ok(match value {
+        metadata::Value::U8(value) => *value as i64,
+        metadata::Value::I8(value) => *value as i64,
+        metadata::Value::U16(value) => *value as i64,
+        metadata::Value::I16(value) => *value as i64,
+        metadata::Value::U32(value) => *value as i64,
+        metadata::Value::I32(value) => *value as i64,
+        metadata::Value::U64(value) => *value as i64,
+        metadata::Value::I64(value) => *value,
+        rest => {
+            return Err(Error::new(&format!(
+                "value `{rest:?}` is not supported as an enum value in MIDL"
+            )))
+        }
+    })
+}
+
+fn contract_version(version: u32) -> String {
+    let major = version >> 16;
+    let minor = version & 0xFFFF;
+
+    if minor == 0 {
+        major.to_string()
+    } else {
+        format!("{major}.{minor}")
+    }
+}
@@ -3,7 +3,14 @@ Learn more about Rust for Windows here: <https://github.com/microsoft/windows-rs
 */
 
 mod args;
+mod builder;
+mod c;
+mod cache;
+mod config_file;
+mod diff;
 mod error;
+mod idl;
+mod list;
 mod metadata;
 mod rdl;
 mod rust;
@@ -11,6 +18,7 @@ mod tokens;
 mod tree;
 mod winmd;
 
+pub use builder::Bindgen;
 pub use error::{Error, Result};
 use tree::Tree;
 
@@ -20,6 +28,10 @@ enum ArgKind {
     Output,
     Filter,
     Config,
+    Diff,
+    CheckCompat,
+    List,
+    Cache,
 }
 
 /// Windows metadata compiler.
@@ -33,11 +45,16 @@ where
 
     let mut kind = ArgKind::None;
     let mut output = None;
+    let mut cache = None;
     let mut input = Vec::<&str>::new();
+    let mut diff = Vec::<&str>::new();
+    let mut compat = Vec::<&str>::new();
+    let mut query = Vec::<&str>::new();
     let mut include = Vec::<&str>::new();
     let mut exclude = Vec::<&str>::new();
     let mut config = std::collections::BTreeMap::<&str, &str>::new();
     let mut format = false;
+    let mut list = false;
 
     for arg in &args {
         if arg.starts_with('-') {
@@ -48,8 +65,15 @@ where
             ArgKind::None => match arg.as_str() {
                 "-i" | "--in" => kind = ArgKind::Input,
                 "-o" | "--out" => kind = ArgKind::Output,
+                "--cache" => kind = ArgKind::Cache,
                 "-f" | "--filter" => kind = ArgKind::Filter,
                 "--config" => kind = ArgKind::Config,
+                "--diff" => kind = ArgKind::Diff,
+                "--check-compat" => kind = ArgKind::CheckCompat,
+                "--list" => {
+                    kind = ArgKind::List;
+                    list = true;
+                }
                 "--format" => format = true,
                 _ => return Err(Error::new(&format!("invalid option `{arg}`"))),
             },
@@ -60,7 +84,23 @@ where
                     return Err(Error::new("too many outputs"));
                 }
             }
+            ArgKind::Cache => {
+                if cache.is_none() {
+                    cache = Some(arg.as_str());
+                } else {
+                    return Err(Error::new("too many cache paths"));
+                }
+            }
             ArgKind::Input => input.push(arg.as_str()),
+            ArgKind::Diff => diff.push(arg.as_str()),
+            ArgKind::CheckCompat => compat.push(arg.as_str()),
+            ArgKind::List => {
+                if query.is_empty() {
+                    query.push(arg.as_str());
+                } else {
+                    return Err(Error::new("too many queries"));
+                }
+            }
             ArgKind::Filter => {
                 if let Some(rest) = arg.strip_prefix('!') {
                     exclude.push(rest);
@@ -101,6 +141,57 @@ where
         return Ok(String::new());
     }
 
+    if list {
+        if output.is_some() || !diff.is_empty() || !compat.is_empty() {
+            return Err(Error::new(
+                "`--list` cannot be combined with `--out`, `--diff`, or `--check-compat`",
+            ));
+        }
+
+        let input = read_input(&input)?;
+        let reader = metadata::Reader::filter(input, &include, &exclude, &config);
+        return list::from_reader(&reader, config, query.first().copied());
+    }
+
+    if !compat.is_empty() {
+        if output.is_some() || !diff.is_empty() {
+            return Err(Error::new(
+                "`--check-compat` cannot be combined with `--out` or `--diff`",
+            ));
+        }
+
+        // The `--in` metadata is checked against the previously shipped metadata.
+        let old = read_input_files(&compat)?;
+        let new = read_input_files(&input)?;
+        let old = metadata::Reader::filter(old, &include, &exclude, &config);
+        let new = metadata::Reader::filter(new, &include, &exclude, &config);
+        diff::check_compat(&old, &new, config)?;
+
+        return Ok("  Finished checking compatibility".to_string());
+    }
+
+    if !diff.is_empty() {
+        // Only the inputs themselves are compared so the default metadata isn't loaded.
+        let old = read_input_files(&input)?;
+        let new = read_input_files(&diff)?;
+        let old = metadata::Reader::filter(old, &include, &exclude, &config);
+        let new = metadata::Reader::filter(new, &include, &exclude, &config);
+        let json = diff::from_readers(&old, &new, config)?;
+
+        let Some(output) = output else {
+            return Ok(json);
+        };
+
+        let output = canonicalize(output)?;
+
+        if extension(&output) != "json" {
+            return Err(Error::new("`--diff` output extension must be json"));
+        }
+
+        write_to_file(&output, json)?;
+        return Ok(format!("  Finished writing `{output}`"));
+    }
+
     let Some(output) = output else {
         return Err(Error::new("no output"));
     };
@@ -113,14 +204,37 @@ where
 
     let output = canonicalize(output)?;
 
+    // The output is only regenerated if the inputs, filter, or config have changed since the cache was written.
+    let hash = if let Some(cache) = cache {
+        let hash = cache::hash(&args, &filter_input(&input, &["winmd", "rdl"])?)?;
+
+        if cache::is_current(cache, &output, &hash) {
+            return Ok(format!("  Skipped writing `{output}` as it is up to date"));
+        }
+
+        Some((cache, hash))
+    } else {
+        None
+    };
+
     let input = read_input(&input)?;
     let reader = metadata::Reader::filter(input, &include, &exclude, &config);
 
     match extension(&output) {
-        "rdl" => rdl::from_reader(reader, config, &output)?,
-        "winmd" => winmd::from_reader(reader, config, &output)?,
-        "rs" => rust::from_reader(reader, config, &output)?,
-        _ => return Err(Error::new("output extension must be one of winmd/rdl/rs")),
+        "rdl" => rdl::from_reader(&reader, config, &output)?,
+        "winmd" => winmd::from_reader(&reader, config, &output)?,
+        "rs" => rust::from_reader(&reader, config, &output)?,
+        "h" => c::from_reader(&reader, config, &output)?,
+        "idl" => idl::from_reader(&reader, config, &output)?,
+        _ => {
+            return Err(Error::new(
+                "output extension must be one of winmd/rdl/rs/h/idl",
+            ))
+        }
+    }
+
+    if let Some((cache, hash)) = hash {
+        cache::write(cache, &hash)?;
     }
 
     let elapsed = time.elapsed().as_secs_f32();
@@ -136,6 +250,58 @@ where
     }
 }
 
+/// Returns the files and directories that [`bindgen`] reads for the given arguments, including any `--etc` files.
+///
+/// This is useful for build scripts that need to emit `cargo:rerun-if-changed` lines:
+///
+/// ```no_run
+/// let args = ["--etc", "bindings.txt"];
+///
+/// for path in windows_bindgen::inputs(args).unwrap() {
+///     println!("cargo:rerun-if-changed={path}");
+/// }
+///
+/// windows_bindgen::bindgen(args).unwrap();
+/// ```
+pub fn inputs<I, S>(args: I) -> Result<Vec<String>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let (args, mut results) = args::expand_with_files(args)?;
+    let mut is_input = false;
+
+    for arg in args {
+        if arg.starts_with('-') {
+            is_input = matches!(arg.as_str(), "-i" | "--in" | "--diff" | "--check-compat");
+        } else if is_input && !results.contains(&arg) {
+            results.push(arg);
+        }
+    }
+
+    Ok(results)
+}
+
+// Generates Rust bindings in memory for the `Bindgen` builder.
+fn generate(
+    input: &[&str],
+    include: &[&str],
+    exclude: &[&str],
+    config: std::collections::BTreeMap<&str, &str>,
+) -> Result<String> {
+    if include.is_empty() {
+        return Err(Error::new("at least one `--filter` must be specified"));
+    }
+
+    // The configuration is checked before the input is read so that an invalid combination is reported as such
+    // rather than as a failure to read the input.
+    rust::check_config(&config, "")?;
+
+    let input = read_input(input)?;
+    let reader = metadata::Reader::filter(input, include, exclude, &config);
+    rust::to_string(&reader, config)
+}
+
 fn filter_input(input: &[&str], extensions: &[&str]) -> Result<Vec<String>> {
     fn try_push(path: &str, extensions: &[&str], results: &mut Vec<String>) -> Result<()> {
         // First canonicalize input so that the extension check below will match the case of the path.
@@ -176,34 +342,54 @@ fn filter_input(input: &[&str], extensions: &[&str]) -> Result<Vec<String>> {
     Ok(results)
 }
 
-fn read_input(input: &[&str]) -> Result<Vec<metadata::File>> {
+fn read_input(input: &[&str]) -> Result<Vec<metadata::File<'static>>> {
     let input = filter_input(input, &["winmd", "rdl"])?;
     let mut results = vec![];
 
+    // The default metadata is embedded in the binary and read in place rather than copied.
     if cfg!(feature = "metadata") {
         results.push(
-            metadata::File::new(std::include_bytes!("../default/Windows.winmd").to_vec()).unwrap(),
+            metadata::File::new(&std::include_bytes!("../default/Windows.winmd")[..]).unwrap(),
         );
         results.push(
-            metadata::File::new(std::include_bytes!("../default/Windows.Win32.winmd").to_vec())
+            metadata::File::new(&std::include_bytes!("../default/Windows.Win32.winmd")[..])
                 .unwrap(),
         );
         results.push(
-            metadata::File::new(std::include_bytes!("../default/Windows.Wdk.winmd").to_vec())
-                .unwrap(),
+            metadata::File::new(&std::include_bytes!("../default/Windows.Wdk.winmd")[..]).unwrap(),
         );
     } else if input.is_empty() {
         return Err(Error::new("no inputs"));
     }
 
-    for input in &input {
-        let file = if extension(input) == "winmd" {
-            read_winmd_file(input)?
+    results.extend(read_metadata_files(&input)?);
+    Ok(results)
+}
+
+fn read_input_files(input: &[&str]) -> Result<Vec<metadata::File<'static>>> {
+    let input = filter_input(input, &["winmd", "rdl"])?;
+
+    if input.is_empty() {
+        return Err(Error::new("no inputs"));
+    }
+
+    read_metadata_files(&input)
+}
+
+fn read_metadata_files(input: &[String]) -> Result<Vec<metadata::File<'static>>> {
+    let mut results = vec![];
+    let mut rdl = vec![];
+
+    for input in input {
+        if extension(input) == "winmd" {
+            results.push(read_winmd_file(input)?);
         } else {
-            read_rdl_file(input)?
-        };
+            rdl.push(input.as_str());
+        }
+    }
 
-        results.push(file);
+    if !rdl.is_empty() {
+        results.push(read_rdl_files(&rdl)?);
     }
 
     Ok(results)
@@ -230,28 +416,43 @@ fn read_file_lines(path: &str) -> Result<Vec<String>> {
     Ok(lines)
 }
 
-fn read_rdl_file(path: &str) -> Result<metadata::File> {
-    read_file_text(path)
-        .and_then(|source| rdl::File::parse_str(&source))
-        .and_then(|file| file.into_winmd())
-        .map(|bytes| {
-            // TODO: Write bytes to file if you need to debug the intermediate .winmd file like so:
-            _ = write_to_file("temp.winmd", &bytes);
+// All of the .rdl files are compiled together so that they may refer to each other's types.
+fn read_rdl_files(paths: &[&str]) -> Result<metadata::File<'static>> {
+    let mut files = vec![];
+    let mut errors = vec![];
 
-            // Unwrapping here is fine since `rdl_to_winmd` should have produced a valid winmd
-            metadata::File::new(bytes).unwrap()
-        })
-        .map_err(|err| err.with_path(path))
+    for path in paths {
+        match rdl::File::parse_file(path) {
+            Ok(file) => files.push(file),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    Error::combine(errors)?;
+
+    rdl::compile(files).map(|bytes| {
+        // The intermediate .winmd is kept in memory. Write `bytes` to a file here if you need to debug it, but don't
+        // commit that since this also runs inside the `windows_bindings!` macro.
+
+        // Unwrapping here is fine since `rdl_to_winmd` should have produced a valid winmd
+        metadata::File::new(bytes).unwrap()
+    })
 }
 
-fn read_winmd_file(path: &str) -> Result<metadata::File> {
+fn read_winmd_file(path: &str) -> Result<metadata::File<'static>> {
     read_file_bytes(path).and_then(|bytes| {
-        metadata::File::new(bytes)
-            .ok_or_else(|| Error::new("failed to read .winmd format").with_path(path))
+        metadata::File::new(bytes).map_err(|error| {
+            Error::new(&format!("failed to read .winmd format: {error}")).with_path(path)
+        })
     })
 }
 
 fn write_to_file<C: AsRef<[u8]>>(path: &str, contents: C) -> Result<()> {
+    // Leaving an unchanged file alone avoids invalidating anything that depends on its modification time.
+    if std::fs::read(path).is_ok_and(|existing| existing == contents.as_ref()) {
+        return Ok(());
+    }
+
     if let Some(parent) = std::path::Path::new(path).parent() {
         std::fs::create_dir_all(parent)
             .map_err(|_| Error::new("failed to create directory").with_path(path))?;
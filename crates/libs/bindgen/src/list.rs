@@ -0,0 +1,349 @@
+use super::*;
+use diff::{signature, ty, value};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+struct TypeInfo {
+    kind: &'static str,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    guid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    base: Option<String>,
+    interfaces: Vec<String>,
+    members: Vec<Member>,
+}
+
+#[derive(Serialize)]
+struct Member {
+    kind: &'static str,
+    name: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    signature: String,
+}
+
+/// Answers a query about the metadata, such as the types in a namespace or the members of a type, as text or JSON.
+///
+/// * no query lists the namespaces
+/// * a namespace lists the items in that namespace
+/// * a fully qualified name lists the members of that item
+/// * `uses=` followed by a fully qualified type name lists the members that refer to that type
+pub fn from_reader(
+    reader: &metadata::Reader<'_>,
+    mut config: BTreeMap<&str, &str>,
+    query: Option<&str>,
+) -> Result<String> {
+    let json = config.remove("json").is_some();
+
+    if let Some((key, _)) = config.first_key_value() {
+        return Err(Error::new(&format!("invalid configuration value `{key}`")));
+    }
+
+    let Some(query) = query else {
+        let namespaces: Vec<&str> = reader
+            .namespaces()
+            .filter(|namespace| reader.namespace_items(namespace).next().is_some())
+            .collect();
+
+        return Ok(if json {
+            to_json(&namespaces)
+        } else {
+            namespaces.join("\n")
+        });
+    };
+
+    if let Some(type_name) = query.strip_prefix("uses=") {
+        let members = uses(reader, type_name);
+        return Ok(if json {
+            to_json(&members)
+        } else {
+            members
+                .iter()
+                .map(Member::to_text)
+                .collect::<Vec<_>>()
+                .join("\n")
+        });
+    }
+
+    if reader.namespaces().any(|namespace| namespace == query) {
+        let members: Vec<Member> = reader.namespace_items(query).map(item_member).collect();
+
+        return Ok(if json {
+            to_json(&members)
+        } else {
+            members
+                .iter()
+                .map(Member::to_text)
+                .collect::<Vec<_>>()
+                .join("\n")
+        });
+    }
+
+    let Some((namespace, name)) = query.rsplit_once('.') else {
+        return Err(Error::new(&format!("`{query}` not found")));
+    };
+
+    let Some(item) = reader
+        .namespace_items(namespace)
+        .find(|item| item_name(item) == name)
+    else {
+        return Err(Error::new(&format!("`{query}` not found")));
+    };
+
+    let info = match item {
+        metadata::Item::Type(def) => type_info(def),
+        metadata::Item::Fn(..) | metadata::Item::Const(_) => {
+            let member = item_member(item);
+
+            TypeInfo {
+                kind: member.kind,
+                name: query.to_string(),
+                guid: None,
+                base: None,
+                interfaces: vec![],
+                members: vec![member],
+            }
+        }
+    };
+
+    Ok(if json { to_json(&info) } else { info.to_text() })
+}
+
+impl TypeInfo {
+    fn to_text(&self) -> String {
+        let mut lines = vec![format!("{} {}", self.kind, self.name)];
+
+        if let Some(guid) = &self.guid {
+            lines.push(format!("  guid {guid}"));
+        }
+
+        if let Some(base) = &self.base {
+            lines.push(format!("  extends {base}"));
+        }
+
+        for interface in &self.interfaces {
+            lines.push(format!("  implements {interface}"));
+        }
+
+        for member in &self.members {
+            lines.push(format!("  {}", member.to_text()));
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl Member {
+    fn to_text(&self) -> String {
+        let Self {
+            kind,
+            name,
+            signature,
+        } = self;
+
+        match *kind {
+            "method" | "function" => format!("fn {name}{signature}"),
+            "field" => format!("{name}: {signature}"),
+            "value" => format!("{name} = {signature}"),
+            "constant" => format!("const {name}: {signature}"),
+            _ => format!("{kind} {name}"),
+        }
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> String {
+    // Unwrapping is fine since the output only contains strings and sequences.
+    serde_json::to_string_pretty(value).unwrap()
+}
+
+fn item_name<'a>(item: &metadata::Item<'a>) -> &'a str {
+    match item {
+        metadata::Item::Type(def) => def.name(),
+        metadata::Item::Fn(method, _) => method.name(),
+        metadata::Item::Const(field) => field.name(),
+    }
+}
+
+fn item_member(item: metadata::Item<'_>) -> Member {
+    match item {
+        metadata::Item::Type(def) => Member {
+            kind: kind(def),
+            name: def.name().to_string(),
+            signature: String::new(),
+        },
+        metadata::Item::Fn(method, _) => Member {
+            kind: "function",
+            name: method.name().to_string(),
+            signature: signature(method, &[], true),
+        },
+        metadata::Item::Const(field) => Member {
+            kind: "constant",
+            name: field.name().to_string(),
+            signature: constant(field),
+        },
+    }
+}
+
+fn kind(def: metadata::TypeDef<'_>) -> &'static str {
+    match def.kind() {
+        metadata::TypeKind::Interface => "interface",
+        metadata::TypeKind::Class => "class",
+        metadata::TypeKind::Enum => "enum",
+        metadata::TypeKind::Struct => "struct",
+        metadata::TypeKind::Delegate => "delegate",
+    }
+}
+
+fn constant(field: metadata::Field<'_>) -> String {
+    let field_ty = ty(&field.ty(None));
+
+    match field.constant() {
+        Some(constant) => format!("{field_ty} = {}", value(&constant.value())),
+        None => field_ty,
+    }
+}
+
+fn type_info(def: metadata::TypeDef<'_>) -> TypeInfo {
+    let generics = metadata::type_def_generics(def);
+    let kind = kind(def);
+
+    let members = match def.kind() {
+        metadata::TypeKind::Interface | metadata::TypeKind::Delegate => def
+            .methods()
+            .map(|method| Member {
+                kind: "method",
+                name: method.name().to_string(),
+                signature: signature(method, &generics, true),
+            })
+            .collect(),
+        metadata::TypeKind::Enum => def
+            .fields()
+            .filter_map(|field| {
+                field.constant().map(|constant| Member {
+                    kind: "value",
+                    name: field.name().to_string(),
+                    signature: value(&constant.value()),
+                })
+            })
+            .collect(),
+        metadata::TypeKind::Struct => def
+            .fields()
+            .map(|field| {
+                if field.flags().contains(metadata::FieldAttributes::Literal) {
+                    Member {
+                        kind: "constant",
+                        name: field.name().to_string(),
+                        signature: constant(field),
+                    }
+                } else {
+                    Member {
+                        kind: "field",
+                        name: field.name().to_string(),
+                        signature: ty(&field.ty(Some(def))),
+                    }
+                }
+            })
+            .collect(),
+        metadata::TypeKind::Class => vec![],
+    };
+
+    TypeInfo {
+        kind,
+        name: def.type_name().to_string(),
+        guid: metadata::type_def_guid(def).map(|guid| format!("{guid:?}")),
+        base: if kind == "class" {
+            def.extends().map(|base| base.to_string())
+        } else {
+            None
+        },
+        interfaces: def
+            .interface_impls()
+            .map(|imp| ty(&imp.ty(&generics)))
+            .collect(),
+        members,
+    }
+}
+
+// Finds the methods, functions, and fields that refer to the given type, whether directly or through pointers,
+// arrays, or generic arguments.
+fn uses(reader: &metadata::Reader<'_>, type_name: &str) -> Vec<Member> {
+    let mut members = vec![];
+
+    for item in reader.items() {
+        match item {
+            metadata::Item::Type(def) => {
+                let generics = metadata::type_def_generics(def);
+                let parent = def.type_name();
+
+                for method in def.methods() {
+                    let sig = method.signature(&generics);
+
+                    if std::iter::once(&sig.return_type)
+                        .chain(&sig.params)
+                        .any(|ty| refers_to(ty, type_name))
+                    {
+                        members.push(Member {
+                            kind: "method",
+                            name: format!("{parent}.{}", method.name()),
+                            signature: signature(method, &generics, true),
+                        });
+                    }
+                }
+
+                if matches!(def.kind(), metadata::TypeKind::Struct) {
+                    for field in def.fields() {
+                        let field_ty = field.ty(Some(def));
+
+                        if refers_to(&field_ty, type_name) {
+                            members.push(Member {
+                                kind: "field",
+                                name: format!("{parent}.{}", field.name()),
+                                signature: ty(&field_ty),
+                            });
+                        }
+                    }
+                }
+            }
+            metadata::Item::Fn(method, namespace) => {
+                let sig = method.signature(&[]);
+
+                if std::iter::once(&sig.return_type)
+                    .chain(&sig.params)
+                    .any(|ty| refers_to(ty, type_name))
+                {
+                    members.push(Member {
+                        kind: "function",
+                        name: format!("{namespace}.{}", method.name()),
+                        signature: signature(method, &[], true),
+                    });
+                }
+            }
+            metadata::Item::Const(_) => {}
+        }
+    }
+
+    members
+}
+
+fn refers_to(ty: &metadata::Type<'_>, type_name: &str) -> bool {
+    use metadata::Type;
+
+    match ty {
+        Type::Name(name) | Type::Const(name) => name.to_string() == type_name,
+        Type::TypeDef(def, generics) => {
+            def.type_name().to_string() == type_name
+                || generics.iter().any(|ty| refers_to(ty, type_name))
+        }
+        Type::MutPtr(ty, _)
+        | Type::ConstPtr(ty, _)
+        | Type::Win32Array(ty, _)
+        | Type::WinrtArray(ty)
+        | Type::WinrtArrayRef(ty)
+        | Type::ConstRef(ty) => refers_to(ty, type_name),
+        Type::PrimitiveOrEnum(primitive, ty) => {
+            refers_to(primitive, type_name) || refers_to(ty, type_name)
+        }
+        _ => false,
+    }
+}
@@ -2,8 +2,8 @@ use std::collections::*;
 pub use windows_metadata::*;
 
 #[derive(Clone)]
-pub struct Interface {
-    pub ty: Type,
+pub struct Interface<'a> {
+    pub ty: Type<'a>,
     pub kind: InterfaceKind,
 }
 
@@ -47,16 +47,16 @@ pub enum SignatureParamKind {
     Other,
 }
 
-pub struct Signature {
-    pub def: MethodDef,
-    pub params: Vec<SignatureParam>,
-    pub return_type: Type,
+pub struct Signature<'a> {
+    pub def: MethodDef<'a>,
+    pub params: Vec<SignatureParam<'a>>,
+    pub return_type: Type<'a>,
     pub call_flags: MethodCallAttributes,
 }
 
-pub struct SignatureParam {
-    pub def: Param,
-    pub ty: Type,
+pub struct SignatureParam<'a> {
+    pub def: Param<'a>,
+    pub ty: Type<'a>,
     pub kind: SignatureParamKind,
 }
 
@@ -85,20 +85,20 @@ pub struct Guid(
 );
 
 impl Guid {
-    pub fn from_args(args: &[(&str, Value)]) -> Self {
-        fn unwrap_u32(value: &Value) -> u32 {
+    pub fn from_args(args: &[(&str, Value<'_>)]) -> Self {
+        fn unwrap_u32(value: &Value<'_>) -> u32 {
             match value {
                 Value::U32(value) => *value,
                 rest => unimplemented!("{rest:?}"),
             }
         }
-        fn unwrap_u16(value: &Value) -> u16 {
+        fn unwrap_u16(value: &Value<'_>) -> u16 {
             match value {
                 Value::U16(value) => *value,
                 rest => unimplemented!("{rest:?}"),
             }
         }
-        fn unwrap_u8(value: &Value) -> u8 {
+        fn unwrap_u8(value: &Value<'_>) -> u8 {
             match value {
                 Value::U8(value) => *value,
                 rest => unimplemented!("{rest:?}"),
@@ -158,7 +158,7 @@ impl SignatureParamKind {
     }
 }
 
-impl SignatureParam {
+impl SignatureParam<'_> {
     pub fn is_convertible(&self) -> bool {
         !self.def.flags().contains(ParamAttributes::Out)
             && !self.ty.is_winrt_array()
@@ -203,7 +203,7 @@ impl SignatureParam {
     }
 }
 
-impl Signature {
+impl Signature<'_> {
     pub fn kind(&self) -> SignatureKind {
         if self
             .def
@@ -261,26 +261,30 @@ impl Signature {
     }
 }
 
-pub fn type_def_invoke_method(row: TypeDef) -> MethodDef {
+pub fn type_def_invoke_method(row: TypeDef<'_>) -> MethodDef<'_> {
     row.methods()
         .find(|method| method.name() == "Invoke")
         .expect("`Invoke` method not found")
 }
 
-pub fn type_def_generics(def: TypeDef) -> Vec<Type> {
+pub fn type_def_generics(def: TypeDef<'_>) -> Vec<Type<'_>> {
     def.generics().map(Type::GenericParam).collect()
 }
 
 // TODO: namespace should not be required - it's a hack to accomodate Win32 metadata
 // TODO: this is very Rust-specific and Win32-metadata specific with all of its translation. Replace with literal signature parser that just returns slice of types.
-pub fn method_def_signature(namespace: &str, row: MethodDef, generics: &[Type]) -> Signature {
+pub fn method_def_signature<'a>(
+    namespace: &str,
+    row: MethodDef<'a>,
+    generics: &[Type<'a>],
+) -> Signature<'a> {
     let reader = row.reader();
     let mut blob = row.blob(4);
     let call_flags = MethodCallAttributes(blob.read_usize() as u8);
     let _param_count = blob.read_usize();
     let mut return_type = reader.type_from_blob(&mut blob, None, generics);
 
-    let mut params: Vec<SignatureParam> = row
+    let mut params: Vec<SignatureParam<'_>> = row
         .params()
         .filter_map(|param| {
             let param_is_const = param.has_attribute("ConstAttribute");
@@ -407,7 +411,7 @@ pub fn method_def_signature(namespace: &str, row: MethodDef, generics: &[Type])
     }
 }
 
-fn param_kind(row: Param) -> SignatureParamKind {
+fn param_kind(row: Param<'_>) -> SignatureParamKind {
     for attribute in row.attributes() {
         match attribute.name() {
             "NativeArrayInfoAttribute" => {
@@ -435,7 +439,7 @@ fn param_kind(row: Param) -> SignatureParamKind {
 }
 
 // TODO: this is a terribly broken Win32 metadata attribute - need to get rid of it.
-fn param_or_enum(row: Param) -> Option<String> {
+fn param_or_enum(row: Param<'_>) -> Option<String> {
     if row.flags().contains(ParamAttributes::Out) {
         return None;
     }
@@ -451,7 +455,7 @@ fn param_or_enum(row: Param) -> Option<String> {
         })
 }
 
-fn signature_param_is_query(params: &[SignatureParam]) -> Option<(usize, usize)> {
+fn signature_param_is_query(params: &[SignatureParam<'_>]) -> Option<(usize, usize)> {
     if let Some(guid) = params.iter().rposition(|param| {
         param.ty == Type::ConstPtr(Box::new(Type::Name(TypeName::GUID)), 1)
             && !param.def.flags().contains(ParamAttributes::Out)
@@ -467,7 +471,7 @@ fn signature_param_is_query(params: &[SignatureParam]) -> Option<(usize, usize)>
     None
 }
 
-fn method_def_last_error(row: MethodDef) -> bool {
+fn method_def_last_error(row: MethodDef<'_>) -> bool {
     if let Some(map) = row.impl_map() {
         map.flags().contains(PInvokeAttributes::SupportsLastError)
     } else {
@@ -475,11 +479,41 @@ fn method_def_last_error(row: MethodDef) -> bool {
     }
 }
 
-pub fn method_def_is_noexcept(method: MethodDef) -> bool {
+pub fn method_def_is_noexcept(method: MethodDef<'_>) -> bool {
     method.has_attribute("NoExceptionAttribute")
 }
 
-pub fn type_is_borrowed(ty: &Type) -> bool {
+/// Returns the documentation carried by a `DocumentationAttribute`, such as doc comments written in RDL.
+pub fn row_docs<'a, R: HasAttributes<'a>>(row: R) -> Option<String> {
+    let attribute = row.find_attribute("DocumentationAttribute")?;
+
+    match attribute.args().into_iter().next() {
+        Some((_, Value::String(docs))) => Some(docs),
+        _ => None,
+    }
+}
+
+/// Returns the documentation of a row as one `#[doc]` attribute per line, matching the attributes produced by `///`
+/// comments, for both RDL and Rust output.
+pub fn row_doc_attributes<'a, R: HasAttributes<'a>>(row: R) -> crate::tokens::TokenStream {
+    let mut tokens = crate::tokens::TokenStream::new();
+
+    if let Some(docs) = row_docs(row) {
+        for line in docs.lines() {
+            let line = if line.is_empty() {
+                String::new()
+            } else {
+                format!(" {line}")
+            };
+
+            tokens.combine(&format!("#[doc = {line:?}]").into());
+        }
+    }
+
+    tokens
+}
+
+pub fn type_is_borrowed(ty: &Type<'_>) -> bool {
     match ty {
         Type::TypeDef(row, _) => !type_def_is_blittable(*row),
         Type::Name(TypeName::BSTR)
@@ -494,7 +528,7 @@ pub fn type_is_borrowed(ty: &Type) -> bool {
     }
 }
 
-fn type_is_trivially_convertible(ty: &Type) -> bool {
+fn type_is_trivially_convertible(ty: &Type<'_>) -> bool {
     match ty {
         Type::TypeDef(row, _) => match row.kind() {
             TypeKind::Struct => type_def_is_handle(*row),
@@ -504,11 +538,11 @@ fn type_is_trivially_convertible(ty: &Type) -> bool {
     }
 }
 
-fn type_def_is_callback(row: TypeDef) -> bool {
+fn type_def_is_callback(row: TypeDef<'_>) -> bool {
     !row.flags().contains(TypeAttributes::WindowsRuntime) && row.kind() == TypeKind::Delegate
 }
 
-pub fn type_has_callback(ty: &Type) -> bool {
+pub fn type_has_callback(ty: &Type<'_>) -> bool {
     match ty {
         Type::TypeDef(row, _) => type_def_has_callback(*row),
         Type::Win32Array(ty, _) => type_has_callback(ty),
@@ -516,14 +550,14 @@ pub fn type_has_callback(ty: &Type) -> bool {
     }
 }
 
-pub fn type_def_has_callback(row: TypeDef) -> bool {
+pub fn type_def_has_callback(row: TypeDef<'_>) -> bool {
     if type_def_is_callback(row) {
         return true;
     }
     if row.kind() != TypeKind::Struct {
         return false;
     }
-    fn check(row: TypeDef) -> bool {
+    fn check(row: TypeDef<'_>) -> bool {
         if row
             .fields()
             .any(|field| type_has_callback(&field.ty(Some(row))))
@@ -548,14 +582,14 @@ pub fn type_def_has_callback(row: TypeDef) -> bool {
     }
 }
 
-pub fn type_def_has_float(def: TypeDef) -> bool {
+pub fn type_def_has_float(def: TypeDef<'_>) -> bool {
     def.kind() == TypeKind::Struct
         && def
             .fields()
             .any(|field| type_has_float(&field.ty(Some(def))))
 }
 
-pub fn type_has_float(ty: &Type) -> bool {
+pub fn type_has_float(ty: &Type<'_>) -> bool {
     match ty {
         Type::F32 | Type::F64 => true,
         Type::Win32Array(ty, _) => type_has_float(ty),
@@ -564,10 +598,10 @@ pub fn type_has_float(ty: &Type) -> bool {
     }
 }
 
-pub fn type_interfaces(ty: &Type) -> Vec<Interface> {
+pub fn type_interfaces<'a>(ty: &Type<'a>) -> Vec<Interface<'a>> {
     // TODO: collect into btree map and then return collected vec
     // This will both sort the results and should make finding dupes faster
-    fn walk(result: &mut Vec<Interface>, parent: &Type, is_base: bool) {
+    fn walk<'a>(result: &mut Vec<Interface<'a>>, parent: &Type<'a>, is_base: bool) {
         if let Type::TypeDef(row, generics) = parent {
             for mut child in type_def_interfaces(*row, generics) {
                 child.kind = if !is_base && child.kind == InterfaceKind::Default {
@@ -629,22 +663,22 @@ pub fn type_interfaces(ty: &Type) -> Vec<Interface> {
     result
 }
 
-fn type_name(ty: &Type) -> &str {
+fn type_name<'a>(ty: &Type<'a>) -> &'a str {
     match ty {
         Type::TypeDef(row, _) => row.name(),
         _ => "",
     }
 }
 
-pub fn field_is_blittable(row: Field, enclosing: TypeDef) -> bool {
+pub fn field_is_blittable(row: Field<'_>, enclosing: TypeDef<'_>) -> bool {
     type_is_blittable(&row.ty(Some(enclosing)))
 }
 
-pub fn field_is_copyable(row: Field, enclosing: TypeDef) -> bool {
+pub fn field_is_copyable(row: Field<'_>, enclosing: TypeDef<'_>) -> bool {
     type_is_copyable(&row.ty(Some(enclosing)))
 }
 
-pub fn type_is_blittable(ty: &Type) -> bool {
+pub fn type_is_blittable(ty: &Type<'_>) -> bool {
     match ty {
         Type::TypeDef(row, _) => type_def_is_blittable(*row),
         Type::String
@@ -660,7 +694,7 @@ pub fn type_is_blittable(ty: &Type) -> bool {
     }
 }
 
-fn type_is_copyable(ty: &Type) -> bool {
+fn type_is_copyable(ty: &Type<'_>) -> bool {
     match ty {
         Type::TypeDef(row, _) => type_def_is_copyable(*row),
         Type::String
@@ -676,7 +710,7 @@ fn type_is_copyable(ty: &Type) -> bool {
     }
 }
 
-pub fn type_def_is_blittable(row: TypeDef) -> bool {
+pub fn type_def_is_blittable(row: TypeDef<'_>) -> bool {
     match row.kind() {
         TypeKind::Struct => {
             if row.flags().contains(TypeAttributes::WindowsRuntime) {
@@ -691,7 +725,7 @@ pub fn type_def_is_blittable(row: TypeDef) -> bool {
     }
 }
 
-pub fn type_def_is_copyable(row: TypeDef) -> bool {
+pub fn type_def_is_copyable(row: TypeDef<'_>) -> bool {
     match row.kind() {
         TypeKind::Struct => row.fields().all(|field| field_is_copyable(field, row)),
         TypeKind::Enum => true,
@@ -700,11 +734,11 @@ pub fn type_def_is_copyable(row: TypeDef) -> bool {
     }
 }
 
-pub fn type_def_is_exclusive(row: TypeDef) -> bool {
+pub fn type_def_is_exclusive(row: TypeDef<'_>) -> bool {
     row.has_attribute("ExclusiveToAttribute")
 }
 
-pub fn type_is_struct(ty: &Type) -> bool {
+pub fn type_is_struct(ty: &Type<'_>) -> bool {
     // This check is used to detect virtual functions that return C-style PODs that affect how the stack is packed for x86.
     // It could be defined as a struct with more than one field but that check is complicated as it would have to detect
     // nested structs. Fortunately, this is rare enough that this check is sufficient.
@@ -715,7 +749,7 @@ pub fn type_is_struct(ty: &Type) -> bool {
     }
 }
 
-fn type_def_is_primitive(row: TypeDef) -> bool {
+fn type_def_is_primitive(row: TypeDef<'_>) -> bool {
     match row.kind() {
         TypeKind::Enum => true,
         TypeKind::Struct => type_def_is_handle(row),
@@ -724,7 +758,7 @@ fn type_def_is_primitive(row: TypeDef) -> bool {
     }
 }
 
-pub fn type_is_primitive(ty: &Type) -> bool {
+pub fn type_is_primitive(ty: &Type<'_>) -> bool {
     match ty {
         Type::TypeDef(row, _) => type_def_is_primitive(*row),
         Type::Bool
@@ -748,7 +782,7 @@ pub fn type_is_primitive(ty: &Type) -> bool {
     }
 }
 
-fn type_has_explicit_layout(ty: &Type) -> bool {
+fn type_has_explicit_layout(ty: &Type<'_>) -> bool {
     match ty {
         Type::TypeDef(row, _) => type_def_has_explicit_layout(*row),
         Type::Win32Array(ty, _) => type_has_explicit_layout(ty),
@@ -756,11 +790,11 @@ fn type_has_explicit_layout(ty: &Type) -> bool {
     }
 }
 
-pub fn type_def_has_explicit_layout(row: TypeDef) -> bool {
+pub fn type_def_has_explicit_layout(row: TypeDef<'_>) -> bool {
     if row.kind() != TypeKind::Struct {
         return false;
     }
-    fn check(row: TypeDef) -> bool {
+    fn check(row: TypeDef<'_>) -> bool {
         if row.flags().contains(TypeAttributes::ExplicitLayout) {
             return true;
         }
@@ -788,7 +822,7 @@ pub fn type_def_has_explicit_layout(row: TypeDef) -> bool {
     }
 }
 
-fn type_has_packing(ty: &Type) -> bool {
+fn type_has_packing(ty: &Type<'_>) -> bool {
     match ty {
         Type::TypeDef(row, _) => type_def_has_packing(*row),
         Type::Win32Array(ty, _) => type_has_packing(ty),
@@ -796,11 +830,11 @@ fn type_has_packing(ty: &Type) -> bool {
     }
 }
 
-pub fn type_def_has_packing(row: TypeDef) -> bool {
+pub fn type_def_has_packing(row: TypeDef<'_>) -> bool {
     if row.kind() != TypeKind::Struct {
         return false;
     }
-    fn check(row: TypeDef) -> bool {
+    fn check(row: TypeDef<'_>) -> bool {
         if row.class_layout().is_some() {
             return true;
         }
@@ -828,10 +862,10 @@ pub fn type_def_has_packing(row: TypeDef) -> bool {
     }
 }
 
-pub fn type_def_interfaces(
-    def: TypeDef,
-    generics: &[Type],
-) -> impl Iterator<Item = Interface> + '_ {
+pub fn type_def_interfaces<'a: 'b, 'b>(
+    def: TypeDef<'a>,
+    generics: &'b [Type<'a>],
+) -> impl Iterator<Item = Interface<'a>> + 'b {
     def.interface_impls().map(|imp| {
         let kind = if imp.has_attribute("DefaultAttribute") {
             InterfaceKind::Default
@@ -845,7 +879,7 @@ pub fn type_def_interfaces(
     })
 }
 
-pub fn type_def_default_interface(row: TypeDef) -> Option<Type> {
+pub fn type_def_default_interface(row: TypeDef<'_>) -> Option<Type<'_>> {
     type_def_interfaces(row, &[]).find_map(move |interface| {
         if interface.kind == InterfaceKind::Default {
             Some(interface.ty)
@@ -855,7 +889,7 @@ pub fn type_def_default_interface(row: TypeDef) -> Option<Type> {
     })
 }
 
-fn type_signature(ty: &Type) -> String {
+fn type_signature(ty: &Type<'_>) -> String {
     match ty {
         Type::Bool => "b1".to_string(),
         Type::Char => "c2".to_string(),
@@ -880,7 +914,7 @@ fn type_signature(ty: &Type) -> String {
     }
 }
 
-pub fn type_def_signature(row: TypeDef, generics: &[Type]) -> String {
+pub fn type_def_signature(row: TypeDef<'_>, generics: &[Type<'_>]) -> String {
     match row.kind() {
         TypeKind::Interface => type_def_interface_signature(row, generics),
         TypeKind::Class => {
@@ -918,7 +952,7 @@ pub fn type_def_signature(row: TypeDef, generics: &[Type]) -> String {
     }
 }
 
-fn type_def_interface_signature(row: TypeDef, generics: &[Type]) -> String {
+fn type_def_interface_signature(row: TypeDef<'_>, generics: &[Type<'_>]) -> String {
     let guid = type_def_guid(row).unwrap();
     if generics.is_empty() {
         format!("{{{guid:#?}}}")
@@ -933,16 +967,16 @@ fn type_def_interface_signature(row: TypeDef, generics: &[Type]) -> String {
     }
 }
 
-pub fn type_def_is_handle(row: TypeDef) -> bool {
+pub fn type_def_is_handle(row: TypeDef<'_>) -> bool {
     row.has_attribute("NativeTypedefAttribute")
 }
 
-pub fn type_def_guid(row: TypeDef) -> Option<Guid> {
+pub fn type_def_guid(row: TypeDef<'_>) -> Option<Guid> {
     row.find_attribute("GuidAttribute")
         .map(|attribute| Guid::from_args(&attribute.args()))
 }
 
-pub fn type_def_bases(mut row: TypeDef) -> Vec<TypeDef> {
+pub fn type_def_bases(mut row: TypeDef<'_>) -> Vec<TypeDef<'_>> {
     let mut bases = Vec::new();
     loop {
         match row.extends() {
@@ -960,7 +994,7 @@ pub fn type_def_bases(mut row: TypeDef) -> Vec<TypeDef> {
     bases
 }
 
-pub fn type_def_invalid_values(row: TypeDef) -> Vec<i64> {
+pub fn type_def_invalid_values(row: TypeDef<'_>) -> Vec<i64> {
     let mut values = Vec::new();
     for attribute in row.attributes() {
         if attribute.name() == "InvalidHandleValueAttribute" {
@@ -972,7 +1006,7 @@ pub fn type_def_invalid_values(row: TypeDef) -> Vec<i64> {
     values
 }
 
-fn type_def_is_nullable(row: TypeDef) -> bool {
+fn type_def_is_nullable(row: TypeDef<'_>) -> bool {
     match row.kind() {
         TypeKind::Interface | TypeKind::Class => true,
         // Win32 callbacks are defined as `Option<T>` so we don't include them here to avoid them
@@ -982,7 +1016,7 @@ fn type_def_is_nullable(row: TypeDef) -> bool {
     }
 }
 
-pub fn type_is_nullable(ty: &Type) -> bool {
+pub fn type_is_nullable(ty: &Type<'_>) -> bool {
     match ty {
         Type::TypeDef(row, _) => type_def_is_nullable(*row),
         Type::Object | Type::Name(TypeName::IUnknown) => true,
@@ -990,7 +1024,7 @@ pub fn type_is_nullable(ty: &Type) -> bool {
     }
 }
 
-pub fn type_def_vtables(row: TypeDef) -> Vec<Type> {
+pub fn type_def_vtables(row: TypeDef<'_>) -> Vec<Type<'_>> {
     let mut result = Vec::new();
     if row.flags().contains(TypeAttributes::WindowsRuntime) {
         result.push(Type::Name(TypeName::IUnknown));
@@ -1,4 +1,5 @@
 use super::*;
+use proc_macro2::LineColumn;
 
 // TODO: should we use rustfmt in the short term (with pre/post)
 
@@ -7,20 +8,56 @@ pub struct Writer {
     out: String,
     indent: usize,
     newline: bool,
+    comments: std::collections::VecDeque<trivia::Comment>,
 }
 
 impl Writer {
     pub fn new(file: &File) -> Self {
-        let mut writer = Self::default();
+        let mut writer = Self {
+            comments: file.comments.iter().cloned().collect(),
+            ..Default::default()
+        };
         writer.rdl_file(file);
         writer
     }
 
     pub fn into_string(mut self) -> String {
+        self.leading(END);
         self.out.push('\n');
         self.out
     }
 
+    fn next_comment(
+        &mut self,
+        accept: impl Fn(&trivia::Comment) -> bool,
+    ) -> Option<trivia::Comment> {
+        if self.comments.front().is_some_and(accept) {
+            self.comments.pop_front()
+        } else {
+            None
+        }
+    }
+
+    // Writes any comments appearing before the given position, each on its own line.
+    fn leading(&mut self, position: LineColumn) {
+        while let Some(comment) = self.next_comment(|comment| comment.start < position) {
+            self.word(&comment.text);
+            self.newline();
+        }
+    }
+
+    // Writes any comments following the end of an element on the same line, keeping them on that line. Comments
+    // beyond the start of the next element, such as the closing brace of a struct written on a single line, belong
+    // to that element instead.
+    fn trailing(&mut self, end: LineColumn, next: LineColumn) {
+        while let Some(comment) = self.next_comment(|comment| {
+            comment.start.line == end.line && comment.start >= end && comment.start < next
+        }) {
+            self.word(" ");
+            self.word(&comment.text);
+        }
+    }
+
     fn word(&mut self, value: &str) {
         if self.newline {
             self.newline = false;
@@ -38,6 +75,8 @@ impl Writer {
     }
 
     fn rdl_file(&mut self, file: &File) {
+        self.leading(file.span.start());
+
         if file.winrt {
             self.word("#![winrt]\n");
         } else {
@@ -46,8 +85,22 @@ impl Writer {
 
         self.newline();
 
-        for reference in &file.references {
+        let mut references = file.references.iter().peekable();
+
+        while let Some(reference) = references.next() {
+            let next = references.peek().map_or_else(
+                || {
+                    file.modules
+                        .first()
+                        .map_or(END, |module| module.span.start())
+                },
+                |next| next.span().start(),
+            );
+
+            self.leading(reference.span().start());
             self.item_use(reference);
+            self.trailing(reference.span().end(), next);
+            self.newline();
         }
 
         for module in &file.modules {
@@ -56,20 +109,30 @@ impl Writer {
     }
 
     fn rdl_module(&mut self, module: &Module) {
+        self.leading(module.span.start());
         self.word("mod ");
         self.word(module.name());
         self.word(" {");
         self.newline();
         self.indent += 1;
 
-        for member in &module.members {
+        let mut members = module.members.iter().peekable();
+
+        while let Some(member) = members.next() {
+            let next = members
+                .peek()
+                .map_or(module.close.start(), |next| member_start(next));
+
             self.rdl_module_member(member);
+            self.trailing(member.span().end(), next);
             self.newline();
         }
 
+        self.leading(module.close.start());
         self.indent -= 1;
         self.newline();
         self.word("}");
+        self.trailing(module.close.end(), END);
         self.newline();
     }
 
@@ -86,7 +149,7 @@ impl Writer {
     }
 
     fn rdl_class(&mut self, member: &Class) {
-        self.attrs(&member.attributes);
+        self.attrs(&member.attributes, member.span.start());
         self.word("class ");
         self.word(&member.name);
 
@@ -114,11 +177,10 @@ impl Writer {
         }
 
         self.word(";");
-        self.newline();
     }
 
     fn rdl_interface(&mut self, member: &Interface) {
-        self.attrs(&member.attributes);
+        self.attrs(&member.attributes, member.span.start());
         self.word("interface ");
         self.word(&member.name);
 
@@ -156,12 +218,20 @@ impl Writer {
         self.newline();
         self.indent += 1;
 
-        for method in &member.methods {
+        let mut methods = member.methods.iter().peekable();
+
+        while let Some(method) = methods.next() {
+            let next = methods.peek().map_or(member.close.start(), |next| {
+                attrs_start(&next.attrs, next.sig.span())
+            });
+
             self.trait_item_fn(method);
             self.word(";");
+            self.trailing(method.span().end(), next);
             self.newline();
         }
 
+        self.leading(member.close.start());
         self.indent -= 1;
         self.newline();
         self.word("}");
@@ -174,10 +244,10 @@ impl Writer {
     fn rdl_function(&mut self, member: &Function) {
         self.trait_item_fn(&member.item);
         self.word(";");
-        self.newline();
     }
 
     fn item_const(&mut self, item: &syn::ItemConst) {
+        self.attrs(&item.attrs, item.const_token.span.start());
         self.word("const ");
         self.ident(&item.ident);
         self.word(": ");
@@ -185,20 +255,54 @@ impl Writer {
         self.word(" = ");
         self.expr(&item.expr);
         self.word(";");
-        self.newline();
     }
 
-    fn attrs(&mut self, attrs: &[syn::Attribute]) {
-        for attr in attrs {
+    // Writes the attributes of an element followed by any comments between them and the element itself, which
+    // starts at the given position.
+    fn attrs(&mut self, attrs: &[syn::Attribute], start: LineColumn) {
+        let mut iter = attrs.iter().peekable();
+
+        while let Some(attr) = iter.next() {
+            let next = iter
+                .peek()
+                .map_or(start, |next| next.pound_token.span.start());
+
+            self.leading(attr.pound_token.span.start());
             self.attr(attr);
+            self.trailing(attr.span().end(), next);
+            self.newline();
         }
+
+        self.leading(start);
     }
 
     fn attr(&mut self, attr: &syn::Attribute) {
+        if let syn::Meta::NameValue(meta) = &attr.meta {
+            if let syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(doc),
+                ..
+            }) = &meta.value
+            {
+                if meta.path.is_ident("doc") {
+                    let mut first = true;
+                    for line in doc.value().split('\n') {
+                        if first {
+                            first = false;
+                        } else {
+                            self.newline();
+                        }
+                        self.word("///");
+                        self.word(line);
+                    }
+
+                    return;
+                }
+            }
+        }
+
         self.word("#[");
         self.meta(&attr.meta);
         self.word("]");
-        self.newline();
     }
 
     fn meta(&mut self, meta: &syn::Meta) {
@@ -223,7 +327,7 @@ impl Writer {
     }
 
     fn rdl_struct(&mut self, member: &Struct) {
-        self.attrs(&member.attributes);
+        self.attrs(&member.attributes, member.span.start());
 
         self.word("struct ");
         self.word(&member.name);
@@ -231,21 +335,30 @@ impl Writer {
         self.newline();
         self.indent += 1;
 
-        for field in &member.fields {
+        let mut fields = member.fields.iter().peekable();
+
+        while let Some(field) = fields.next() {
+            let next = fields.peek().map_or(member.close.start(), |next| {
+                attrs_start(&next.attributes, next.span)
+            });
+
+            self.attrs(&field.attributes, field.span.start());
             self.word(&field.name);
             self.word(": ");
             self.ty(&field.ty);
             self.word(",");
+            self.trailing(field.span.end(), next);
             self.newline();
         }
 
+        self.leading(member.close.start());
         self.indent -= 1;
         self.newline();
         self.word("}");
     }
 
     fn rdl_enum(&mut self, member: &Enum) {
-        self.attrs(&member.item.attrs);
+        self.attrs(&member.item.attrs, member.item.enum_token.span.start());
 
         self.word("enum ");
         self.ident(&member.item.ident);
@@ -253,23 +366,33 @@ impl Writer {
         self.newline();
         self.indent += 1;
 
-        for variant in &member.item.variants {
+        let close = member.item.brace_token.span.close().start();
+        let mut variants = member.item.variants.iter().peekable();
+
+        while let Some(variant) = variants.next() {
+            let next = variants
+                .peek()
+                .map_or(close, |next| attrs_start(&next.attrs, next.ident.span()));
+
+            self.attrs(&variant.attrs, variant.ident.span().start());
             self.ident(&variant.ident);
             if let Some((_, expr)) = &variant.discriminant {
                 self.word(" = ");
                 self.expr(expr);
             }
             self.word(",");
+            self.trailing(variant.span().end(), next);
             self.newline();
         }
 
+        self.leading(close);
         self.indent -= 1;
         self.newline();
         self.word("}");
     }
 
     fn trait_item_fn(&mut self, method: &syn::TraitItemFn) {
-        self.attrs(&method.attrs);
+        self.attrs(&method.attrs, method.sig.span().start());
         self.signature(&method.sig);
     }
 
@@ -278,14 +401,41 @@ impl Writer {
         self.ident(&signature.ident);
         self.word("(");
 
-        let mut first = true;
-        for input in &signature.inputs {
-            if first {
-                first = false;
-            } else {
-                self.word(", ");
+        let close = signature.paren_token.span.close().start();
+
+        // Parameters are written one per line, as `rustfmt` would for a long list, only if that's needed to keep any
+        // comments alongside them.
+        if self
+            .comments
+            .front()
+            .is_some_and(|comment| comment.start < close)
+        {
+            self.indent += 1;
+            let mut inputs = signature.inputs.iter().peekable();
+
+            while let Some(input) = inputs.next() {
+                let next = inputs.peek().map_or(close, |next| next.span().start());
+                self.newline();
+                self.leading(input.span().start());
+                self.fn_arg(input);
+                self.word(",");
+                self.trailing(input.span().end(), next);
+            }
+
+            self.newline();
+            self.leading(close);
+            self.indent -= 1;
+            self.newline();
+        } else {
+            let mut first = true;
+            for input in &signature.inputs {
+                if first {
+                    first = false;
+                } else {
+                    self.word(", ");
+                }
+                self.fn_arg(input);
             }
-            self.fn_arg(input);
         }
 
         self.word(")");
@@ -427,7 +577,6 @@ impl Writer {
         self.word("use ");
         self.use_tree(&item.tree);
         self.word(";");
-        self.newline();
     }
 
     fn use_tree(&mut self, use_tree: &syn::UseTree) {
@@ -452,3 +601,25 @@ impl Writer {
         self.word(&ident.to_string());
     }
 }
+
+const END: LineColumn = LineColumn {
+    line: usize::MAX,
+    column: 0,
+};
+
+// The start of an element including any attributes, which is where any comments preceding it end.
+fn attrs_start(attrs: &[syn::Attribute], span: proc_macro2::Span) -> LineColumn {
+    attrs
+        .first()
+        .map_or(span, |attr| attr.pound_token.span)
+        .start()
+}
+
+fn member_start(member: &ModuleMember) -> LineColumn {
+    match member {
+        ModuleMember::Interface(member) => attrs_start(&member.attributes, member.span),
+        ModuleMember::Struct(member) => attrs_start(&member.attributes, member.span),
+        ModuleMember::Class(member) => attrs_start(&member.attributes, member.span),
+        rest => rest.span().start(),
+    }
+}
@@ -1,8 +1,9 @@
 use super::*;
+use metadata::HasAttributes;
 use tokens::{quote, to_ident, TokenStream};
 
 pub fn from_reader(
-    reader: &'static metadata::Reader,
+    reader: &metadata::Reader<'_>,
     mut config: std::collections::BTreeMap<&str, &str>,
     output: &str,
 ) -> Result<()> {
@@ -34,7 +35,7 @@ pub fn from_reader(
     }
 }
 
-fn gen_split(writer: &Writer) -> Result<()> {
+fn gen_split(writer: &Writer<'_>) -> Result<()> {
     let tree = Tree::new(writer.reader);
     let directory = directory(&writer.output);
 
@@ -51,7 +52,7 @@ fn gen_split(writer: &Writer) -> Result<()> {
     Ok(())
 }
 
-fn gen_file(writer: &Writer) -> Result<()> {
+fn gen_file(writer: &Writer<'_>) -> Result<()> {
     let tree = Tree::new(writer.reader);
     let tokens = writer.tree(&tree);
     writer.write_to_file(&writer.output, tokens)
@@ -63,16 +64,16 @@ enum Dialect {
     WinRT,
 }
 
-struct Writer {
-    reader: &'static metadata::Reader,
-    namespace: &'static str,
+struct Writer<'a> {
+    reader: &'a metadata::Reader<'a>,
+    namespace: &'a str,
     dialect: Dialect,
     split: bool,
     output: String,
 }
 
-impl Writer {
-    fn new(reader: &'static metadata::Reader, output: &str, dialect: Dialect) -> Self {
+impl<'a> Writer<'a> {
+    fn new(reader: &'a metadata::Reader<'a>, output: &str, dialect: Dialect) -> Self {
         Self {
             reader,
             namespace: "",
@@ -82,7 +83,7 @@ impl Writer {
         }
     }
 
-    fn with_namespace(&self, namespace: &'static str) -> Self {
+    fn with_namespace(&self, namespace: &'a str) -> Self {
         Self {
             reader: self.reader,
             namespace,
@@ -108,7 +109,7 @@ impl Writer {
         //write_to_file(output, tokens.into_string())
     }
 
-    fn tree(&self, tree: &Tree) -> TokenStream {
+    fn tree(&self, tree: &Tree<'_>) -> TokenStream {
         let items = self.items(tree);
 
         if self.split {
@@ -153,7 +154,7 @@ impl Writer {
         }
     }
 
-    fn items(&self, tree: &Tree) -> TokenStream {
+    fn items(&self, tree: &Tree<'_>) -> TokenStream {
         let mut functions = vec![];
         let mut constants = vec![];
         let mut types = vec![];
@@ -194,18 +195,20 @@ impl Writer {
         }
     }
 
-    fn function(&self, def: metadata::MethodDef, _namespace: &str) -> TokenStream {
+    fn function(&self, def: metadata::MethodDef<'_>, _namespace: &str) -> TokenStream {
         let name = to_ident(def.name());
         quote! { fn #name(); }
     }
 
-    fn constant(&self, def: metadata::Field) -> TokenStream {
+    fn constant(&self, def: metadata::Field<'_>) -> TokenStream {
         let name = to_ident(def.name());
         quote! { const #name: i32 = 0; }
     }
 
-    fn type_def(&self, def: metadata::TypeDef) -> TokenStream {
-        if let Some(extends) = def.extends() {
+    fn type_def(&self, def: metadata::TypeDef<'_>) -> TokenStream {
+        let docs = self.docs(def);
+
+        let tokens = if let Some(extends) = def.extends() {
             if extends.namespace() == "System" {
                 if extends.name() == "Enum" {
                     self.enum_def(def)
@@ -221,26 +224,56 @@ impl Writer {
             }
         } else {
             self.interface_def(def)
+        };
+
+        quote! {
+            #docs
+            #tokens
         }
     }
 
-    fn enum_def(&self, def: metadata::TypeDef) -> TokenStream {
+    fn docs<R: HasAttributes<'a>>(&self, row: R) -> TokenStream {
+        metadata::row_doc_attributes(row)
+    }
+
+    fn enum_def(&self, def: metadata::TypeDef<'_>) -> TokenStream {
         let name = to_ident(def.name());
 
-        quote! {
-            struct #name {
+        // The `value__` field holding the underlying type has no constant and is thus skipped. RDL enums are always
+        // `i32` so unsigned values, as used by WinRT flags, keep their bit pattern.
+        let variants = def.fields().filter_map(|field| {
+            let value = match field.constant()?.value() {
+                metadata::Value::I32(value) => value,
+                metadata::Value::U32(value) => value as i32,
+                _ => return None,
+            };
+
+            let docs = self.docs(field);
+            let name = to_ident(field.name());
+            let value: TokenStream = value.to_string().into();
 
+            Some(quote! {
+                #docs
+                #name = #value
+            })
+        });
+
+        quote! {
+            enum #name {
+                #(#variants),*
             }
         }
     }
 
-    fn struct_def(&self, def: metadata::TypeDef) -> TokenStream {
+    fn struct_def(&self, def: metadata::TypeDef<'_>) -> TokenStream {
         let name = to_ident(def.name());
 
         let fields = def.fields().map(|field| {
+            let docs = self.docs(field);
             let name = to_ident(field.name());
             let ty = self.ty(&field.ty(Some(def)));
             quote! {
+                #docs
                 #name: #ty
             }
         });
@@ -252,7 +285,7 @@ impl Writer {
         }
     }
 
-    fn delegate_def(&self, def: metadata::TypeDef) -> TokenStream {
+    fn delegate_def(&self, def: metadata::TypeDef<'_>) -> TokenStream {
         let name = to_ident(def.name());
 
         quote! {
@@ -262,7 +295,7 @@ impl Writer {
         }
     }
 
-    fn class_def(&self, def: metadata::TypeDef) -> TokenStream {
+    fn class_def(&self, def: metadata::TypeDef<'_>) -> TokenStream {
         let name = to_ident(def.name());
         let implements = self.implements(def, &[]);
 
@@ -271,12 +304,13 @@ impl Writer {
         }
     }
 
-    fn interface_def(&self, def: metadata::TypeDef) -> TokenStream {
+    fn interface_def(&self, def: metadata::TypeDef<'_>) -> TokenStream {
         let name = to_ident(def.name());
         let generics = &metadata::type_def_generics(def);
         let implements = self.implements(def, generics);
 
         let methods = def.methods().map(|method| {
+            let docs = self.docs(method);
             let name = to_ident(method.name());
 
             // TODO: use reader.method_def_signature instead
@@ -291,6 +325,7 @@ impl Writer {
             });
 
             quote! {
+                #docs
                 fn #name(#(#params),*) #return_type;
             }
         });
@@ -304,7 +339,7 @@ impl Writer {
         }
     }
 
-    fn generics(&self, generics: &[metadata::Type]) -> TokenStream {
+    fn generics(&self, generics: &[metadata::Type<'_>]) -> TokenStream {
         if generics.is_empty() {
             quote! {}
         } else {
@@ -314,7 +349,11 @@ impl Writer {
         }
     }
 
-    fn implements(&self, def: metadata::TypeDef, generics: &[metadata::Type]) -> TokenStream {
+    fn implements(
+        &self,
+        def: metadata::TypeDef<'_>,
+        generics: &[metadata::Type<'_>],
+    ) -> TokenStream {
         let mut types = Vec::<TokenStream>::new();
 
         // TODO: if a winrt composable class then start with base
@@ -346,7 +385,7 @@ impl Writer {
         }
     }
 
-    fn return_type(&self, ty: &metadata::Type) -> TokenStream {
+    fn return_type(&self, ty: &metadata::Type<'_>) -> TokenStream {
         match ty {
             metadata::Type::Void => quote! {},
             _ => {
@@ -356,7 +395,7 @@ impl Writer {
         }
     }
 
-    fn ty(&self, ty: &metadata::Type) -> TokenStream {
+    fn ty(&self, ty: &metadata::Type<'_>) -> TokenStream {
         match ty {
             metadata::Type::Void => quote! { core::ffi::c_void },
             metadata::Type::Bool => quote! { bool },
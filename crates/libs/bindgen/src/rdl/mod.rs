@@ -3,48 +3,82 @@
 use super::*;
 mod fmt;
 mod from_reader;
+mod resolve;
 mod to_winmd;
+mod trivia;
 use crate::Result;
 pub use from_reader::from_reader;
 use syn::spanned::Spanned;
 
-// TODO: may want to finally get rid of `syn` as it also doesn't support preserving code comments
-
 impl File {
     pub fn parse_str(input: &str) -> Result<Self> {
-        Ok(syn::parse_str::<Self>(input)?)
+        let mut file =
+            syn::parse_str::<Self>(input).map_err(|error| Error::from(error).with_source(input))?;
+        file.comments = trivia::comments(input);
+        file.source = input.to_string();
+        Ok(file)
+    }
+
+    pub fn parse_file(path: &str) -> Result<Self> {
+        read_file_text(path)
+            .and_then(|source| Self::parse_str(&source))
+            .map(|file| Self {
+                path: path.to_string(),
+                ..file
+            })
+            .map_err(|error| error.with_path(path))
     }
 
     // Note: this isn't called automatically by `parse_str` to avoid canonicalizing when we're merely formatting IDL.
+    // This rewrites the `File` to resolve any `super` references and use declarations so that subsequently the
+    // rdl-to-winmd conversion can just assume everything's fully qualified. This way we can quickly kick out common
+    // invalid IDL files before we lose file/span context info.
     pub fn canonicalize(&mut self) -> Result<()> {
-        // TODO maybe we rewrite the `File` here to resolve any `super` references and use declarations so that
-        // subsequently the rdl-to-winmd conversion can just assume everything's fully qualified?
-        // * super can't refer to something outside of the IDL file
-        // * use declarations are only used for unqualified names that aren't defined in the IDL file
-        // * use declarations don't support globs and must name all externally defined types
-        // This way we can quickly kick out common invalid IDL files before we lost file/span context info
-
-        Ok(())
+        let errors = resolve::resolve(std::slice::from_mut(self));
+        file_errors(std::slice::from_ref(self), errors)
     }
 
     pub fn fmt(&self) -> String {
         fmt::Writer::new(self).into_string()
     }
 
-    pub fn into_winmd(mut self) -> Result<Vec<u8>> {
-        self.canonicalize()?;
-        to_winmd::rdl_to_winmd(&self)
+    pub fn into_winmd(self) -> Result<Vec<u8>> {
+        compile(vec![self])
     }
 }
 
+// Compiles any number of files as a single unit such that types defined in one file may be referenced from another,
+// producing a single winmd.
+pub fn compile(mut files: Vec<File>) -> Result<Vec<u8>> {
+    // Conversion is attempted even if canonicalizing fails so that all of the errors are reported together.
+    let errors = resolve::resolve(&mut files);
+    to_winmd::rdl_to_winmd(&files, errors)
+}
+
+// Errors are collected separately for each file and then combined with the path and source of the file they belong to
+// so that all of them may be reported together.
+fn file_errors(files: &[File], errors: Vec<Vec<syn::Error>>) -> Result<()> {
+    Error::combine(files.iter().zip(errors).filter_map(|(file, errors)| {
+        combine(errors).err().map(|error| {
+            Error::from(error)
+                .with_source(&file.source)
+                .with_path(&file.path)
+        })
+    }))
+}
+
 // The value of the IDL-specific memory representation is that it allows for constructs that are not modeled in the abstract Module
 // tree such as the use declarations and if we get rid of it we'd always "format" IDL by stripping out any of that into a single
 // canonical form which would not be very friendly to developers.
 #[derive(Debug)]
 pub struct File {
+    pub path: String,
     pub winrt: bool,
+    pub span: proc_macro2::Span,
     pub references: Vec<syn::ItemUse>,
     pub modules: Vec<Module>,
+    pub comments: Vec<trivia::Comment>,
+    pub source: String,
 }
 
 // TODO: need to change these to unpack the syn types and store strings we can reference for efficiency along with spans since the syn
@@ -53,6 +87,8 @@ pub struct File {
 #[derive(Clone, Debug)]
 pub struct Module {
     pub namespace: String,
+    pub span: proc_macro2::Span,
+    pub close: proc_macro2::Span,
     pub members: Vec<ModuleMember>,
 }
 
@@ -79,6 +115,18 @@ impl ModuleMember {
             Self::Constant(member) => &member.name,
         }
     }
+
+    pub fn span(&self) -> proc_macro2::Span {
+        match self {
+            Self::Module(module) => module.span,
+            Self::Interface(member) => member.span,
+            Self::Struct(member) => member.span,
+            Self::Enum(member) => member.item.span(),
+            Self::Class(member) => member.span,
+            Self::Function(member) => member.item.span(),
+            Self::Constant(member) => member.item.span(),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -100,6 +148,7 @@ pub struct Struct {
     pub name: String,
     pub attributes: Vec<syn::Attribute>,
     pub span: proc_macro2::Span,
+    pub close: proc_macro2::Span,
     pub fields: Vec<Field>,
 }
 
@@ -114,6 +163,7 @@ pub struct Field {
 #[derive(Clone, Debug)]
 pub struct Class {
     pub name: String,
+    pub span: proc_macro2::Span,
     pub attributes: Vec<syn::Attribute>,
     pub base: Option<syn::TypePath>,
     pub extends: Vec<syn::TypePath>,
@@ -130,6 +180,8 @@ pub struct Interface {
     pub winrt: bool,
     pub name: String,
     pub generics: Vec<String>,
+    pub span: proc_macro2::Span,
+    pub close: proc_macro2::Span,
     pub attributes: Vec<syn::Attribute>,
     pub extends: Vec<syn::TypePath>,
     pub methods: Vec<syn::TraitItemFn>,
@@ -162,22 +214,40 @@ impl syn::parse::Parse for File {
     fn parse(input: syn::parse::ParseStream<'_>) -> syn::Result<Self> {
         let mut references = vec![];
         let mut modules = vec![];
+        let span = input.span();
         let winrt = winrt(input)?;
+        let mut errors = vec![];
 
         while !input.is_empty() {
+            let cursor = input.cursor();
             let lookahead = input.lookahead1();
-            if lookahead.peek(syn::Token![mod]) {
-                modules.push(Module::parse("", winrt, input)?);
+
+            let result = if lookahead.peek(syn::Token![mod]) {
+                Module::parse("", winrt, input, &mut errors).map(|module| modules.push(module))
             } else if lookahead.peek(syn::Token![use]) {
-                references.push(input.parse()?);
+                input.parse().map(|reference| references.push(reference))
             } else {
-                return Err(lookahead.error());
+                Err(lookahead.error())
+            };
+
+            if let Err(error) = result {
+                errors.push(error);
+                recover(input, cursor, |input| {
+                    input.peek(syn::Token![mod]) || input.peek(syn::Token![use])
+                });
             }
         }
+
+        combine(errors)?;
+
         Ok(Self {
+            path: String::new(),
             winrt,
+            span,
             references,
             modules,
+            comments: vec![],
+            source: String::new(),
         })
     }
 }
@@ -193,8 +263,9 @@ impl Module {
         namespace: &str,
         winrt: bool,
         input: syn::parse::ParseStream<'_>,
+        errors: &mut Vec<syn::Error>,
     ) -> syn::Result<Self> {
-        input.parse::<syn::Token![mod]>()?;
+        let start = input.parse::<syn::Token![mod]>()?.span;
         let name = input.parse::<syn::Ident>()?.to_string();
 
         let namespace = if namespace.is_empty() {
@@ -204,20 +275,45 @@ impl Module {
         };
 
         let content;
-        syn::braced!(content in input);
+        let brace = syn::braced!(content in input);
         let mut members = vec![];
         while !content.is_empty() {
-            members.push(ModuleMember::parse(&namespace, winrt, &content)?);
+            let cursor = content.cursor();
+
+            match ModuleMember::parse(&namespace, winrt, &content, errors) {
+                Ok(member) => members.push(member),
+                Err(error) => {
+                    errors.push(error);
+                    recover(&content, cursor, ModuleMember::peek);
+                }
+            }
         }
-        Ok(Self { namespace, members })
+        Ok(Self {
+            namespace,
+            span: join(start, brace.span.close()),
+            close: brace.span.close(),
+            members,
+        })
     }
 }
 
 impl ModuleMember {
+    fn peek(input: syn::parse::ParseStream<'_>) -> bool {
+        input.peek(syn::Token![#])
+            || input.peek(syn::Token![mod])
+            || input.peek(interface)
+            || input.peek(syn::Token![struct])
+            || input.peek(syn::Token![enum])
+            || input.peek(class)
+            || input.peek(syn::Token![fn])
+            || input.peek(syn::Token![const])
+    }
+
     fn parse(
         namespace: &str,
         winrt: bool,
         input: syn::parse::ParseStream<'_>,
+        errors: &mut Vec<syn::Error>,
     ) -> syn::Result<Self> {
         let attributes: Vec<syn::Attribute> = input.call(syn::Attribute::parse_outer)?;
         let lookahead = input.lookahead1();
@@ -229,11 +325,11 @@ impl ModuleMember {
                 ));
             }
             Ok(ModuleMember::Module(Module::parse(
-                namespace, winrt, input,
+                namespace, winrt, input, errors,
             )?))
         } else if lookahead.peek(interface) {
             Ok(ModuleMember::Interface(Interface::parse(
-                namespace, winrt, attributes, input,
+                namespace, winrt, attributes, input, errors,
             )?))
         } else if lookahead.peek(syn::Token![struct]) {
             Ok(ModuleMember::Struct(Struct::parse(
@@ -264,7 +360,7 @@ impl Class {
         attributes: Vec<syn::Attribute>,
         input: syn::parse::ParseStream<'_>,
     ) -> syn::Result<Self> {
-        input.parse::<class>()?;
+        let start = input.parse::<class>()?.span;
         let name = input.parse::<syn::Ident>()?.to_string();
         let mut extends = Vec::new();
         let mut base = None;
@@ -282,8 +378,9 @@ impl Class {
             }
         }
 
-        input.parse::<syn::Token![;]>()?;
+        let end = input.parse::<syn::Token![;]>()?.span;
         Ok(Self {
+            span: join(start, end),
             attributes,
             name,
             base,
@@ -298,8 +395,9 @@ impl Interface {
         winrt: bool,
         attributes: Vec<syn::Attribute>,
         input: syn::parse::ParseStream<'_>,
+        errors: &mut Vec<syn::Error>,
     ) -> syn::Result<Self> {
-        input.parse::<interface>()?;
+        let start = input.parse::<interface>()?.span;
         let name = input.parse::<syn::Ident>()?.to_string();
 
         let mut generics = Vec::new();
@@ -325,13 +423,25 @@ impl Interface {
         }
 
         let content;
-        syn::braced!(content in input);
+        let brace = syn::braced!(content in input);
         let mut methods = vec![];
         while !content.is_empty() {
-            methods.push(content.parse()?);
+            let cursor = content.cursor();
+
+            match content.parse() {
+                Ok(method) => methods.push(method),
+                Err(error) => {
+                    errors.push(error);
+                    recover(&content, cursor, |input| {
+                        input.peek(syn::Token![#]) || input.peek(syn::Token![fn])
+                    });
+                }
+            }
         }
         Ok(Self {
             winrt,
+            span: join(start, brace.span.close()),
+            close: brace.span.close(),
             attributes,
             generics,
             extends,
@@ -359,13 +469,15 @@ impl Struct {
             return Err(syn::Error::new(item.span(), "unnamed fields not supported"));
         };
 
-        for field in named.named {
+        for field in &named.named {
+            // Simply unwrapping since we already know that it is a named field.
+            let ident = field.ident.as_ref().unwrap();
+
             fields.push(Field {
-                span: field.span(),
-                attributes: field.attrs,
-                // Simply unwrapping since we already know that it is a named field.
-                name: field.ident.unwrap().to_string(),
-                ty: field.ty,
+                span: join(ident.span(), field.ty.span()),
+                attributes: field.attrs.clone(),
+                name: ident.to_string(),
+                ty: field.ty.clone(),
             });
         }
 
@@ -373,7 +485,8 @@ impl Struct {
             winrt,
             name,
             attributes,
-            span,
+            span: join(span, named.brace_token.span.close()),
+            close: named.brace_token.span.close(),
             fields,
         })
     }
@@ -418,3 +531,39 @@ impl Function {
         Ok(Self { name, item })
     }
 }
+
+fn join(start: proc_macro2::Span, end: proc_macro2::Span) -> proc_macro2::Span {
+    start.join(end).unwrap_or(start)
+}
+
+// Skips ahead to the next token accepted by `peek` so that parsing may continue after an error and report as many
+// errors as possible in a single run. At least one token is skipped if the error didn't consume any input.
+fn recover(
+    input: syn::parse::ParseStream<'_>,
+    cursor: syn::buffer::Cursor<'_>,
+    peek: fn(syn::parse::ParseStream<'_>) -> bool,
+) {
+    if input.cursor() == cursor {
+        _ = input.parse::<proc_macro2::TokenTree>();
+    }
+
+    while !input.is_empty() && !peek(input) {
+        _ = input.parse::<proc_macro2::TokenTree>();
+    }
+}
+
+// Combines any errors into a single error, ordered by their location in the source.
+fn combine(mut errors: Vec<syn::Error>) -> syn::Result<()> {
+    errors.sort_by_key(|error| {
+        let start = error.span().start();
+        (start.line, start.column)
+    });
+
+    errors
+        .into_iter()
+        .reduce(|mut first, error| {
+            first.combine(error);
+            first
+        })
+        .map_or(Ok(()), Err)
+}
@@ -0,0 +1,284 @@
+use super::*;
+use std::collections::{HashMap, HashSet};
+
+// Rewrites every type reference in the file to be fully qualified so that the rdl-to-winmd conversion can simply
+// assume that any path names a namespace followed by a type name.
+//
+// * `super` can't refer to something outside of the RDL file
+// * `use` declarations are only used for unqualified names that aren't defined in the RDL file
+// * `use` declarations don't support globs or renames and must name all externally defined types
+//
+// Paths with more than one segment are relative if their first segment names a nested module and are otherwise assumed
+// to be absolute. Absolute paths aren't verified since they may refer to types defined by other metadata.
+//
+// Types defined in any of the `files` may be referenced from any other while `use` declarations only apply to the file
+// in which they appear. The errors for each file are returned separately.
+pub fn resolve(files: &mut [File]) -> Vec<Vec<syn::Error>> {
+    let mut resolver = Resolver::default();
+
+    for file in files.iter() {
+        file.modules
+            .iter()
+            .for_each(|module| resolver.collect(module));
+    }
+
+    files
+        .iter_mut()
+        .map(|file| {
+            resolver.uses.clear();
+
+            file.references
+                .iter()
+                .for_each(|reference| resolver.use_tree(&[], &reference.tree));
+
+            file.modules
+                .iter_mut()
+                .for_each(|module| resolver.module(module));
+
+            std::mem::take(&mut resolver.errors)
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct Resolver {
+    // Namespaces defined by the file along with the names of the types they contain.
+    types: HashMap<String, HashSet<String>>,
+    // Unqualified names introduced by `use` declarations along with their namespaces.
+    uses: HashMap<String, String>,
+    errors: Vec<syn::Error>,
+}
+
+impl Resolver {
+    fn collect(&mut self, module: &Module) {
+        self.types.entry(module.namespace.to_string()).or_default();
+
+        for member in &module.members {
+            match member {
+                ModuleMember::Module(module) => self.collect(module),
+                ModuleMember::Interface(_)
+                | ModuleMember::Struct(_)
+                | ModuleMember::Enum(_)
+                | ModuleMember::Class(_) => {
+                    self.types
+                        .entry(module.namespace.to_string())
+                        .or_default()
+                        .insert(member.name().to_string());
+                }
+                ModuleMember::Function(_) | ModuleMember::Constant(_) => {}
+            }
+        }
+    }
+
+    fn use_tree(&mut self, prefix: &[String], tree: &syn::UseTree) {
+        match tree {
+            syn::UseTree::Path(path) => {
+                let mut prefix = prefix.to_vec();
+                prefix.push(path.ident.to_string());
+                self.use_tree(&prefix, &path.tree);
+            }
+            syn::UseTree::Name(name) => {
+                if prefix.is_empty() {
+                    self.error(name, "`use` declarations must be fully qualified");
+                    return;
+                }
+
+                let namespace = prefix.join(".");
+                let name = name.ident.to_string();
+
+                if self
+                    .uses
+                    .get(&name)
+                    .is_some_and(|previous| *previous != namespace)
+                {
+                    self.error(tree, &format!("`{name}` is imported more than once"));
+                } else {
+                    self.uses.insert(name, namespace);
+                }
+            }
+            syn::UseTree::Rename(rename) => self.error(rename, "renaming imports is not supported"),
+            syn::UseTree::Glob(glob) => self.error(glob, "glob imports are not supported"),
+            syn::UseTree::Group(group) => group
+                .items
+                .iter()
+                .for_each(|tree| self.use_tree(prefix, tree)),
+        }
+    }
+
+    fn module(&mut self, module: &mut Module) {
+        let namespace = &module.namespace;
+
+        for member in &mut module.members {
+            match member {
+                ModuleMember::Module(module) => self.module(module),
+                ModuleMember::Interface(member) => {
+                    for extends in &mut member.extends {
+                        self.type_path(namespace, &member.generics, extends);
+                    }
+
+                    for method in &mut member.methods {
+                        self.signature(namespace, &member.generics, &mut method.sig);
+                    }
+                }
+                ModuleMember::Struct(member) => {
+                    for field in &mut member.fields {
+                        self.ty(namespace, &[], &mut field.ty);
+                    }
+                }
+                ModuleMember::Class(member) => {
+                    if let Some(base) = &mut member.base {
+                        self.type_path(namespace, &[], base);
+                    }
+
+                    for extends in &mut member.extends {
+                        self.type_path(namespace, &[], extends);
+                    }
+                }
+                ModuleMember::Function(member) => {
+                    self.signature(namespace, &[], &mut member.item.sig)
+                }
+                ModuleMember::Constant(member) => self.ty(namespace, &[], &mut member.item.ty),
+                ModuleMember::Enum(_) => {}
+            }
+        }
+    }
+
+    fn signature(&mut self, namespace: &str, generics: &[String], sig: &mut syn::Signature) {
+        for input in &mut sig.inputs {
+            if let syn::FnArg::Typed(pat_type) = input {
+                self.ty(namespace, generics, &mut pat_type.ty);
+            }
+        }
+
+        if let syn::ReturnType::Type(_, ty) = &mut sig.output {
+            self.ty(namespace, generics, ty);
+        }
+    }
+
+    fn ty(&mut self, namespace: &str, generics: &[String], ty: &mut syn::Type) {
+        match ty {
+            syn::Type::Path(ty) => self.type_path(namespace, generics, ty),
+            syn::Type::Ptr(ptr) => self.ty(namespace, generics, &mut ptr.elem),
+            syn::Type::Array(array) => self.ty(namespace, generics, &mut array.elem),
+            // Anything else is reported as unsupported when converting to winmd.
+            _ => {}
+        }
+    }
+
+    fn type_path(&mut self, namespace: &str, generics: &[String], ty: &mut syn::TypePath) {
+        if ty.qself.is_some() {
+            return;
+        }
+
+        let path = &mut ty.path;
+
+        // Unwrapping is fine as there should always be at least one segment.
+        if let syn::PathArguments::AngleBracketed(args) =
+            &mut path.segments.last_mut().unwrap().arguments
+        {
+            for arg in &mut args.args {
+                if let syn::GenericArgument::Type(ty) = arg {
+                    self.ty(namespace, generics, ty);
+                }
+            }
+        }
+
+        let segments: Vec<String> = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+
+        let (name, qualifiers) = segments.split_last().unwrap();
+
+        let type_namespace = if path.leading_colon.is_some() && !qualifiers.is_empty() {
+            qualifiers.join(".")
+        } else if qualifiers.is_empty() {
+            if generics.contains(name) || to_winmd::syn_primitive(name).is_some() {
+                return;
+            }
+
+            if self.contains(namespace, name) {
+                namespace.to_string()
+            } else if let Some(namespace) = self.uses.get(name) {
+                namespace.to_string()
+            } else {
+                self.error(&*path, &format!("unresolved type `{name}`"));
+                return;
+            }
+        } else if qualifiers[0] == "super" {
+            let mut builder: Vec<&str> = namespace.split('.').collect();
+            let mut qualifiers = qualifiers;
+
+            while let Some(("super", rest)) = qualifiers
+                .split_first()
+                .map(|(first, rest)| (first.as_str(), rest))
+            {
+                builder.pop();
+
+                if builder.is_empty() {
+                    self.error(&*path, "too many leading `super` keywords");
+                    return;
+                }
+
+                qualifiers = rest;
+            }
+
+            builder.extend(qualifiers.iter().map(String::as_str));
+            let type_namespace = builder.join(".");
+
+            if !self.contains(&type_namespace, name) {
+                self.error(
+                    &*path,
+                    &format!("unresolved type `{}`", segments.join("::")),
+                );
+                return;
+            }
+
+            type_namespace
+        } else {
+            let relative = format!("{namespace}.{}", qualifiers.join("."));
+
+            if self.types.contains_key(&relative) {
+                if !self.contains(&relative, name) {
+                    self.error(
+                        &*path,
+                        &format!("unresolved type `{}`", segments.join("::")),
+                    );
+                    return;
+                }
+
+                relative
+            } else {
+                qualifiers.join(".")
+            }
+        };
+
+        // Unwrapping is fine as there should always be at least one segment.
+        let last = path.segments.pop().unwrap().into_value();
+        let span = last.ident.span();
+
+        path.leading_colon = None;
+        path.segments = type_namespace
+            .split('.')
+            .map(|segment| {
+                // Module names may be raw identifiers but `Ident::new` rejects the `r#` prefix.
+                syn::PathSegment::from(segment.strip_prefix("r#").map_or_else(
+                    || syn::Ident::new(segment, span),
+                    |segment| syn::Ident::new_raw(segment, span),
+                ))
+            })
+            .chain(std::iter::once(last))
+            .collect();
+    }
+
+    fn contains(&self, namespace: &str, name: &str) -> bool {
+        self.types
+            .get(namespace)
+            .is_some_and(|types| types.contains(name))
+    }
+
+    fn error<T: Spanned>(&mut self, tokens: T, message: &str) {
+        self.errors.push(syn::Error::new(tokens.span(), message));
+    }
+}
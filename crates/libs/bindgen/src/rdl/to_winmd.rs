@@ -2,28 +2,34 @@ use super::*;
 use crate::winmd::writer;
 
 // TODO: store span in winmd so that errors resolving type references can be traced back to file/line/column
-use std::collections::HashMap;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashSet};
 //use syn::spanned::Spanned;
 
 // TODO: this creates a temporary in-memory winmd used to treat the IDL content uniformly as metadata.
 // The winmd_to_winmd does the harder job of validating and producing canonical winmd for public consumption.
 
-pub fn rdl_to_winmd(file: &File) -> Result<Vec<u8>> {
-    // Local-to-qualified type names found in use declaration - e.g. "IStringable" -> "Windows.Foundation.IStringable"
-    // This is just a convenience for the developer to shorten common references like this but would not support globs or renames.
-    // Note that none of these are verified to be real until much later when the winmd is validated since we don't
-    // know what other metadata may be combined
-    let mut _use_map = HashMap::<String, String>::new();
-
-    // TODO: read file and populate use_map
-
+// The `files` are expected to have been canonicalized, with any errors from doing so passed along in `errors`. Errors
+// are collected rather than returned immediately so that as many as possible are reported in a single run.
+pub fn rdl_to_winmd(files: &[File], mut errors: Vec<Vec<syn::Error>>) -> Result<Vec<u8>> {
     // Types are collected here in two passes - this allows us to figure out whether a local name points to a relative type
     // or a type from a use declaration...?
-    let mut collector = HashMap::<String, HashMap<&str, ModuleMember>>::new();
+    // The collector is ordered so that the rows of the resulting winmd don't depend on the order of hashing or inputs.
+    let mut collector = BTreeMap::<String, BTreeMap<&str, ModuleMember>>::new();
 
-    file.modules
-        .iter()
-        .for_each(|module| collect_module(&mut collector, module));
+    for (file, errors) in files.iter().zip(&mut errors) {
+        file.modules
+            .iter()
+            .for_each(|module| collect_module(&mut collector, errors, module));
+
+        let mut checker = Checker { errors };
+
+        file.modules
+            .iter()
+            .for_each(|module| checker.module(module));
+    }
+
+    file_errors(files, errors)?;
 
     // TODO: collect type names into hashmap (phase 1) and just drop clones of the IDL members into the collector
 
@@ -42,22 +48,24 @@ pub fn rdl_to_winmd(file: &File) -> Result<Vec<u8>> {
 }
 
 fn collect_module<'a>(
-    collector: &mut HashMap<String, HashMap<&'a str, ModuleMember>>,
+    collector: &mut BTreeMap<String, BTreeMap<&'a str, ModuleMember>>,
+    errors: &mut Vec<syn::Error>,
     module: &'a Module,
 ) {
     module
         .members
         .iter()
-        .for_each(|member| collect_member(collector, module, member));
+        .for_each(|member| collect_member(collector, errors, module, member));
 }
 
 fn collect_member<'a>(
-    collector: &mut HashMap<String, HashMap<&'a str, ModuleMember>>,
+    collector: &mut BTreeMap<String, BTreeMap<&'a str, ModuleMember>>,
+    errors: &mut Vec<syn::Error>,
     module: &'a Module,
     member: &'a ModuleMember,
 ) {
     match member {
-        ModuleMember::Module(module) => collect_module(collector, module),
+        ModuleMember::Module(module) => collect_module(collector, errors, module),
         ModuleMember::Constant(_) | ModuleMember::Function(_) => {
             collector
                 .entry(module.namespace.to_string())
@@ -66,13 +74,207 @@ fn collect_member<'a>(
                 .or_insert(member.clone());
         }
         _ => {
-            collector
+            match collector
                 .entry(module.namespace.to_string())
                 .or_default()
                 .entry(member.name())
-                .or_insert(member.clone());
+            {
+                Entry::Occupied(_) => errors.push(syn::Error::new(
+                    member.span(),
+                    format!(
+                        "duplicate definition of `{}.{}`",
+                        module.namespace,
+                        member.name()
+                    ),
+                )),
+                Entry::Vacant(entry) => {
+                    entry.insert(member.clone());
+                }
+            }
+        }
+    }
+}
+
+// The checker catches errors that would otherwise cause a panic or produce invalid metadata when the members are
+// written. Everything is checked up front so that all of the errors may be reported with their spans.
+struct Checker<'a> {
+    errors: &'a mut Vec<syn::Error>,
+}
+
+impl Checker<'_> {
+    fn module(&mut self, module: &Module) {
+        for member in &module.members {
+            match member {
+                ModuleMember::Module(module) => self.module(module),
+                ModuleMember::Interface(member) => self.interface(&module.namespace, member),
+                ModuleMember::Struct(member) => self.structure(&module.namespace, member),
+                ModuleMember::Enum(member) => self.enumeration(member),
+                ModuleMember::Class(member) => self.class(&module.namespace, member),
+                ModuleMember::Function(member) => self.attributes(&member.item.attrs),
+                ModuleMember::Constant(member) => self.attributes(&member.item.attrs),
+            }
+        }
+    }
+
+    fn interface(&mut self, namespace: &str, member: &Interface) {
+        self.attributes(&member.attributes);
+
+        for extends in &member.extends {
+            self.type_path(namespace, &member.generics, extends);
+        }
+
+        for method in &member.methods {
+            self.attributes(&method.attrs);
+
+            for input in &method.sig.inputs {
+                match input {
+                    syn::FnArg::Typed(pat_type) => {
+                        if !matches!(&*pat_type.pat, syn::Pat::Ident(_)) {
+                            self.error(&pat_type.pat, "parameter must be an identifier");
+                        }
+
+                        self.ty(namespace, &member.generics, &pat_type.ty);
+                    }
+                    syn::FnArg::Receiver(receiver) => {
+                        self.error(receiver, "`self` parameters not supported")
+                    }
+                }
+            }
+
+            if let syn::ReturnType::Type(_, ty) = &method.sig.output {
+                self.ty(namespace, &member.generics, ty);
+            }
+        }
+    }
+
+    fn structure(&mut self, namespace: &str, member: &Struct) {
+        self.attributes(&member.attributes);
+        let mut names = HashSet::new();
+
+        for field in &member.fields {
+            self.attributes(&field.attributes);
+
+            if !names.insert(&field.name) {
+                self.errors.push(syn::Error::new(
+                    field.span,
+                    format!("duplicate field `{}`", field.name),
+                ));
+            }
+
+            self.ty(namespace, &[], &field.ty);
+        }
+    }
+
+    fn enumeration(&mut self, member: &Enum) {
+        self.attributes(&member.item.attrs);
+        let mut names = HashSet::new();
+
+        for variant in &member.item.variants {
+            self.attributes(&variant.attrs);
+
+            if !names.insert(&variant.ident) {
+                self.error(
+                    &variant.ident,
+                    &format!("duplicate variant `{}`", variant.ident),
+                );
+            }
+
+            if !matches!(variant.fields, syn::Fields::Unit) {
+                self.error(&variant.fields, "enum variants cannot have fields");
+            }
+
+            if let Some((_, expr)) = &variant.discriminant {
+                if discriminant(expr).is_none() {
+                    self.error(expr, "enum discriminant must be an `i32` literal");
+                }
+            }
+        }
+    }
+
+    fn class(&mut self, namespace: &str, member: &Class) {
+        self.attributes(&member.attributes);
+
+        if let Some(base) = &member.base {
+            self.type_path(namespace, &[], base);
+        }
+
+        for extends in &member.extends {
+            self.type_path(namespace, &[], extends);
+        }
+    }
+
+    // Doc comments are the only attributes that are currently understood and are otherwise silently dropped.
+    fn attributes(&mut self, attributes: &[syn::Attribute]) {
+        for attribute in attributes {
+            if !attribute.path().is_ident("doc") {
+                let path: Vec<String> = attribute
+                    .path()
+                    .segments
+                    .iter()
+                    .map(|segment| segment.ident.to_string())
+                    .collect();
+
+                self.error(
+                    attribute,
+                    &format!("unsupported attribute `{}`", path.join("::")),
+                );
+            } else if !matches!(
+                &attribute.meta,
+                syn::Meta::NameValue(syn::MetaNameValue {
+                    value: syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(_),
+                        ..
+                    }),
+                    ..
+                })
+            ) {
+                self.error(attribute, "expected `#[doc = \"...\"]`");
+            }
+        }
+    }
+
+    fn ty(&mut self, namespace: &str, generics: &[String], ty: &syn::Type) {
+        match ty {
+            syn::Type::Path(ty) => self.type_path(namespace, generics, ty),
+            syn::Type::Ptr(ptr) => self.ty(namespace, &[], &ptr.elem),
+            syn::Type::Array(array) => {
+                self.ty(namespace, &[], &array.elem);
+
+                if !matches!(&array.len, syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(lit), .. }) if lit.base10_parse::<usize>().is_ok())
+                {
+                    self.error(&array.len, "array length must be an integer literal");
+                }
+            }
+            rest => self.error(rest, "unsupported type"),
+        }
+    }
+
+    fn type_path(&mut self, namespace: &str, generics: &[String], ty: &syn::TypePath) {
+        if ty.qself.is_some() {
+            self.error(ty, "unsupported type");
+            return;
+        }
+
+        let path = &ty.path;
+
+        // Unwrapping is fine as there should always be at least one segment.
+        let last = path.segments.last().unwrap();
+
+        if let syn::PathArguments::AngleBracketed(args) = &last.arguments {
+            for arg in &args.args {
+                match arg {
+                    syn::GenericArgument::Type(ty) => self.ty(namespace, generics, ty),
+                    rest => self.error(rest, "unsupported generic argument"),
+                }
+            }
+        } else if !last.arguments.is_empty() {
+            self.error(&last.arguments, "unsupported generic arguments");
         }
     }
+
+    fn error<T: Spanned>(&mut self, tokens: T, message: &str) {
+        self.errors.push(syn::Error::new(tokens.span(), message));
+    }
 }
 
 fn write_member(writer: &mut winmd::Writer, namespace: &str, name: &str, member: &ModuleMember) {
@@ -103,6 +305,12 @@ fn write_interface(writer: &mut winmd::Writer, namespace: &str, name: &str, memb
         TypeNamespace: writer.strings.insert(namespace),
     });
 
+    write_docs(
+        writer,
+        writer::HasAttribute::TypeDef(writer.tables.TypeDef.len() as u32 - 1),
+        &member.attributes,
+    );
+
     for (number, generic) in member.generics.iter().enumerate() {
         writer.tables.GenericParam.push(writer::GenericParam {
             Number: number as u16,
@@ -120,6 +328,7 @@ fn write_interface(writer: &mut winmd::Writer, namespace: &str, name: &str, memb
                 writer.insert_type_ref(&type_name.namespace, &type_name.name)
             }
             winmd::Type::TypeRef(_) => writer.insert_type_spec(ty),
+            winmd::Type::IUnknown => writer.insert_type_ref("Windows.Win32.System.Com", "IUnknown"),
             rest => unimplemented!("{rest:?}"),
         };
 
@@ -167,6 +376,12 @@ fn write_interface(writer: &mut winmd::Writer, namespace: &str, name: &str, memb
                 Name: writer.strings.insert(&param.name),
             });
         }
+
+        write_docs(
+            writer,
+            writer::HasAttribute::MethodDef(writer.tables.MethodDef.len() as u32 - 1),
+            &method.attrs,
+        );
     }
 }
 
@@ -190,6 +405,12 @@ fn write_struct(writer: &mut winmd::Writer, namespace: &str, name: &str, member:
         TypeNamespace: writer.strings.insert(namespace),
     });
 
+    write_docs(
+        writer,
+        writer::HasAttribute::TypeDef(writer.tables.TypeDef.len() as u32 - 1),
+        &member.attributes,
+    );
+
     for field in &member.fields {
         let flags = metadata::FieldAttributes::Public;
         let ty = syn_type(namespace, &[], &field.ty);
@@ -200,10 +421,109 @@ fn write_struct(writer: &mut winmd::Writer, namespace: &str, name: &str, member:
             Name: writer.strings.insert(&field.name),
             Signature: signature,
         });
+
+        write_docs(
+            writer,
+            writer::HasAttribute::Field(writer.tables.Field.len() as u32 - 1),
+            &field.attributes,
+        );
     }
 }
 
-fn write_enum(_writer: &mut winmd::Writer, _namespace: &str, _name: &str, _member: &Enum) {}
+// Enums are always written with an `i32` underlying type, the only one supported by WinRT, with variants numbered
+// from zero or from the previous discriminant as in Rust.
+fn write_enum(writer: &mut winmd::Writer, namespace: &str, name: &str, member: &Enum) {
+    let mut flags = metadata::TypeAttributes::Public | metadata::TypeAttributes::Sealed;
+
+    if member.winrt {
+        flags |= metadata::TypeAttributes::WindowsRuntime
+    }
+
+    let extends = writer.insert_type_ref("System", "Enum");
+
+    writer.tables.TypeDef.push(winmd::TypeDef {
+        Extends: extends,
+        FieldList: writer.tables.Field.len() as u32,
+        MethodList: writer.tables.MethodDef.len() as u32,
+        Flags: flags.0,
+        TypeName: writer.strings.insert(name),
+        TypeNamespace: writer.strings.insert(namespace),
+    });
+
+    write_docs(
+        writer,
+        writer::HasAttribute::TypeDef(writer.tables.TypeDef.len() as u32 - 1),
+        &member.item.attrs,
+    );
+
+    let flags = metadata::FieldAttributes::Private
+        | metadata::FieldAttributes::SpecialName
+        | metadata::FieldAttributes::RTSpecialName;
+
+    let signature = writer.insert_field_sig(&winmd::Type::I32);
+
+    writer.tables.Field.push(winmd::Field {
+        Flags: flags.0,
+        Name: writer.strings.insert("value__"),
+        Signature: signature,
+    });
+
+    let flags = metadata::FieldAttributes::Public
+        | metadata::FieldAttributes::Static
+        | metadata::FieldAttributes::Literal
+        | metadata::FieldAttributes::HasDefault;
+
+    let signature = writer.insert_field_sig(&winmd::Type::TypeRef(winmd::TypeName {
+        namespace: namespace.to_string(),
+        name: name.to_string(),
+        generics: vec![],
+    }));
+
+    let mut value = 0;
+
+    for variant in &member.item.variants {
+        // The checker has already rejected any discriminant that isn't an `i32` literal.
+        if let Some((_, expr)) = &variant.discriminant {
+            value = discriminant(expr).unwrap_or(value);
+        }
+
+        writer.tables.Field.push(winmd::Field {
+            Flags: flags.0,
+            Name: writer.strings.insert(&variant.ident.to_string()),
+            Signature: signature,
+        });
+
+        let field = writer.tables.Field.len() as u32 - 1;
+
+        writer.tables.Constant.push(winmd::Constant {
+            Type: metadata::ELEMENT_TYPE_I4 as u16,
+            Parent: writer::HasConstant::Field(field),
+            Value: writer.blobs.insert(&value.to_le_bytes()),
+        });
+
+        write_docs(writer, writer::HasAttribute::Field(field), &variant.attrs);
+        value = value.wrapping_add(1);
+    }
+}
+
+fn discriminant(expr: &syn::Expr) -> Option<i32> {
+    integer(expr)?.try_into().ok()
+}
+
+fn integer(expr: &syn::Expr) -> Option<i64> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => lit.base10_parse().ok(),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => integer(expr)?.checked_neg(),
+        _ => None,
+    }
+}
 
 fn write_class(writer: &mut winmd::Writer, namespace: &str, name: &str, member: &Class) {
     let flags = metadata::TypeAttributes::Public
@@ -229,6 +549,12 @@ fn write_class(writer: &mut winmd::Writer, namespace: &str, name: &str, member:
         TypeNamespace: writer.strings.insert(namespace),
     });
 
+    write_docs(
+        writer,
+        writer::HasAttribute::TypeDef(writer.tables.TypeDef.len() as u32 - 1),
+        &member.attributes,
+    );
+
     for (index, extends) in member.extends.iter().enumerate() {
         let ty = syn_type_path(namespace, &[], extends);
 
@@ -255,6 +581,68 @@ fn write_class(writer: &mut winmd::Writer, namespace: &str, name: &str, member:
     }
 }
 
+// Doc comments are stored using a `DocumentationAttribute` with a single string argument so that they survive the
+// round trip through metadata and may be used by other language projections.
+fn write_docs(
+    writer: &mut winmd::Writer,
+    parent: writer::HasAttribute,
+    attributes: &[syn::Attribute],
+) {
+    let docs = docs(attributes);
+
+    if docs.is_empty() {
+        return;
+    }
+
+    let signature = writer.insert_method_sig(
+        metadata::MethodCallAttributes::HASTHIS,
+        &winmd::Type::Void,
+        &[winmd::Type::String],
+    );
+
+    let constructor = writer.insert_member_ref(
+        "Windows.Foundation.Metadata",
+        "DocumentationAttribute",
+        ".ctor",
+        signature,
+    );
+
+    let mut values = 1u16.to_le_bytes().to_vec(); // prolog
+    writer::usize_blob(docs.len(), &mut values);
+    values.extend_from_slice(docs.as_bytes());
+    values.extend_from_slice(&0u16.to_le_bytes()); // named arg count
+
+    writer.tables.CustomAttribute.push(winmd::CustomAttribute {
+        Parent: parent,
+        Type: writer::AttributeType::MemberRef(constructor),
+        Value: writer.blobs.insert(&values),
+    });
+}
+
+fn docs(attributes: &[syn::Attribute]) -> String {
+    let mut lines = vec![];
+
+    for attribute in attributes {
+        if let syn::Meta::NameValue(meta) = &attribute.meta {
+            if let syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(doc),
+                ..
+            }) = &meta.value
+            {
+                if meta.path.is_ident("doc") {
+                    let doc = doc.value();
+
+                    for line in doc.split('\n') {
+                        lines.push(line.strip_prefix(' ').unwrap_or(line).to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    lines.join("\n")
+}
+
 fn syn_signature(namespace: &str, generics: &[String], sig: &syn::Signature) -> winmd::Signature {
     let params = sig
         .inputs
@@ -334,39 +722,14 @@ fn syn_path(namespace: &str, generics: &[String], path: &syn::Path) -> winmd::Ty
                 return winmd::Type::GenericParam(number as u16);
             }
 
-            match name.as_str() {
-                "void" => return winmd::Type::Void,
-                "bool" => return winmd::Type::Bool,
-                "char" => return winmd::Type::Char,
-                "i8" => return winmd::Type::I8,
-                "u8" => return winmd::Type::U8,
-                "i16" => return winmd::Type::I16,
-                "u16" => return winmd::Type::U16,
-                "i32" => return winmd::Type::I32,
-                "u32" => return winmd::Type::U32,
-                "i64" => return winmd::Type::I64,
-                "u64" => return winmd::Type::U64,
-                "f32" => return winmd::Type::F32,
-                "f64" => return winmd::Type::F64,
-                "isize" => return winmd::Type::ISize,
-                "usize" => return winmd::Type::USize,
-                "HSTRING" => return winmd::Type::String,
-                "GUID" => return winmd::Type::GUID,
-                "IUnknown" => return winmd::Type::IUnknown,
-                "IInspectable" => return winmd::Type::IInspectable,
-                "HRESULT" => return winmd::Type::HRESULT,
-                "PSTR" => return winmd::Type::PSTR,
-                "PWSTR" => return winmd::Type::PWSTR,
-                "PCSTR" => return winmd::Type::PCSTR,
-                "PCWSTR" => return winmd::Type::PCWSTR,
-                "BSTR" => return winmd::Type::BSTR,
-                _ => {}
-            };
+            if let Some(ty) = syn_primitive(&name) {
+                return ty;
+            }
         }
     }
 
-    // TODO: Here we assume that paths are absolute since there's no way to disambiguate between nested and absolute paths
-    // The canonicalize function (should maybe) preprocesses the IDL to make this work
+    // Paths are fully qualified by `File::canonicalize` so any remaining `super` keywords are only encountered if that
+    // failed, in which case the resulting winmd is discarded anyway.
 
     let mut builder = vec![];
 
@@ -413,3 +776,34 @@ fn syn_path(namespace: &str, generics: &[String], path: &syn::Path) -> winmd::Ty
         generics: type_generics,
     })
 }
+
+pub fn syn_primitive(name: &str) -> Option<winmd::Type> {
+    Some(match name {
+        "void" => winmd::Type::Void,
+        "bool" => winmd::Type::Bool,
+        "char" => winmd::Type::Char,
+        "i8" => winmd::Type::I8,
+        "u8" => winmd::Type::U8,
+        "i16" => winmd::Type::I16,
+        "u16" => winmd::Type::U16,
+        "i32" => winmd::Type::I32,
+        "u32" => winmd::Type::U32,
+        "i64" => winmd::Type::I64,
+        "u64" => winmd::Type::U64,
+        "f32" => winmd::Type::F32,
+        "f64" => winmd::Type::F64,
+        "isize" => winmd::Type::ISize,
+        "usize" => winmd::Type::USize,
+        "HSTRING" => winmd::Type::String,
+        "GUID" => winmd::Type::GUID,
+        "IUnknown" => winmd::Type::IUnknown,
+        "IInspectable" => winmd::Type::IInspectable,
+        "HRESULT" => winmd::Type::HRESULT,
+        "PSTR" => winmd::Type::PSTR,
+        "PWSTR" => winmd::Type::PWSTR,
+        "PCSTR" => winmd::Type::PCSTR,
+        "PCWSTR" => winmd::Type::PCWSTR,
+        "BSTR" => winmd::Type::BSTR,
+        _ => return None,
+    })
+}
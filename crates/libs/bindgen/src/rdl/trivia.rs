@@ -0,0 +1,179 @@
+// `syn` discards comments while tokenizing so they're collected separately here and later woven back into the
+// formatted output by position. Doc comments (`///`, `//!`, `/** */`, and `/*! */`) are left alone since `syn`
+// already preserves them as `doc` attributes.
+//
+// Comments are attached to the nearest enclosing item, field, variant, method, or parameter by the formatter, either
+// trailing the tokens they follow on the same line or leading the tokens that follow them. Comments anywhere else,
+// such as within a type or between an item's name and its base types, are written on their own line ahead of the
+// next such element.
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comment {
+    /// The position at which the comment starts, using the same line and column numbering as `proc_macro2`.
+    pub start: proc_macro2::LineColumn,
+    /// The comment text including the `//` or `/* */` delimiters.
+    pub text: String,
+}
+
+pub fn comments(input: &str) -> Vec<Comment> {
+    let mut scanner = Scanner {
+        input,
+        offset: 0,
+        line: 1,
+        line_start: 0,
+    };
+
+    let mut comments = vec![];
+    let mut ident = false;
+
+    while let Some(c) = input[scanner.offset..].chars().next() {
+        let rest = &input[scanner.offset..];
+        let start = scanner.position();
+
+        let len = if rest.starts_with("//") {
+            let len = rest.find('\n').unwrap_or(rest.len());
+            let text = rest[..len].trim_end();
+
+            if !is_doc(text, "///", "//!") {
+                comments.push(Comment {
+                    start,
+                    text: text.to_string(),
+                });
+            }
+
+            len
+        } else if rest.starts_with("/*") {
+            let len = block_comment_len(rest);
+            let text = &rest[..len];
+
+            if !is_doc(text, "/**", "/*!") || text.starts_with("/***") {
+                comments.push(Comment {
+                    start,
+                    text: text.to_string(),
+                });
+            }
+
+            len
+        } else if c == '"' {
+            string_len(rest)
+        } else if c == '\'' {
+            char_len(rest)
+        } else if let Some(len) = raw_string_len(rest).filter(|_| !ident) {
+            len
+        } else {
+            c.len_utf8()
+        };
+
+        ident = len == c.len_utf8() && (c.is_alphanumeric() || c == '_');
+        scanner.skip(len);
+    }
+
+    comments
+}
+
+struct Scanner<'a> {
+    input: &'a str,
+    offset: usize,
+    line: usize,
+    line_start: usize,
+}
+
+impl Scanner<'_> {
+    fn position(&self) -> proc_macro2::LineColumn {
+        proc_macro2::LineColumn {
+            line: self.line,
+            column: self.input[self.line_start..self.offset].chars().count(),
+        }
+    }
+
+    fn skip(&mut self, len: usize) {
+        let end = self.offset + len;
+
+        for (index, c) in self.input[self.offset..end].char_indices() {
+            if c == '\n' {
+                self.line += 1;
+                self.line_start = self.offset + index + 1;
+            }
+        }
+
+        self.offset = end;
+    }
+}
+
+fn is_doc(text: &str, outer: &str, inner: &str) -> bool {
+    (text.starts_with(outer) && !text[outer.len()..].starts_with('/')) || text.starts_with(inner)
+}
+
+// Block comments nest in Rust and thus also in RDL.
+fn block_comment_len(input: &str) -> usize {
+    let mut depth = 0;
+    let mut offset = 0;
+
+    while offset < input.len() {
+        let rest = &input[offset..];
+
+        if rest.starts_with("/*") {
+            depth += 1;
+            offset += 2;
+        } else if rest.starts_with("*/") {
+            depth -= 1;
+            offset += 2;
+
+            if depth == 0 {
+                return offset;
+            }
+        } else {
+            offset += rest.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+
+    input.len()
+}
+
+fn string_len(input: &str) -> usize {
+    let mut chars = input.char_indices().skip(1);
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => _ = chars.next(),
+            '"' => return index + 1,
+            _ => {}
+        }
+    }
+
+    input.len()
+}
+
+// A quote either starts a character literal like `'a'` or `'\''` or is part of a lifetime or label like `'a`, in
+// which case only the quote itself is skipped.
+fn char_len(input: &str) -> usize {
+    let mut chars = input.char_indices().skip(1);
+
+    match chars.next() {
+        Some((_, '\\')) => input
+            .get(3..)
+            .and_then(|rest| rest.find('\''))
+            .map_or(input.len(), |end| end + 4),
+        Some((index, c)) if input[index + c.len_utf8()..].starts_with('\'') => {
+            index + c.len_utf8() + 1
+        }
+        _ => 1,
+    }
+}
+
+// Raw strings like `r#"..."#` may contain quotes and thus need to be skipped as a whole.
+fn raw_string_len(input: &str) -> Option<usize> {
+    let rest = input
+        .strip_prefix("br")
+        .or_else(|| input.strip_prefix('r'))?;
+
+    let hashes = rest.len() - rest.trim_start_matches('#').len();
+    let body = rest[hashes..].strip_prefix('"')?;
+    let prefix = input.len() - body.len();
+    let close = format!("\"{}", "#".repeat(hashes));
+
+    Some(
+        body.find(&close)
+            .map_or(input.len(), |end| prefix + end + close.len()),
+    )
+}
@@ -1,7 +1,7 @@
 use super::*;
 use metadata::HasAttributes;
 
-pub fn writer(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
+pub fn writer(writer: &Writer<'_>, def: metadata::TypeDef<'_>) -> TokenStream {
     if !def
         .flags()
         .contains(metadata::TypeAttributes::WindowsRuntime)
@@ -23,7 +23,7 @@ pub fn writer(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
     }
 }
 
-fn gen_class(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
+fn gen_class(writer: &Writer<'_>, def: metadata::TypeDef<'_>) -> TokenStream {
     if def.extends() == Some(metadata::TypeName::Attribute) {
         return TokenStream::new();
     }
@@ -35,6 +35,7 @@ fn gen_class(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
 
     let cfg = cfg::type_def_cfg(writer, def, &[]);
     let features = writer.cfg_features(&cfg);
+    let docs = writer.docs(def);
 
     for interface in &interfaces {
         if let metadata::Type::TypeDef(def, generics) = &interface.ty {
@@ -98,6 +99,7 @@ fn gen_class(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
         };
 
         let mut tokens = quote! {
+            #docs
             #features
             #[repr(transparent)]
             #[derive(PartialEq, Eq, Debug, Clone)]
@@ -153,6 +155,7 @@ fn gen_class(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
         tokens
     } else {
         let mut tokens = quote! {
+            #docs
             #features
             pub struct #name;
             #features
@@ -168,11 +171,11 @@ fn gen_class(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
 }
 
 fn gen_conversions(
-    writer: &Writer,
-    def: metadata::TypeDef,
+    writer: &Writer<'_>,
+    def: metadata::TypeDef<'_>,
     ident: &TokenStream,
-    interfaces: &[metadata::Interface],
-    cfg: &cfg::Cfg,
+    interfaces: &[metadata::Interface<'_>],
+    cfg: &cfg::Cfg<'_>,
 ) -> TokenStream {
     let features = writer.cfg_features(cfg);
     let mut tokens = quote! {
@@ -218,7 +221,7 @@ fn gen_conversions(
     tokens
 }
 
-fn type_def_has_default_constructor(row: metadata::TypeDef) -> bool {
+fn type_def_has_default_constructor(row: metadata::TypeDef<'_>) -> bool {
     for attribute in row.attributes() {
         if attribute.name() == "ActivatableAttribute" {
             if attribute
@@ -235,7 +238,7 @@ fn type_def_has_default_constructor(row: metadata::TypeDef) -> bool {
     false
 }
 
-fn type_is_exclusive(ty: &metadata::Type) -> bool {
+fn type_is_exclusive(ty: &metadata::Type<'_>) -> bool {
     match ty {
         metadata::Type::TypeDef(row, _) => metadata::type_def_is_exclusive(*row),
         _ => false,
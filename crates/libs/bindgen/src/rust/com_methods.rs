@@ -1,10 +1,10 @@
 use super::*;
 
 pub fn writer(
-    writer: &Writer,
-    def: metadata::TypeDef,
+    writer: &Writer<'_>,
+    def: metadata::TypeDef<'_>,
     kind: metadata::InterfaceKind,
-    method: metadata::MethodDef,
+    method: metadata::MethodDef<'_>,
     method_names: &mut MethodNames,
     virtual_names: &mut MethodNames,
 ) -> TokenStream {
@@ -27,8 +27,9 @@ pub fn writer(
         return quote! {};
     }
 
+    let docs = writer.docs(method);
     let kind = signature.kind();
-    match kind {
+    let tokens = match kind {
         metadata::SignatureKind::Query(_) => {
             let args = writer.win32_args(&signature.params, kind);
             let params = writer.win32_params(&signature.params, kind);
@@ -163,10 +164,19 @@ pub fn writer(
                 }
             }
         }
+    };
+
+    quote! {
+        #docs
+        #tokens
     }
 }
 
-pub fn gen_upcall(writer: &Writer, sig: &metadata::Signature, inner: TokenStream) -> TokenStream {
+pub fn gen_upcall(
+    writer: &Writer<'_>,
+    sig: &metadata::Signature<'_>,
+    inner: TokenStream,
+) -> TokenStream {
     match sig.kind() {
         metadata::SignatureKind::ResultValue => {
             let invoke_args = sig.params[..sig.params.len() - 1]
@@ -221,7 +231,7 @@ pub fn gen_upcall(writer: &Writer, sig: &metadata::Signature, inner: TokenStream
     }
 }
 
-fn gen_win32_invoke_arg(writer: &Writer, param: &metadata::SignatureParam) -> TokenStream {
+fn gen_win32_invoke_arg(writer: &Writer<'_>, param: &metadata::SignatureParam<'_>) -> TokenStream {
     let name = writer.param_name(param.def);
 
     if param.def.flags().contains(metadata::ParamAttributes::In)
@@ -1,7 +1,23 @@
 use super::*;
 use metadata::HasAttributes;
 
-pub fn writer(writer: &Writer, def: metadata::Field) -> TokenStream {
+pub fn writer(writer: &Writer<'_>, def: metadata::Field<'_>) -> TokenStream {
+    let tokens = gen_constant(writer, def);
+
+    // Unscoped enum values are also written as constants so this is where they pick up their docs.
+    if tokens.is_empty() {
+        tokens
+    } else {
+        let docs = writer.docs(def);
+
+        quote! {
+            #docs
+            #tokens
+        }
+    }
+}
+
+fn gen_constant(writer: &Writer<'_>, def: metadata::Field<'_>) -> TokenStream {
     let name = to_ident(def.name());
     let ty = def.ty(None).to_const_type();
     let cfg = cfg::field_cfg(writer, def);
@@ -78,7 +94,7 @@ pub fn writer(writer: &Writer, def: metadata::Field) -> TokenStream {
     }
 }
 
-fn is_signed_error(ty: &metadata::Type) -> bool {
+fn is_signed_error(ty: &metadata::Type<'_>) -> bool {
     match ty {
         metadata::Type::Name(metadata::TypeName::HResult) => true,
         metadata::Type::TypeDef(def, _) => def.type_name() == metadata::TypeName::NTSTATUS,
@@ -86,7 +102,7 @@ fn is_signed_error(ty: &metadata::Type) -> bool {
     }
 }
 
-fn initializer(writer: &Writer, def: metadata::Field) -> Option<TokenStream> {
+fn initializer(writer: &Writer<'_>, def: metadata::Field<'_>) -> Option<TokenStream> {
     let value = constant(def)?;
     let mut input = value.as_str();
 
@@ -106,8 +122,8 @@ fn initializer(writer: &Writer, def: metadata::Field) -> Option<TokenStream> {
 }
 
 fn field_initializer<'a>(
-    writer: &Writer,
-    field: metadata::Field,
+    writer: &Writer<'_>,
+    field: metadata::Field<'_>,
     input: &'a str,
 ) -> (TokenStream, &'a str) {
     let name = to_ident(field.name());
@@ -137,7 +153,7 @@ fn field_initializer<'a>(
     }
 }
 
-fn constant(def: metadata::Field) -> Option<String> {
+fn constant(def: metadata::Field<'_>) -> Option<String> {
     def.find_attribute("ConstantAttribute").map(|attribute| {
         let args = attribute.args();
         match &args[0].1 {
@@ -194,16 +210,16 @@ fn read_literal_array(input: &str, len: usize) -> (Vec<&str>, &str) {
     (result, read_token(input, b'}'))
 }
 
-fn field_guid(row: metadata::Field) -> Option<metadata::Guid> {
+fn field_guid(row: metadata::Field<'_>) -> Option<metadata::Guid> {
     row.find_attribute("GuidAttribute")
         .map(|attribute| metadata::Guid::from_args(&attribute.args()))
 }
 
-fn field_is_ansi(row: metadata::Field) -> bool {
+fn field_is_ansi(row: metadata::Field<'_>) -> bool {
     row.find_attribute("NativeEncodingAttribute").is_some_and(|attribute| matches!(attribute.args().first(), Some((_, metadata::Value::String(encoding))) if encoding == "ansi"))
 }
 
-fn type_has_replacement(ty: &metadata::Type) -> bool {
+fn type_has_replacement(ty: &metadata::Type<'_>) -> bool {
     match ty {
         metadata::Type::Name(metadata::TypeName::HResult)
         | metadata::Type::Const(metadata::TypeName::PSTR)
@@ -215,7 +231,7 @@ fn type_has_replacement(ty: &metadata::Type) -> bool {
     }
 }
 
-fn type_underlying_type(ty: &metadata::Type) -> metadata::Type {
+fn type_underlying_type<'a>(ty: &metadata::Type<'a>) -> metadata::Type<'a> {
     match ty {
         metadata::Type::TypeDef(row, _) => row.underlying_type(),
         metadata::Type::Name(metadata::TypeName::HResult) => metadata::Type::I32,
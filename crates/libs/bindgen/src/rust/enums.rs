@@ -1,22 +1,21 @@
 use super::*;
 use metadata::HasAttributes;
 
-pub fn writer(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
+pub fn writer(writer: &Writer<'_>, def: metadata::TypeDef<'_>) -> TokenStream {
     let type_name = def.type_name();
-    let ident = to_ident(type_name.name());
+    let ident = to_ident(writer.type_def_rename(def));
     let underlying_type = def.underlying_type();
     let underlying_type = writer.type_name(&underlying_type);
 
-    // TODO: unscoped enums should be removed from metadata
-    let is_scoped = def
-        .flags()
-        .contains(metadata::TypeAttributes::WindowsRuntime)
-        || def.has_attribute("ScopedEnumAttribute");
+    let is_scoped = is_scoped(def);
 
     let cfg = cfg::type_def_cfg(writer, def, &[]);
     let features = writer.cfg_features(&cfg);
 
-    let fields: Vec<(TokenStream, TokenStream)> = def
+    let docs = writer.docs(def);
+    let attributes = writer.override_attributes(&type_name.to_string());
+
+    let fields: Vec<(TokenStream, TokenStream, TokenStream)> = def
         .fields()
         .filter_map(|field| {
             if field.flags().contains(metadata::FieldAttributes::Literal) {
@@ -24,44 +23,47 @@ pub fn writer(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
                 let constant = field.constant().unwrap();
                 let value = writer.value(&constant.value());
 
-                Some((field_name, value))
+                Some((field_name, value, writer.docs(field)))
             } else {
                 None
             }
         })
         .collect();
 
-    let derive = if writer.sys {
-        if is_scoped {
-            quote! {
-                #[derive(Copy, Clone)]
-            }
-        } else {
-            quote! {}
-        }
+    let derive = derives(writer, def);
+
+    let derive = if derive.is_empty() {
+        quote! {}
     } else {
+        let derive = derive.iter().map(|derive| to_ident(derive));
+
         quote! {
-            #[derive(PartialEq, Eq, Copy, Clone, Default)]
+            #[derive(#(#derive),*)]
         }
     };
 
     let mut tokens = if is_scoped || !writer.sys {
         quote! {
+            #docs
             #features
             #[repr(transparent)]
             #derive
+            #attributes
             pub struct #ident(pub #underlying_type);
         }
     } else {
         quote! {
+            #docs
             #features
+            #attributes
             pub type #ident = #underlying_type;
         }
     };
 
     if is_scoped {
-        let fields = fields.iter().map(|(field_name, value)| {
+        let fields = fields.iter().map(|(field_name, value, docs)| {
             quote! {
+                #docs
                 pub const #field_name: Self = Self(#value);
             }
         });
@@ -75,19 +77,7 @@ pub fn writer(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
     }
 
     if !writer.sys {
-        let name = type_name.name();
-        tokens.combine(&quote! {
-            #features
-            impl windows_core::TypeKind for #ident {
-                type TypeKind = windows_core::CopyType;
-            }
-            #features
-            impl core::fmt::Debug for #ident {
-                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                    f.debug_tuple(#name).field(&self.0).finish()
-                }
-            }
-        });
+        let name = writer.type_def_rename(def);
 
         // Win32 enums use the Flags attribute. WinRT enums don't have the Flags attribute but are paritioned merely based
         // on whether they are signed.
@@ -98,6 +88,30 @@ pub fn writer(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
                 .contains(metadata::TypeAttributes::WindowsRuntime)
                 && def.underlying_type() == metadata::Type::U32);
 
+        tokens.combine(&quote! {
+            #features
+            impl windows_core::TypeKind for #ident {
+                type TypeKind = windows_core::CopyType;
+            }
+        });
+
+        if type_def_is_flags && writer.decode_flags {
+            tokens.combine(&gen_decode_flags(&ident, &features, &fields));
+        } else {
+            tokens.combine(&quote! {
+                #features
+                impl core::fmt::Debug for #ident {
+                    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        f.debug_tuple(#name).field(&self.0).finish()
+                    }
+                }
+            });
+        }
+
+        if writer.rust_enums && !type_def_is_flags {
+            tokens.combine(&gen_rust_enum(writer, def, &ident, &features, &fields));
+        }
+
         if type_def_is_flags {
             tokens.combine(&quote! {
                 #features
@@ -162,3 +176,154 @@ pub fn writer(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
 
     tokens
 }
+
+/// The traits that the writer derives for the given enum.
+pub fn derives(writer: &Writer<'_>, def: metadata::TypeDef<'_>) -> &'static [&'static str] {
+    if !writer.sys {
+        &["PartialEq", "Eq", "Copy", "Clone", "Default"]
+    } else if is_scoped(def) {
+        &["Copy", "Clone"]
+    } else {
+        &[]
+    }
+}
+
+// TODO: unscoped enums should be removed from metadata
+fn is_scoped(def: metadata::TypeDef<'_>) -> bool {
+    def.flags()
+        .contains(metadata::TypeAttributes::WindowsRuntime)
+        || def.has_attribute("ScopedEnumAttribute")
+}
+
+// Decodes the set bits into the names of the flags, leaving any unnamed bits in hexadecimal. Names are only reported
+// for bits that weren't already covered by an earlier name so that aliases and combinations aren't repeated.
+fn gen_decode_flags(
+    ident: &TokenStream,
+    features: &TokenStream,
+    fields: &[(TokenStream, TokenStream, TokenStream)],
+) -> TokenStream {
+    let names = fields.iter().map(|(field_name, value, _)| {
+        let field_name = field_name.as_str();
+        quote! { (#field_name, Self(#value)), }
+    });
+
+    quote! {
+        #features
+        impl #ident {
+            const NAMES: &'static [(&'static str, Self)] = &[#(#names)*];
+
+            pub fn iter_names(&self) -> impl Iterator<Item = (&'static str, Self)> {
+                let bits = self.0;
+                let mut remaining = self.0;
+
+                Self::NAMES.iter().copied().filter(move |(_, value)| {
+                    if value.0 != 0 && bits & value.0 == value.0 && remaining & value.0 != 0 {
+                        remaining &= !value.0;
+                        true
+                    } else {
+                        false
+                    }
+                })
+            }
+
+            pub fn from_name(name: &str) -> Option<Self> {
+                Self::NAMES.iter().find(|(flag, _)| *flag == name).map(|(_, value)| *value)
+            }
+        }
+        #features
+        impl core::fmt::Display for #ident {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                if self.0 == 0 {
+                    return match Self::NAMES.iter().find(|(_, value)| value.0 == 0) {
+                        Some((name, _)) => f.write_str(name),
+                        None => f.write_str("0"),
+                    };
+                }
+
+                let mut remaining = self.0;
+                let mut separator = "";
+
+                for (name, value) in self.iter_names() {
+                    f.write_str(separator)?;
+                    f.write_str(name)?;
+                    remaining &= !value.0;
+                    separator = " | ";
+                }
+
+                if remaining != 0 {
+                    f.write_str(separator)?;
+                    write!(f, "{:#x}", remaining)?;
+                }
+
+                Ok(())
+            }
+        }
+        #features
+        impl core::fmt::Debug for #ident {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Display::fmt(self, f)
+            }
+        }
+    }
+}
+
+// A Rust enum can be matched exhaustively whereas the newtype must remain for ABI compatibility. Only the first of any
+// constants sharing a value becomes a variant so that conversions are unambiguous.
+fn gen_rust_enum(
+    writer: &Writer<'_>,
+    def: metadata::TypeDef<'_>,
+    ident: &TokenStream,
+    features: &TokenStream,
+    fields: &[(TokenStream, TokenStream, TokenStream)],
+) -> TokenStream {
+    let name = to_ident(&format!("{}Enum", writer.type_def_rename(def)));
+    let mut values = std::collections::HashSet::new();
+
+    let fields: Vec<_> = fields
+        .iter()
+        .filter(|(_, value, _)| values.insert(value.as_str()))
+        .collect();
+
+    let variants = fields.iter().map(|(field_name, _, docs)| {
+        quote! {
+            #docs
+            #field_name,
+        }
+    });
+
+    let try_from = fields.iter().map(|(field_name, value, _)| {
+        quote! { #ident(#value) => Ok(Self::#field_name), }
+    });
+
+    let from = fields.iter().map(|(field_name, value, _)| {
+        quote! { #name::#field_name => Self(#value), }
+    });
+
+    quote! {
+        #features
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum #name {
+            #(#variants)*
+        }
+        #features
+        impl core::convert::TryFrom<#ident> for #name {
+            type Error = #ident;
+
+            fn try_from(value: #ident) -> Result<Self, Self::Error> {
+                match value {
+                    #(#try_from)*
+                    _ => Err(value),
+                }
+            }
+        }
+        #features
+        impl From<#name> for #ident {
+            fn from(value: #name) -> Self {
+                match value {
+                    #(#from)*
+                }
+            }
+        }
+    }
+}
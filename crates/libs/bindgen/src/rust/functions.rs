@@ -1,27 +1,114 @@
 use super::*;
 use metadata::HasAttributes;
 
-pub fn writer(writer: &Writer, namespace: &str, def: metadata::MethodDef) -> TokenStream {
-    // TODO: remove inline functions from metadata
-    if def.module_name() == "FORCEINLINE" {
+pub fn writer(writer: &Writer<'_>, namespace: &str, def: metadata::MethodDef<'_>) -> TokenStream {
+    if !is_importable(def) {
         return quote! {};
     }
 
-    // TODO: remove ordinal functions from metadata
-    if let Some(impl_map) = def.impl_map() {
-        if impl_map.import_name().starts_with('#') {
-            return quote! {};
+    if writer.sys {
+        gen_sys_function(writer, namespace, def)
+    } else {
+        let docs = writer.docs(def);
+        let attributes = writer.override_attributes(&format!("{namespace}.{}", def.name()));
+        let tokens = gen_win_function(writer, namespace, def);
+
+        quote! {
+            #docs
+            #attributes
+            #tokens
         }
     }
+}
 
-    if writer.sys {
-        gen_sys_function(writer, namespace, def)
+/// Returns the library, calling convention, x86 import name type, and declaration of a function so that standalone
+/// bindings can group functions into `raw-dylib` extern blocks rather than depending on `windows-targets`.
+pub fn raw_dylib(
+    writer: &Writer<'_>,
+    namespace: &str,
+    def: metadata::MethodDef<'_>,
+) -> Option<(String, &'static str, &'static str, TokenStream)> {
+    if !is_importable(def) {
+        return None;
+    }
+
+    let signature = metadata::method_def_signature(namespace, def, &[]);
+
+    if !cfg::signature_cfg(writer, def).included(writer) {
+        return None;
+    }
+
+    let mut library = if namespace.starts_with("Windows.") {
+        def.module_name().to_lowercase()
+    } else {
+        def.module_name().to_string()
+    };
+
+    // The `+verbatim` modifier requires the full file name.
+    if !library.contains('.') {
+        library.push_str(".dll");
+    }
+
+    // Outside of x86 the calling conventions are the same and Rust only accepts "C".
+    let abi = match method_def_extern_abi(def) {
+        "cdecl" => "C",
+        abi => abi,
+    };
+
+    let attributes = sys_attributes(writer, namespace, def);
+
+    Some((
+        library,
+        abi,
+        import_name_type(def),
+        link_declaration(writer, namespace, &signature, &attributes),
+    ))
+}
+
+// On x86 the name a library exports may be decorated according to the calling convention, as in `_Name` for cdecl or
+// `_Name@8` for stdcall. The metadata records the name as exported, so comparing it with the function name tells
+// `raw-dylib` which decoration to apply. The system libraries export undecorated names regardless of the calling
+// convention, which is what `windows-targets` assumes for every function.
+fn import_name_type(def: metadata::MethodDef<'_>) -> &'static str {
+    let name = def.name();
+    let import_name = def
+        .impl_map()
+        .map_or(name, |impl_map| impl_map.import_name());
+
+    // Whatever follows the name is either nothing or the stdcall argument size.
+    let decorated = |prefix: &str| {
+        import_name
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_prefix(name))
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('@'))
+    };
+
+    if import_name == name {
+        "undecorated"
+    } else if decorated("_") {
+        "decorated"
+    } else if decorated("") {
+        "noprefix"
     } else {
-        gen_win_function(writer, namespace, def)
+        "undecorated"
     }
 }
 
-fn gen_sys_function(writer: &Writer, namespace: &str, def: metadata::MethodDef) -> TokenStream {
+// TODO: remove inline and ordinal functions from metadata
+fn is_importable(def: metadata::MethodDef<'_>) -> bool {
+    if def.module_name() == "FORCEINLINE" {
+        return false;
+    }
+
+    !def.impl_map()
+        .is_some_and(|impl_map| impl_map.import_name().starts_with('#'))
+}
+
+fn gen_sys_function(
+    writer: &Writer<'_>,
+    namespace: &str,
+    def: metadata::MethodDef<'_>,
+) -> TokenStream {
     let signature = metadata::method_def_signature(namespace, def, &[]);
     let cfg = cfg::signature_cfg(writer, def);
 
@@ -29,20 +116,55 @@ fn gen_sys_function(writer: &Writer, namespace: &str, def: metadata::MethodDef)
         return quote! {};
     }
 
+    let attributes = sys_attributes(writer, namespace, def);
     let mut tokens = writer.cfg_features(&cfg);
-    tokens.combine(&gen_link(writer, namespace, &signature));
+    tokens.combine(&gen_link(writer, namespace, &signature, &attributes));
+    tokens
+}
+
+// The `link!` macro accepts a single attribute ahead of the function so the docs are joined into one `#[doc]`
+// attribute rather than one per line.
+fn sys_attributes(
+    writer: &Writer<'_>,
+    namespace: &str,
+    def: metadata::MethodDef<'_>,
+) -> TokenStream {
+    let mut tokens = quote! {};
+
+    if writer.docs {
+        if let Some(docs) = metadata::row_docs(def) {
+            let docs: Vec<String> = docs
+                .lines()
+                .map(|line| {
+                    if line.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" {line}")
+                    }
+                })
+                .collect();
+
+            tokens.combine(&format!("#[doc = {:?}]", docs.join("\n")).into());
+        }
+    }
+
+    tokens.combine(&writer.override_attributes(&format!("{namespace}.{}", def.name())));
     tokens
 }
 
-fn gen_win_function(writer: &Writer, namespace: &str, def: metadata::MethodDef) -> TokenStream {
-    let name = to_ident(def.name());
+fn gen_win_function(
+    writer: &Writer<'_>,
+    namespace: &str,
+    def: metadata::MethodDef<'_>,
+) -> TokenStream {
+    let name = to_ident(writer.function_rename(namespace, def));
     let signature = metadata::method_def_signature(namespace, def, &[]);
     let generics = writer.constraint_generics(&signature.params);
     let where_clause = writer.where_clause(&signature.params);
     let abi_return_type = writer.return_sig(&signature);
     let cfg = cfg::signature_cfg(writer, def);
     let features = writer.cfg_features(&cfg);
-    let link = gen_link(writer, namespace, &signature);
+    let link = gen_link(writer, namespace, &signature, &quote! {});
 
     let kind = signature.kind();
     match kind {
@@ -201,9 +323,14 @@ fn gen_win_function(writer: &Writer, namespace: &str, def: metadata::MethodDef)
     }
 }
 
-fn gen_link(writer: &Writer, namespace: &str, signature: &metadata::Signature) -> TokenStream {
-    let name = signature.def.name();
-    let ident = to_ident(name);
+fn gen_link(
+    writer: &Writer<'_>,
+    namespace: &str,
+    signature: &metadata::Signature<'_>,
+    attributes: &TokenStream,
+) -> TokenStream {
+    // A renamed function still links to its original symbol.
+    let name = writer.function_rename(namespace, signature.def);
 
     // Windows libs are always produced with lowercase module names.
     let library = if namespace.starts_with("Windows.") {
@@ -213,49 +340,16 @@ fn gen_link(writer: &Writer, namespace: &str, signature: &metadata::Signature) -
     };
 
     let abi = method_def_extern_abi(signature.def);
-
-    let symbol = if let Some(impl_map) = signature.def.impl_map() {
-        impl_map.import_name()
-    } else {
-        name
-    };
-
-    let link_name = if symbol != name {
-        quote! { #[link_name = #symbol] }
-    } else {
-        quote! {}
-    };
-
-    let params = signature.params.iter().map(|p| {
-        let name = writer.param_name(p.def);
-        let tokens = if p.kind == metadata::SignatureParamKind::ValueType {
-            writer.type_default_name(&p.ty)
-        } else {
-            writer.type_abi_name(&p.ty)
-        };
-        quote! { #name: #tokens }
-    });
-
-    let return_type = writer.return_sig(signature);
-
-    let vararg = if writer.sys
-        && signature
-            .call_flags
-            .contains(metadata::MethodCallAttributes::VARARG)
-    {
-        "...".into()
-    } else {
-        quote! {}
-    };
+    let symbol = link_symbol(signature.def);
 
     if !namespace.starts_with("Windows.") {
         let library = library.trim_end_matches(".dll");
+        let declaration = link_declaration(writer, namespace, signature, attributes);
 
         quote! {
             #[link(name = #library)]
             extern #abi {
-                #link_name
-                pub fn #ident(#(#params,)* #vararg) #return_type;
+                #declaration
             }
         }
     } else {
@@ -266,16 +360,86 @@ fn gen_link(writer: &Writer, namespace: &str, signature: &metadata::Signature) -
         };
 
         let mut tokens = String::new();
-        for param in params {
+        for param in link_params(writer, signature) {
             tokens.push_str(&format!("{}, ", param.as_str()));
         }
-        tokens.push_str(&vararg.0);
+        tokens.push_str(&link_vararg(writer, signature).0);
         let tokens = tokens.trim_end_matches(", ");
-        format!(r#"windows_targets::link!("{library}" "{abi}"{symbol} fn {name}({tokens}){return_type});"#).into()
+        let return_type = writer.return_sig(signature);
+        let attributes = if attributes.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", attributes.as_str())
+        };
+
+        format!(r#"windows_targets::link!("{library}" "{abi}"{symbol}{attributes} fn {name}({tokens}){return_type});"#).into()
+    }
+}
+
+fn link_declaration(
+    writer: &Writer<'_>,
+    namespace: &str,
+    signature: &metadata::Signature<'_>,
+    attributes: &TokenStream,
+) -> TokenStream {
+    let name = writer.function_rename(namespace, signature.def);
+    let ident = to_ident(name);
+    let symbol = link_symbol(signature.def);
+
+    let link_name = if symbol != name {
+        quote! { #[link_name = #symbol] }
+    } else {
+        quote! {}
+    };
+
+    let params = link_params(writer, signature);
+    let vararg = link_vararg(writer, signature);
+    let return_type = writer.return_sig(signature);
+
+    quote! {
+        #attributes
+        #link_name
+        pub fn #ident(#(#params,)* #vararg) #return_type;
+    }
+}
+
+fn link_symbol(def: metadata::MethodDef<'_>) -> &str {
+    if let Some(impl_map) = def.impl_map() {
+        impl_map.import_name()
+    } else {
+        def.name()
+    }
+}
+
+fn link_params(writer: &Writer<'_>, signature: &metadata::Signature<'_>) -> Vec<TokenStream> {
+    signature
+        .params
+        .iter()
+        .map(|p| {
+            let name = writer.param_name(p.def);
+            let tokens = if p.kind == metadata::SignatureParamKind::ValueType {
+                writer.type_default_name(&p.ty)
+            } else {
+                writer.type_abi_name(&p.ty)
+            };
+            quote! { #name: #tokens }
+        })
+        .collect()
+}
+
+fn link_vararg(writer: &Writer<'_>, signature: &metadata::Signature<'_>) -> TokenStream {
+    if writer.sys
+        && signature
+            .call_flags
+            .contains(metadata::MethodCallAttributes::VARARG)
+    {
+        "...".into()
+    } else {
+        quote! {}
     }
 }
 
-fn does_not_return(def: metadata::MethodDef) -> TokenStream {
+fn does_not_return(def: metadata::MethodDef<'_>) -> TokenStream {
     if def.has_attribute("DoesNotReturnAttribute") {
         quote! { -> ! }
     } else {
@@ -283,7 +447,7 @@ fn does_not_return(def: metadata::MethodDef) -> TokenStream {
     }
 }
 
-fn handle_last_error(def: metadata::MethodDef, signature: &metadata::Signature) -> bool {
+fn handle_last_error(def: metadata::MethodDef<'_>, signature: &metadata::Signature<'_>) -> bool {
     if let Some(map) = def.impl_map() {
         if map
             .flags()
@@ -308,7 +472,7 @@ fn handle_last_error(def: metadata::MethodDef, signature: &metadata::Signature)
     false
 }
 
-fn method_def_extern_abi(def: metadata::MethodDef) -> &'static str {
+fn method_def_extern_abi(def: metadata::MethodDef<'_>) -> &'static str {
     let impl_map = def.impl_map().expect("ImplMap not found");
     let flags = impl_map.flags();
 
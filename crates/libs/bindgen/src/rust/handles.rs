@@ -1,7 +1,7 @@
 use super::*;
 use metadata::{AsRow, HasAttributes};
 
-pub fn writer(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
+pub fn writer(writer: &Writer<'_>, def: metadata::TypeDef<'_>) -> TokenStream {
     if writer.sys {
         gen_sys_handle(writer, def)
     } else {
@@ -9,8 +9,8 @@ pub fn writer(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
     }
 }
 
-pub fn gen_sys_handle(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
-    let ident = to_ident(def.name());
+pub fn gen_sys_handle(writer: &Writer<'_>, def: metadata::TypeDef<'_>) -> TokenStream {
+    let ident = to_ident(writer.type_def_rename(def));
     let signature = writer.type_default_name(&def.underlying_type());
 
     quote! {
@@ -18,8 +18,8 @@ pub fn gen_sys_handle(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
     }
 }
 
-pub fn gen_win_handle(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
-    let name = def.name();
+pub fn gen_win_handle(writer: &Writer<'_>, def: metadata::TypeDef<'_>) -> TokenStream {
+    let name = writer.type_def_rename(def);
     let ident = to_ident(name);
     let underlying_type = def.underlying_type();
     let signature = writer.type_default_name(&underlying_type);
@@ -59,7 +59,7 @@ pub fn gen_win_handle(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
             // TODO: https://github.com/microsoft/win32metadata/issues/1891
             quote! {}
         } else {
-            let name = to_ident(function.name());
+            let name = to_ident(writer.function_rename(def.namespace(), function));
             let signature = metadata::method_def_signature(def.namespace(), function, &[]);
 
             // BCryptCloseAlgorithmProvider has an unused trailing parameter.
@@ -107,9 +107,7 @@ pub fn gen_win_handle(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
     };
 
     if let Some(dependency) = type_def_usable_for(def) {
-        let type_name = dependency.type_name();
-        let mut dependency = writer.namespace(type_name.namespace());
-        dependency.push_str(type_name.name());
+        let dependency = writer.type_def_name(dependency, &[]);
 
         tokens.combine(&quote! {
             impl windows_core::imp::CanInto<#dependency> for #ident {}
@@ -124,7 +122,7 @@ pub fn gen_win_handle(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
     tokens
 }
 
-fn type_def_usable_for(row: metadata::TypeDef) -> Option<metadata::TypeDef> {
+fn type_def_usable_for(row: metadata::TypeDef<'_>) -> Option<metadata::TypeDef<'_>> {
     if let Some(attribute) = row.find_attribute("AlsoUsableForAttribute") {
         if let Some((_, metadata::Value::String(name))) = attribute.args().first() {
             return row
@@ -136,7 +134,7 @@ fn type_def_usable_for(row: metadata::TypeDef) -> Option<metadata::TypeDef> {
     None
 }
 
-fn free_function(def: metadata::TypeDef) -> Option<metadata::MethodDef> {
+fn free_function(def: metadata::TypeDef<'_>) -> Option<metadata::MethodDef<'_>> {
     if let Some(attribute) = def.find_attribute("RAIIFreeAttribute") {
         // TODO: https://github.com/microsoft/win32metadata/issues/1892
         if matches!(def.name(), "COMPRESSOR_HANDLE" | "WSAEVENT") {
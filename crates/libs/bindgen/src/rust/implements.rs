@@ -1,7 +1,7 @@
 use super::*;
 use metadata::HasAttributes;
 
-pub fn writer(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
+pub fn writer(writer: &Writer<'_>, def: metadata::TypeDef<'_>) -> TokenStream {
     if def.kind() != metadata::TypeKind::Interface
         || (!writer.implement && def.has_attribute("ExclusiveToAttribute"))
     {
@@ -32,9 +32,9 @@ pub fn writer(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
     );
 
     fn gen_required_trait(
-        writer: &Writer,
-        def: metadata::TypeDef,
-        generics: &[metadata::Type],
+        writer: &Writer<'_>,
+        def: metadata::TypeDef<'_>,
+        generics: &[metadata::Type<'_>],
     ) -> TokenStream {
         let name = writer.type_def_name_imp(def, generics, "_Impl");
         quote! {
@@ -1,8 +1,8 @@
 use super::*;
+use metadata::Item;
 use rust::cfg::*;
 use serde::Serialize;
 use std::collections::BTreeMap;
-use windows_metadata::Item;
 
 #[derive(Default, Serialize)]
 struct Index {
@@ -17,7 +17,7 @@ struct IndexItem {
     features: Vec<usize>,
 }
 
-pub fn gen_index(writer: &Writer) -> String {
+pub fn gen_index(writer: &Writer<'_>) -> String {
     let mut feature_index = Index {
         ..Default::default()
     };
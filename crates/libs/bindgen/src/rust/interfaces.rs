@@ -1,6 +1,6 @@
 use super::*;
 
-pub fn writer(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
+pub fn writer(writer: &Writer<'_>, def: metadata::TypeDef<'_>) -> TokenStream {
     if writer.sys {
         gen_sys_interface(writer, def)
     } else {
@@ -8,7 +8,9 @@ pub fn writer(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
     }
 }
 
-fn gen_sys_interface(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
+// Docs are only included for non-sys interfaces since sys bindings only include the vtable and IID rather than a type
+// that represents the interface itself.
+fn gen_sys_interface(writer: &Writer<'_>, def: metadata::TypeDef<'_>) -> TokenStream {
     if !writer.vtbl {
         return quote! {};
     }
@@ -34,7 +36,7 @@ fn gen_sys_interface(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
     tokens
 }
 
-fn gen_win_interface(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
+fn gen_win_interface(writer: &Writer<'_>, def: metadata::TypeDef<'_>) -> TokenStream {
     let generics = &metadata::type_def_generics(def);
     let ident = writer.type_def_name(def, generics);
     let vtbl_ident = writer.type_def_vtbl_name(def, generics);
@@ -43,6 +45,7 @@ fn gen_win_interface(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
     let constraints = writer.generic_constraints(generics);
     let cfg = cfg::type_def_cfg(writer, def, &[]);
     let features = writer.cfg_features(&cfg);
+    let docs = writer.docs(def);
     let interfaces = metadata::type_interfaces(&metadata::Type::TypeDef(def, generics.to_vec()));
     let vtables = metadata::type_def_vtables(def);
     let has_unknown_base = matches!(
@@ -57,10 +60,11 @@ fn gen_win_interface(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
             let iid = writer.guid_literal(metadata::type_def_guid(def));
             tokens.combine(&quote! {
                 #features
-                windows_core::imp::define_interface!(#ident, #vtbl_ident, #iid);
+                windows_core::imp::define_interface!(#docs #ident, #vtbl_ident, #iid);
             });
         } else {
             tokens.combine(&quote! {
+                #docs
                 #features
                 #[repr(transparent)]
                 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -70,7 +74,7 @@ fn gen_win_interface(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
     } else {
         tokens.combine(&quote! {
             #features
-            windows_core::imp::define_interface!(#ident, #vtbl_ident);
+            windows_core::imp::define_interface!(#docs #ident, #vtbl_ident);
         });
     }
 
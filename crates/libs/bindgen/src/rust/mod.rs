@@ -24,10 +24,138 @@ use rayon::prelude::*;
 use writer::*;
 
 pub fn from_reader(
-    reader: &'static metadata::Reader,
-    mut config: std::collections::BTreeMap<&str, &str>,
+    reader: &metadata::Reader<'_>,
+    config: std::collections::BTreeMap<&str, &str>,
     output: &str,
 ) -> Result<()> {
+    check_config(&config, output)?;
+    let writer = new_writer(reader, config, output)?;
+    standalone::check(&writer)?;
+    gen_explain(&writer)?;
+
+    if writer.package {
+        gen_package(&writer)
+    } else {
+        write_to_file(&writer.output, gen_file(&writer))
+    }
+}
+
+/// Generates the bindings as a single string of Rust source rather than writing them to a file. The caller is expected
+/// to have already checked the configuration with `check_config`.
+pub fn to_string(
+    reader: &metadata::Reader<'_>,
+    config: std::collections::BTreeMap<&str, &str>,
+) -> Result<String> {
+    let writer = new_writer(reader, config, "")?;
+    standalone::check(&writer)?;
+    gen_explain(&writer)?;
+    Ok(gen_file(&writer))
+}
+
+/// Checks the configuration values that don't depend on the metadata so that invalid combinations may be reported
+/// before any input is read.
+pub fn check_config(config: &std::collections::BTreeMap<&str, &str>, output: &str) -> Result<()> {
+    let has = |key| config.contains_key(key);
+
+    if has("package") && has("flatten") {
+        return Err(Error::new(
+            "cannot combine `package` and `flatten` configuration values",
+        ));
+    }
+
+    if has("decode-flags") && has("sys") {
+        return Err(Error::new(
+            "cannot combine `decode-flags` and `sys` configuration values",
+        ));
+    }
+
+    if has("rust-enums") && has("sys") {
+        return Err(Error::new(
+            "cannot combine `rust-enums` and `sys` configuration values",
+        ));
+    }
+
+    if has("raw-dylib") && !(has("sys") && has("flatten")) {
+        return Err(Error::new(
+            "the `raw-dylib` configuration value requires the `sys` and `flatten` configuration values",
+        ));
+    }
+
+    if config.get("explain") == Some(&"") && output.is_empty() {
+        return Err(Error::new(
+            "the `explain` configuration value requires a path when there is no output",
+        ));
+    }
+
+    if has("explain") && !has("flatten") {
+        return Err(Error::new(
+            "the `explain` configuration value requires the `flatten` configuration value",
+        ));
+    }
+
+    if has("implement") && has("sys") {
+        return Err(Error::new(
+            "cannot combine `implement` and `sys` configuration values",
+        ));
+    }
+
+    if has("package") && output.is_empty() {
+        return Err(Error::new(
+            "the `package` configuration value requires an output directory",
+        ));
+    }
+
+    for (key, value) in config {
+        match key.split_once(':') {
+            Some(("derive", _)) => {
+                let mut derives = std::collections::BTreeSet::new();
+
+                for derive in value.split(',').map(str::trim) {
+                    if syn::parse_str::<syn::Path>(derive).is_err() {
+                        return Err(Error::new(&format!(
+                            "`{derive}` is not a valid derive for `{key}`"
+                        )));
+                    }
+
+                    if !derives.insert(derive) {
+                        return Err(Error::new(&format!(
+                            "`{derive}` is derived more than once for `{key}`"
+                        )));
+                    }
+                }
+            }
+            Some(("attribute", _)) => {
+                let attribute = override_attribute(value);
+
+                if !syn::parse::Parser::parse_str(syn::Attribute::parse_outer, &attribute)
+                    .is_ok_and(|attributes| attributes.len() == 1)
+                {
+                    return Err(Error::new(&format!(
+                        "`{value}` is not a valid attribute for `{key}`"
+                    )));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+// Attributes may be given with or without the surrounding `#[...]`.
+fn override_attribute(value: &str) -> String {
+    if value.starts_with('#') {
+        value.to_string()
+    } else {
+        format!("#[{value}]")
+    }
+}
+
+fn new_writer<'a>(
+    reader: &'a metadata::Reader<'a>,
+    mut config: std::collections::BTreeMap<&str, &str>,
+    output: &str,
+) -> Result<Writer<'a>> {
     let mut writer = Writer::new(reader, output);
     writer.package = config.remove("package").is_some();
     writer.flatten = config.remove("flatten").is_some();
@@ -37,22 +165,63 @@ pub fn from_reader(
     writer.no_inner_attributes = config.remove("no-inner-attributes").is_some();
     writer.no_bindgen_comment = config.remove("no-bindgen-comment").is_some();
     writer.vtbl = config.remove("vtbl").is_some();
+    writer.docs = config.remove("docs").is_some();
+    writer.decode_flags = config.remove("decode-flags").is_some();
+    writer.rust_enums = config.remove("rust-enums").is_some();
+    writer.raw_dylib = config.remove("raw-dylib").is_some();
     writer.rustfmt_config = if let Some(config) = config.remove("rustfmt-config") {
         config.to_string()
     } else {
         String::new()
     };
+    writer.explain = match config.remove("explain") {
+        // The report is written next to the bindings unless a path is given.
+        Some("") => format!(
+            "{}.explain.txt",
+            output.rsplit_once('.').map_or(output, |(stem, _)| stem)
+        ),
+        Some(path) => path.to_string(),
+        None => String::new(),
+    };
 
-    if writer.package && writer.flatten {
-        return Err(Error::new(
-            "cannot combine `package` and `flatten` configuration values",
-        ));
+    let keys: Vec<&str> = config.keys().copied().collect();
+
+    for key in keys {
+        let Some((kind, full_name)) = key.split_once(':') else {
+            continue;
+        };
+
+        let set = match kind {
+            "opaque" => &mut writer.opaque,
+            "exclude" => &mut writer.exclude,
+            _ => continue,
+        };
+
+        config.remove(key);
+
+        if !writer.flatten {
+            return Err(Error::new(&format!(
+                "the `{kind}` configuration value requires the `flatten` configuration value"
+            )));
+        }
+
+        let is_struct = full_name.rsplit_once('.').is_some_and(|(namespace, name)| {
+            reader
+                .get_type_def(namespace, name)
+                .any(|def| def.kind() == metadata::TypeKind::Struct)
+        });
+
+        if !is_struct {
+            return Err(Error::new(&format!("`{full_name}` does not name a struct")));
+        }
+
+        set.insert(full_name.to_string());
     }
 
-    if writer.implement && writer.sys {
-        return Err(Error::new(
-            "cannot combine `implement` and `sys` configuration values",
-        ));
+    if let Some(name) = writer.opaque.intersection(&writer.exclude).next() {
+        return Err(Error::new(&format!(
+            "`{name}` cannot be both opaque and excluded"
+        )));
     }
 
     config.retain(|key, value| {
@@ -69,24 +238,167 @@ pub fn from_reader(
         true
     });
 
+    let keys: Vec<&str> = config.keys().copied().collect();
+
+    for key in keys {
+        let Some((kind, full_name)) = key.split_once(':') else {
+            continue;
+        };
+
+        if !matches!(
+            kind,
+            "rename" | "derive" | "attribute" | "must-use" | "hidden"
+        ) {
+            continue;
+        }
+
+        // Unwrapping is fine since the key was just found in the map.
+        let value = config.remove(key).unwrap();
+
+        let Some(target) = override_target(reader, full_name) else {
+            return Err(Error::new(&format!(
+                "`{full_name}` does not name a struct, enum, function, or field"
+            )));
+        };
+
+        let derived = if kind == "derive" {
+            derived_traits(&writer, full_name)
+        } else {
+            vec![]
+        };
+
+        let entry = writer.overrides.entry(full_name.to_string()).or_default();
+
+        match (kind, target) {
+            ("rename", _) => {
+                if syn::parse_str::<syn::Ident>(value).is_err() {
+                    return Err(Error::new(&format!(
+                        "`{value}` is not a valid identifier for `{key}`"
+                    )));
+                }
+
+                entry.rename = Some(value.to_string());
+            }
+            ("derive", OverrideTarget::Type) => {
+                for derive in value.split(',').map(str::trim) {
+                    // The configuration has already been checked so each derive is a valid path.
+                    let last = derive.rsplit("::").next().unwrap_or(derive).trim();
+
+                    if derived.contains(&last) {
+                        return Err(Error::new(&format!(
+                            "`{derive}` is already derived for `{full_name}`"
+                        )));
+                    }
+
+                    entry.derive.push(derive.to_string());
+                }
+            }
+            ("attribute", _) => entry.attributes.push(override_attribute(value)),
+            ("must-use", OverrideTarget::Function) => {
+                entry.attributes.push("#[must_use]".to_string())
+            }
+            ("hidden", _) => entry.attributes.push("#[doc(hidden)]".to_string()),
+            _ => {
+                return Err(Error::new(&format!(
+                    "`{kind}` cannot be applied to `{full_name}`"
+                )))
+            }
+        }
+
+        // Sys-style functions in `Windows` namespaces are declared with the `link!` macro, which accepts a single
+        // attribute that is shared with the docs.
+        if writer.sys
+            && !writer.raw_dylib
+            && target == OverrideTarget::Function
+            && full_name.starts_with("Windows.")
+        {
+            let (namespace, name) = full_name.rsplit_once('.').unwrap_or_default();
+
+            let docs = writer.docs
+                && reader
+                    .get_method_def(namespace, name)
+                    .any(|(def, _)| metadata::row_docs(def).is_some());
+
+            if entry.attributes.len() + usize::from(docs) > 1 {
+                return Err(Error::new(&format!(
+                    "`{full_name}` cannot have more than one attribute, including its docs, with the `sys` configuration value"
+                )));
+            }
+        }
+    }
+
     if let Some((key, _)) = config.first_key_value() {
         return Err(Error::new(&format!("invalid configuration value `{key}`")));
     }
 
-    if writer.package {
-        gen_package(&writer)
+    Ok(writer)
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum OverrideTarget {
+    Type,
+    Function,
+    Field,
+}
+
+// The traits that the writer already derives for the named type, which overrides may not repeat.
+fn derived_traits(writer: &Writer<'_>, full_name: &str) -> Vec<&'static str> {
+    let (namespace, name) = full_name.rsplit_once('.').unwrap_or_default();
+
+    writer
+        .reader
+        .get_type_def(namespace, name)
+        .flat_map(|def| match def.kind() {
+            metadata::TypeKind::Struct => structs::derives(writer, def).into_iter().collect(),
+            metadata::TypeKind::Enum => enums::derives(writer, def).to_vec(),
+            _ => vec![],
+        })
+        .collect()
+}
+
+// Overrides are limited to the items whose names are used consistently throughout the generated bindings.
+fn override_target(reader: &metadata::Reader<'_>, full_name: &str) -> Option<OverrideTarget> {
+    let (namespace, name) = full_name.rsplit_once('.')?;
+
+    if reader.get_type_def(namespace, name).any(|def| {
+        matches!(
+            def.kind(),
+            metadata::TypeKind::Struct | metadata::TypeKind::Enum
+        )
+    }) {
+        return Some(OverrideTarget::Type);
+    }
+
+    if reader.get_method_def(namespace, name).next().is_some() {
+        return Some(OverrideTarget::Function);
+    }
+
+    let (namespace, type_name) = namespace.rsplit_once('.')?;
+
+    reader
+        .get_type_def(namespace, type_name)
+        .any(|def| {
+            matches!(def.kind(), metadata::TypeKind::Struct)
+                && def.fields().any(|field| field.name() == name)
+        })
+        .then_some(OverrideTarget::Field)
+}
+
+fn gen_explain(writer: &Writer<'_>) -> Result<()> {
+    if writer.explain.is_empty() {
+        Ok(())
     } else {
-        gen_file(&writer)
+        write_to_file(&writer.explain, standalone::explain(writer))
     }
 }
 
-fn gen_file(writer: &Writer) -> Result<()> {
+fn gen_file(writer: &Writer<'_>) -> String {
     // TODO: harmonize this output code so we don't need these two wildly differnt code paths
     // there should be a simple way to generate the with or without namespaces.
 
     if writer.flatten {
         let tokens = standalone::standalone_imp(writer);
-        write_to_file(&writer.output, try_format(writer, &tokens))
+        try_format(writer, &tokens)
     } else {
         let mut tokens = String::new();
         let root = Tree::new(writer.reader);
@@ -95,11 +407,11 @@ fn gen_file(writer: &Writer) -> Result<()> {
             tokens.push_str(&namespace(writer, tree));
         }
 
-        write_to_file(&writer.output, try_format(writer, &tokens))
+        try_format(writer, &tokens)
     }
 }
 
-fn gen_package(writer: &Writer) -> Result<()> {
+fn gen_package(writer: &Writer<'_>) -> Result<()> {
     let directory = directory(&writer.output);
     let root = Tree::new(writer.reader);
     let mut root_len = 0;
@@ -176,7 +488,7 @@ use std::fmt::Write;
 use tokens::*;
 use try_format::*;
 
-fn namespace(writer: &Writer, tree: &Tree) -> String {
+fn namespace(writer: &Writer<'_>, tree: &Tree<'_>) -> String {
     let writer = &mut writer.clone();
     writer.namespace = tree.namespace;
     let mut tokens = TokenStream::new();
@@ -256,7 +568,7 @@ fn namespace(writer: &Writer, tree: &Tree) -> String {
     tokens.into_string()
 }
 
-fn namespace_impl(writer: &Writer, tree: &Tree) -> String {
+fn namespace_impl(writer: &Writer<'_>, tree: &Tree<'_>) -> String {
     let writer = &mut writer.clone();
     writer.namespace = tree.namespace;
     let mut types = std::collections::BTreeMap::new();
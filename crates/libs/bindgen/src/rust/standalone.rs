@@ -1,100 +1,67 @@
 use super::*;
 use metadata::AsRow;
 
-pub fn standalone_imp(writer: &Writer) -> String {
-    let mut types = std::collections::BTreeSet::new();
-    let mut functions = std::collections::BTreeSet::new();
-    let mut constants = std::collections::BTreeSet::new();
+pub fn standalone_imp<'a>(writer: &Writer<'a>) -> String {
+    let Standalone {
+        types,
+        functions,
+        constants,
+        ..
+    } = collect(writer);
 
-    for item in writer.reader.items() {
-        item_collect_standalone(writer, item.clone(), &mut types);
+    let mut sorted = SortedTokens::default();
 
-        match item {
-            metadata::Item::Type(_) => {}
-            metadata::Item::Fn(def, namespace) => _ = functions.insert((def, namespace)),
-            metadata::Item::Const(def) => _ = constants.insert(def),
+    for ty in types {
+        match standalone_type(writer, &ty) {
+            Some(StandaloneType::Builtin(name, tokens)) => sorted.insert(name, tokens),
+            Some(StandaloneType::Opaque(def)) => {
+                let name = to_ident(writer.type_def_rename(def));
+                sorted.insert(def.name(), quote! { pub type #name = core::ffi::c_void; });
+            }
+            Some(StandaloneType::TypeDef(def)) => sorted.insert(def.name(), writer.type_def(def)),
+            None => {}
         }
     }
 
-    let mut sorted = SortedTokens::default();
+    if writer.raw_dylib {
+        // Functions are grouped into an extern block per library, calling convention, and x86 import name type so
+        // that the bindings have no dependencies at all.
+        let mut libraries = std::collections::BTreeMap::<(String, &str, &str), TokenStream>::new();
 
-    for ty in types {
-        match ty {
-            metadata::Type::Name(metadata::TypeName::HResult) if writer.sys => sorted.insert("HRESULT", quote! { pub type HRESULT = i32; }),
-            metadata::Type::Name(metadata::TypeName::IUnknown) if writer.sys => sorted.insert(
-                "IUnknown",
-                if !writer.vtbl {
-                    quote! {}
-                } else {
-                    quote! {
-                        pub const IID_IUnknown: GUID = GUID::from_u128(0x00000000_0000_0000_c000_000000000046);
-                        #[repr(C)]
-                        pub struct IUnknown_Vtbl {
-                            pub QueryInterface: unsafe extern "system" fn(this: *mut core::ffi::c_void, iid: *const GUID, interface: *mut *mut core::ffi::c_void) -> HRESULT,
-                            pub AddRef: unsafe extern "system" fn(this: *mut core::ffi::c_void) -> u32,
-                            pub Release: unsafe extern "system" fn(this: *mut core::ffi::c_void) -> u32,
-                        }
-                    }
-                },
-            ),
-            metadata::Type::Object if writer.sys => sorted.insert(
-                "IInspectable",
-                if !writer.vtbl {
-                    quote! {}
-                } else {
-                    quote! {
-                        pub const IID_IInspectable: GUID = GUID::from_u128(0xaf86e2e0_b12d_4c6a_9c5a_d7aa65101e90);
-                        #[repr(C)]
-                        pub struct IInspectable_Vtbl {
-                            pub base: IUnknown_Vtbl,
-                            pub GetIids: unsafe extern "system" fn(this: *mut std::ffi::c_void, count: *mut u32, values: *mut *mut GUID) -> HRESULT,
-                            pub GetRuntimeClassName: unsafe extern "system" fn(this: *mut std::ffi::c_void, value: *mut *mut std::ffi::c_void) -> HRESULT,
-                            pub GetTrustLevel: unsafe extern "system" fn(this: *mut std::ffi::c_void, value: *mut i32) -> HRESULT,
-                        }
-                    }
-                },
-            ),
-            metadata::Type::Name(metadata::TypeName::PSTR) if writer.sys => sorted.insert("PSTR", quote! { pub type PSTR = *mut u8; }),
-            metadata::Type::Name(metadata::TypeName::PWSTR) if writer.sys => sorted.insert("PWSTR", quote! { pub type PWSTR = *mut u16; }),
-            metadata::Type::Const(metadata::TypeName::PSTR) if writer.sys => sorted.insert("PCSTR", quote! { pub type PCSTR = *const u8; }),
-            metadata::Type::Const(metadata::TypeName::PWSTR) if writer.sys => sorted.insert("PCWSTR", quote! { pub type PCWSTR = *const u16; }),
-            metadata::Type::Name(metadata::TypeName::BSTR) if writer.sys => sorted.insert("BSTR", quote! { pub type BSTR = *const u16; }),
-            metadata::Type::Name(metadata::TypeName::GUID) if writer.sys => {
-                sorted.insert(
-                    "GUID",
-                    quote! {
-                        #[repr(C)]
-                        #[derive(Clone, Copy)]
-                        pub struct GUID {
-                            pub data1: u32,
-                            pub data2: u16,
-                            pub data3: u16,
-                            pub data4: [u8; 8],
-                        }
-                        impl GUID {
-                            pub const fn from_u128(uuid: u128) -> Self {
-                                Self { data1: (uuid >> 96) as u32, data2: (uuid >> 80 & 0xffff) as u16, data3: (uuid >> 64 & 0xffff) as u16, data4: (uuid as u64).to_be_bytes() }
-                            }
-                        }
-                    },
-                );
-            }
-            metadata::Type::TypeDef(def, _) => {
-                sorted.insert(def.name(), writer.type_def(def));
+        for (function, namespace) in functions {
+            if let Some((library, abi, import_name_type, declaration)) =
+                functions::raw_dylib(writer, namespace, function)
+            {
+                libraries
+                    .entry((library, abi, import_name_type))
+                    .or_default()
+                    .combine(&declaration);
             }
-            _ => {}
         }
-    }
 
-    for (function, namespace) in functions {
-        sorted.insert(
-            &format!(
-                ".{}.{}",
-                function.module_name().to_lowercase(),
-                function.name()
-            ),
-            functions::writer(writer, namespace, function),
-        );
+        for ((library, abi, import_name_type), declarations) in libraries {
+            sorted.insert(
+                &format!(".{library}.{abi}.{import_name_type}"),
+                quote! {
+                    #[cfg_attr(target_arch = "x86", link(name = #library, kind = "raw-dylib", modifiers = "+verbatim", import_name_type = #import_name_type))]
+                    #[cfg_attr(not(target_arch = "x86"), link(name = #library, kind = "raw-dylib", modifiers = "+verbatim"))]
+                    extern #abi {
+                        #declarations
+                    }
+                },
+            );
+        }
+    } else {
+        for (function, namespace) in functions {
+            sorted.insert(
+                &format!(
+                    ".{}.{}",
+                    function.module_name().to_lowercase(),
+                    function.name()
+                ),
+                functions::writer(writer, namespace, function),
+            );
+        }
     }
 
     for constant in constants {
@@ -106,6 +73,194 @@ pub fn standalone_imp(writer: &Writer) -> String {
     tokens.into_string()
 }
 
+/// Explains why each item was included in the standalone bindings by listing the chain of items that required it,
+/// as text or, if the path has a .json extension, as JSON.
+pub fn explain<'a>(writer: &Writer<'a>) -> String {
+    let standalone = collect(writer);
+    let mut report = std::collections::BTreeMap::<String, Vec<String>>::new();
+
+    let names = standalone
+        .types
+        .iter()
+        .filter(|ty| standalone_type(writer, ty).is_some())
+        .map(diff::ty)
+        .chain(
+            standalone
+                .functions
+                .iter()
+                .map(|(def, namespace)| format!("{namespace}.{}", def.name())),
+        )
+        .chain(
+            standalone
+                .constants
+                .iter()
+                .map(|def| def.name().to_string()),
+        );
+
+    for name in names {
+        let mut chain = vec![];
+        let mut next = standalone.parents.get(&name);
+
+        while let Some(parent) = next {
+            chain.push(parent.clone());
+            next = standalone.parents.get(parent);
+        }
+
+        report.insert(name, chain);
+    }
+
+    if extension(&writer.explain) == "json" {
+        // Unwrapping is fine since the report only contains strings and sequences.
+        return serde_json::to_string_pretty(&report).unwrap();
+    }
+
+    let mut text = String::new();
+
+    for (name, chain) in report {
+        text.push_str(&name);
+
+        for parent in chain {
+            text.push_str(" <- ");
+            text.push_str(&parent);
+        }
+
+        text.push('\n');
+    }
+
+    text
+}
+
+/// Checks that opaque and excluded structs are only used through pointers.
+pub fn check<'a>(writer: &Writer<'a>) -> Result<()> {
+    if writer.opaque.is_empty() && writer.exclude.is_empty() {
+        return Ok(());
+    }
+
+    Error::combine(
+        collect(writer)
+            .errors
+            .iter()
+            .map(|message| Error::new(message)),
+    )
+}
+
+#[derive(Default)]
+struct Standalone<'a> {
+    types: std::collections::BTreeSet<metadata::Type<'a>>,
+    functions: std::collections::BTreeSet<(metadata::MethodDef<'a>, &'a str)>,
+    constants: std::collections::BTreeSet<metadata::Field<'a>>,
+    // The item that first required each type, keyed by name. Items matching the filter have no parent.
+    parents: std::collections::BTreeMap<String, String>,
+    errors: std::collections::BTreeSet<String>,
+}
+
+fn collect<'a>(writer: &Writer<'a>) -> Standalone<'a> {
+    let mut standalone = Standalone::default();
+
+    for item in writer.reader.items() {
+        item_collect_standalone(writer, item.clone(), &mut standalone);
+
+        match item {
+            metadata::Item::Type(_) => {}
+            metadata::Item::Fn(def, namespace) => _ = standalone.functions.insert((def, namespace)),
+            metadata::Item::Const(def) => _ = standalone.constants.insert(def),
+        }
+    }
+
+    // A type matching the filter may have been reached through another item first.
+    for item in writer.reader.items() {
+        if let metadata::Item::Type(def) = item {
+            standalone
+                .parents
+                .remove(&diff::ty(&metadata::Type::TypeDef(def, vec![])));
+        }
+    }
+
+    standalone
+}
+
+// The types that are included in standalone bindings. Types like `GUID` are defined by the bindings themselves when
+// there is no dependency on `windows-core` to provide them.
+enum StandaloneType<'a> {
+    Builtin(&'static str, TokenStream),
+    Opaque(metadata::TypeDef<'a>),
+    TypeDef(metadata::TypeDef<'a>),
+}
+
+// Both `standalone_imp` and `explain` rely on this to agree on which types are included.
+fn standalone_type<'a>(writer: &Writer<'a>, ty: &metadata::Type<'a>) -> Option<StandaloneType<'a>> {
+    Some(match ty {
+        metadata::Type::Name(metadata::TypeName::HResult) if writer.sys => {
+            StandaloneType::Builtin("HRESULT", quote! { pub type HRESULT = i32; })
+        }
+        metadata::Type::Name(metadata::TypeName::IUnknown) if writer.sys && writer.vtbl => {
+            StandaloneType::Builtin(
+                "IUnknown",
+                quote! {
+                    pub const IID_IUnknown: GUID = GUID::from_u128(0x00000000_0000_0000_c000_000000000046);
+                    #[repr(C)]
+                    pub struct IUnknown_Vtbl {
+                        pub QueryInterface: unsafe extern "system" fn(this: *mut core::ffi::c_void, iid: *const GUID, interface: *mut *mut core::ffi::c_void) -> HRESULT,
+                        pub AddRef: unsafe extern "system" fn(this: *mut core::ffi::c_void) -> u32,
+                        pub Release: unsafe extern "system" fn(this: *mut core::ffi::c_void) -> u32,
+                    }
+                },
+            )
+        }
+        metadata::Type::Object if writer.sys && writer.vtbl => StandaloneType::Builtin(
+            "IInspectable",
+            quote! {
+                pub const IID_IInspectable: GUID = GUID::from_u128(0xaf86e2e0_b12d_4c6a_9c5a_d7aa65101e90);
+                #[repr(C)]
+                pub struct IInspectable_Vtbl {
+                    pub base: IUnknown_Vtbl,
+                    pub GetIids: unsafe extern "system" fn(this: *mut std::ffi::c_void, count: *mut u32, values: *mut *mut GUID) -> HRESULT,
+                    pub GetRuntimeClassName: unsafe extern "system" fn(this: *mut std::ffi::c_void, value: *mut *mut std::ffi::c_void) -> HRESULT,
+                    pub GetTrustLevel: unsafe extern "system" fn(this: *mut std::ffi::c_void, value: *mut i32) -> HRESULT,
+                }
+            },
+        ),
+        metadata::Type::Name(metadata::TypeName::PSTR) if writer.sys => {
+            StandaloneType::Builtin("PSTR", quote! { pub type PSTR = *mut u8; })
+        }
+        metadata::Type::Name(metadata::TypeName::PWSTR) if writer.sys => {
+            StandaloneType::Builtin("PWSTR", quote! { pub type PWSTR = *mut u16; })
+        }
+        metadata::Type::Const(metadata::TypeName::PSTR) if writer.sys => {
+            StandaloneType::Builtin("PCSTR", quote! { pub type PCSTR = *const u8; })
+        }
+        metadata::Type::Const(metadata::TypeName::PWSTR) if writer.sys => {
+            StandaloneType::Builtin("PCWSTR", quote! { pub type PCWSTR = *const u16; })
+        }
+        metadata::Type::Name(metadata::TypeName::BSTR) if writer.sys => {
+            StandaloneType::Builtin("BSTR", quote! { pub type BSTR = *const u16; })
+        }
+        metadata::Type::Name(metadata::TypeName::GUID) if writer.sys => StandaloneType::Builtin(
+            "GUID",
+            quote! {
+                #[repr(C)]
+                #[derive(Clone, Copy)]
+                pub struct GUID {
+                    pub data1: u32,
+                    pub data2: u16,
+                    pub data3: u16,
+                    pub data4: [u8; 8],
+                }
+                impl GUID {
+                    pub const fn from_u128(uuid: u128) -> Self {
+                        Self { data1: (uuid >> 96) as u32, data2: (uuid >> 80 & 0xffff) as u16, data3: (uuid >> 64 & 0xffff) as u16, data4: (uuid as u64).to_be_bytes() }
+                    }
+                }
+            },
+        ),
+        metadata::Type::TypeDef(def, _) if writer.opaque.contains(&def.type_name().to_string()) => {
+            StandaloneType::Opaque(*def)
+        }
+        metadata::Type::TypeDef(def, _) => StandaloneType::TypeDef(*def),
+        _ => return None,
+    })
+}
+
 #[derive(Default)]
 struct SortedTokens(std::collections::BTreeMap<String, TokenStream>);
 
@@ -115,49 +270,98 @@ impl SortedTokens {
     }
 }
 
-fn item_collect_standalone(
-    writer: &Writer,
-    item: metadata::Item,
-    set: &mut std::collections::BTreeSet<metadata::Type>,
+fn item_collect_standalone<'a>(
+    writer: &Writer<'a>,
+    item: metadata::Item<'a>,
+    set: &mut Standalone<'a>,
 ) {
     match item {
         metadata::Item::Type(def) => {
-            type_collect_standalone(writer, &metadata::Type::TypeDef(def, vec![]), set)
+            type_collect_standalone(writer, &metadata::Type::TypeDef(def, vec![]), set, "")
         }
         metadata::Item::Const(def) => {
-            type_collect_standalone(writer, &def.ty(None).to_const_type(), set)
+            type_collect_standalone(writer, &def.ty(None).to_const_type(), set, def.name())
         }
         metadata::Item::Fn(def, namespace) => {
+            let parent = format!("{namespace}.{}", def.name());
             let signature = metadata::method_def_signature(namespace, def, &[]);
-            type_collect_standalone(writer, &signature.return_type, set);
+            type_collect_standalone(writer, &signature.return_type, set, &parent);
             signature
                 .params
                 .iter()
-                .for_each(|param| type_collect_standalone(writer, &param.ty, set));
+                .for_each(|param| type_collect_standalone(writer, &param.ty, set, &parent));
         }
     }
 }
 // TODO: remove or move to riddle
-fn type_collect_standalone(
-    writer: &Writer,
-    ty: &metadata::Type,
-    set: &mut std::collections::BTreeSet<metadata::Type>,
+fn type_collect_standalone<'a>(
+    writer: &Writer<'a>,
+    ty: &metadata::Type<'a>,
+    set: &mut Standalone<'a>,
+    parent: &str,
 ) {
+    let by_value = !matches!(
+        ty,
+        metadata::Type::MutPtr(..) | metadata::Type::ConstPtr(..)
+    );
     let ty = ty.to_underlying_type();
-    if !set.insert(ty.clone()) {
+    let name = diff::ty(&ty);
+
+    // Opaque and excluded structs are never chased, so only their size is unknown and they can't be used by value.
+    let is_opaque = writer.opaque.contains(&name);
+    let is_excluded = writer.exclude.contains(&name);
+
+    if is_opaque || is_excluded {
+        if by_value && !parent.is_empty() {
+            let kind = if is_opaque { "opaque" } else { "excluded" };
+
+            set.errors.insert(format!(
+                "`{name}` cannot be {kind} because `{parent}` uses it by value"
+            ));
+        }
+
+        if is_excluded {
+            return;
+        }
+    }
+
+    if !set.types.insert(ty.clone()) {
         return;
     }
 
+    if !parent.is_empty() {
+        set.parents
+            .entry(name.clone())
+            .or_insert_with(|| parent.to_string());
+    }
+
+    if is_opaque {
+        return;
+    }
+
+    let parent = name.as_str();
+
     if writer.vtbl {
         match ty {
             metadata::Type::Name(metadata::TypeName::IUnknown) => {
-                set.insert(metadata::Type::Name(metadata::TypeName::GUID));
-                set.insert(metadata::Type::Name(metadata::TypeName::HResult));
+                type_collect_standalone(
+                    writer,
+                    &metadata::Type::Name(metadata::TypeName::GUID),
+                    set,
+                    parent,
+                );
+                type_collect_standalone(
+                    writer,
+                    &metadata::Type::Name(metadata::TypeName::HResult),
+                    set,
+                    parent,
+                );
             }
             metadata::Type::Object => type_collect_standalone(
                 writer,
                 &metadata::Type::Name(metadata::TypeName::IUnknown),
                 set,
+                parent,
             ),
             _ => {}
         }
@@ -181,13 +385,18 @@ fn type_collect_standalone(
             .get_type_def(type_name.namespace(), type_name.name())
         {
             if def != row {
-                type_collect_standalone(writer, &metadata::Type::TypeDef(row, Vec::new()), set);
+                type_collect_standalone(
+                    writer,
+                    &metadata::Type::TypeDef(row, Vec::new()),
+                    set,
+                    parent,
+                );
             }
         }
     }
 
     for generic in &generics {
-        type_collect_standalone(writer, generic, set);
+        type_collect_standalone(writer, generic, set, parent);
     }
 
     for field in def.fields() {
@@ -197,7 +406,7 @@ fn type_collect_standalone(
                 continue;
             }
         }
-        type_collect_standalone(writer, &ty, set);
+        type_collect_standalone(writer, &ty, set, parent);
     }
 
     for method in def.methods() {
@@ -206,21 +415,26 @@ fn type_collect_standalone(
             continue;
         }
         let signature = metadata::method_def_signature(def.namespace(), method, &generics);
-        type_collect_standalone(writer, &signature.return_type, set);
+        type_collect_standalone(writer, &signature.return_type, set, parent);
         signature
             .params
             .iter()
-            .for_each(|param| type_collect_standalone(writer, &param.ty, set));
+            .for_each(|param| type_collect_standalone(writer, &param.ty, set, parent));
     }
 
     for interface in metadata::type_interfaces(&ty) {
-        type_collect_standalone(writer, &interface.ty, set);
+        type_collect_standalone(writer, &interface.ty, set, parent);
     }
 
     match def.kind() {
         metadata::TypeKind::Struct => {
             if def.fields().next().is_none() && metadata::type_def_guid(def).is_some() {
-                set.insert(metadata::Type::Name(metadata::TypeName::GUID));
+                type_collect_standalone(
+                    writer,
+                    &metadata::Type::Name(metadata::TypeName::GUID),
+                    set,
+                    parent,
+                );
             }
         }
         metadata::TypeKind::Interface => {
@@ -228,22 +442,23 @@ fn type_collect_standalone(
                 .flags()
                 .contains(metadata::TypeAttributes::WindowsRuntime)
             {
-                type_collect_standalone(writer, &metadata::Type::Object, set);
+                type_collect_standalone(writer, &metadata::Type::Object, set, parent);
             }
         }
         _ => {}
     }
 
-    type_collect_standalone_nested(writer, def, set);
+    type_collect_standalone_nested(writer, def, set, parent);
 }
 
-fn type_collect_standalone_nested(
-    writer: &Writer,
-    td: metadata::TypeDef,
-    set: &mut std::collections::BTreeSet<metadata::Type>,
+fn type_collect_standalone_nested<'a>(
+    writer: &Writer<'a>,
+    td: metadata::TypeDef<'a>,
+    set: &mut Standalone<'a>,
+    parent: &str,
 ) {
     for nested in td.reader().nested_types(td) {
-        type_collect_standalone_nested(writer, nested, set);
+        type_collect_standalone_nested(writer, nested, set, parent);
 
         for field in nested.fields() {
             let ty = field.ty(Some(nested));
@@ -254,7 +469,7 @@ fn type_collect_standalone_nested(
                     continue;
                 }
             }
-            type_collect_standalone(writer, &ty, set);
+            type_collect_standalone(writer, &ty, set, parent);
         }
     }
 }
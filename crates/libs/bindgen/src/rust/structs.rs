@@ -1,7 +1,7 @@
 use super::*;
 use metadata::HasAttributes;
 
-pub fn writer(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
+pub fn writer(writer: &Writer<'_>, def: metadata::TypeDef<'_>) -> TokenStream {
     if def.has_attribute("ApiContractAttribute") {
         return quote! {};
     }
@@ -11,7 +11,7 @@ pub fn writer(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
     }
 
     if let Some(guid) = clsid(def) {
-        let ident = to_ident(def.name());
+        let ident = to_ident(writer.type_def_rename(def));
         let value = writer.guid(&guid);
         let guid = writer.type_name(&metadata::Type::Name(metadata::TypeName::GUID));
         return quote! {
@@ -19,14 +19,19 @@ pub fn writer(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
         };
     }
 
-    gen_struct_with_name(writer, def, def.name(), &cfg::Cfg::default())
+    gen_struct_with_name(
+        writer,
+        def,
+        writer.type_def_rename(def),
+        &cfg::Cfg::default(),
+    )
 }
 
 fn gen_struct_with_name(
-    writer: &Writer,
-    def: metadata::TypeDef,
+    writer: &Writer<'_>,
+    def: metadata::TypeDef<'_>,
     struct_name: &str,
-    cfg: &cfg::Cfg,
+    cfg: &cfg::Cfg<'_>,
 ) -> TokenStream {
     let name = to_ident(struct_name);
     let flags = def.flags();
@@ -47,8 +52,10 @@ fn gen_struct_with_name(
         quote! { (pub u8); }
     } else {
         let fields = def.fields().map(|f| {
-            let name = to_ident(f.name());
+            let name = to_ident(writer.field_rename(def, f));
             let ty = f.ty(Some(def));
+            let mut docs = writer.docs(f);
+            docs.combine(&writer.override_attributes(&format!("{}.{}", def.type_name(), f.name())));
 
             if f.flags().contains(metadata::FieldAttributes::Literal) {
                 quote! {}
@@ -57,21 +64,21 @@ fn gen_struct_with_name(
                 && !metadata::field_is_copyable(f, def)
             {
                 let ty = writer.type_default_name(&ty);
-                quote! { pub #name: core::mem::ManuallyDrop<#ty>, }
+                quote! { #docs pub #name: core::mem::ManuallyDrop<#ty>, }
             } else if !writer.sys
                 && !flags.contains(metadata::TypeAttributes::WindowsRuntime)
                 && !metadata::field_is_blittable(f, def)
             {
                 if let metadata::Type::Win32Array(ty, len) = ty {
                     let ty = writer.type_default_name(&ty);
-                    quote! { pub #name: [core::mem::ManuallyDrop<#ty>; #len], }
+                    quote! { #docs pub #name: [core::mem::ManuallyDrop<#ty>; #len], }
                 } else {
                     let ty = writer.type_default_name(&ty);
-                    quote! { pub #name: core::mem::ManuallyDrop<#ty>, }
+                    quote! { #docs pub #name: core::mem::ManuallyDrop<#ty>, }
                 }
             } else {
                 let ty = writer.type_default_name(&ty);
-                quote! { pub #name: #ty, }
+                quote! { #docs pub #name: #ty, }
             }
         });
 
@@ -86,11 +93,15 @@ fn gen_struct_with_name(
 
     let features = writer.cfg_features(&cfg);
     let derive = gen_derive(writer, def);
+    let docs = writer.docs(def);
+    let attributes = writer.override_attributes(&def.type_name().to_string());
 
     let mut tokens = quote! {
+        #docs
         #repr
         #features
         #derive
+        #attributes
         pub #struct_or_union #name #fields
     };
 
@@ -123,10 +134,10 @@ fn gen_struct_with_name(
 }
 
 fn gen_windows_traits(
-    writer: &Writer,
-    def: metadata::TypeDef,
+    writer: &Writer<'_>,
+    def: metadata::TypeDef<'_>,
     name: &TokenStream,
-    cfg: &cfg::Cfg,
+    cfg: &cfg::Cfg<'_>,
 ) -> TokenStream {
     if writer.sys {
         quote! {}
@@ -165,24 +176,42 @@ fn gen_windows_traits(
     }
 }
 
-fn gen_derive(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
+fn gen_derive(writer: &Writer<'_>, def: metadata::TypeDef<'_>) -> TokenStream {
+    let derive = derives(writer, def);
+
+    if derive.is_empty() {
+        quote! {}
+    } else {
+        let derive = derive.into_iter().map(to_ident);
+
+        quote! {
+            #[derive(#(#derive),*)]
+        }
+    }
+}
+
+/// The traits that the writer derives for the given struct.
+pub fn derives(
+    writer: &Writer<'_>,
+    def: metadata::TypeDef<'_>,
+) -> std::collections::BTreeSet<&'static str> {
     let mut derive = std::collections::BTreeSet::new();
 
     if !writer.sys
         && !metadata::type_def_has_explicit_layout(def)
         && !metadata::type_def_has_packing(def)
     {
-        derive.insert(to_ident("Debug"));
+        derive.insert("Debug");
     }
 
     if writer.sys || metadata::type_def_is_copyable(def) {
-        derive.insert(to_ident("Copy"));
-        derive.insert(to_ident("Clone"));
+        derive.insert("Copy");
+        derive.insert("Clone");
     } else if def
         .flags()
         .contains(metadata::TypeAttributes::WindowsRuntime)
     {
-        derive.insert(to_ident("Clone"));
+        derive.insert("Clone");
     }
 
     if !writer.sys
@@ -190,27 +219,21 @@ fn gen_derive(writer: &Writer, def: metadata::TypeDef) -> TokenStream {
         && !metadata::type_def_has_packing(def)
         && !metadata::type_def_has_callback(def)
     {
-        derive.insert(to_ident("PartialEq"));
+        derive.insert("PartialEq");
 
         if !metadata::type_def_has_float(def) {
-            derive.insert(to_ident("Eq"));
+            derive.insert("Eq");
         }
     }
 
-    if derive.is_empty() {
-        quote! {}
-    } else {
-        quote! {
-            #[derive(#(#derive),*)]
-        }
-    }
+    derive
 }
 
 fn gen_clone(
-    writer: &Writer,
-    def: metadata::TypeDef,
+    writer: &Writer<'_>,
+    def: metadata::TypeDef<'_>,
     name: &TokenStream,
-    cfg: &cfg::Cfg,
+    cfg: &cfg::Cfg<'_>,
 ) -> TokenStream {
     if writer.sys
         || metadata::type_def_is_copyable(def)
@@ -235,10 +258,10 @@ fn gen_clone(
 }
 
 fn gen_struct_constants(
-    writer: &Writer,
-    def: metadata::TypeDef,
+    writer: &Writer<'_>,
+    def: metadata::TypeDef<'_>,
     struct_name: &TokenStream,
-    cfg: &cfg::Cfg,
+    cfg: &cfg::Cfg<'_>,
 ) -> TokenStream {
     let features = writer.cfg_features(cfg);
 
@@ -271,7 +294,7 @@ fn gen_struct_constants(
     tokens
 }
 
-fn clsid(def: metadata::TypeDef) -> Option<metadata::Guid> {
+fn clsid(def: metadata::TypeDef<'_>) -> Option<metadata::Guid> {
     if def.fields().next().is_none() {
         return metadata::type_def_guid(def);
     }
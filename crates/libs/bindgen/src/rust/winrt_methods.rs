@@ -2,11 +2,11 @@ use super::*;
 
 // TODO take Signature instead of MethodDef (wherever MethodDef is found)
 pub fn writer(
-    writer: &Writer,
-    def: metadata::TypeDef,
-    generic_types: &[metadata::Type],
+    writer: &Writer<'_>,
+    def: metadata::TypeDef<'_>,
+    generic_types: &[metadata::Type<'_>],
     kind: metadata::InterfaceKind,
-    method: metadata::MethodDef,
+    method: metadata::MethodDef<'_>,
     method_names: &mut MethodNames,
     virtual_names: &mut MethodNames,
 ) -> TokenStream {
@@ -118,7 +118,9 @@ pub fn writer(
         }
     };
 
-    match kind {
+    let docs = writer.docs(method);
+
+    let tokens = match kind {
         metadata::InterfaceKind::Default => quote! {
             #features
             pub fn #name<#generics>(&self, #params) #return_type_tokens #where_clause {
@@ -149,10 +151,15 @@ pub fn writer(
                 }
             }
         }
+    };
+
+    quote! {
+        #docs
+        #tokens
     }
 }
 
-fn gen_winrt_params(writer: &Writer, params: &[metadata::SignatureParam]) -> TokenStream {
+fn gen_winrt_params(writer: &Writer<'_>, params: &[metadata::SignatureParam<'_>]) -> TokenStream {
     let mut result = quote! {};
 
     let mut generic_params = writer.generic_params(params);
@@ -185,7 +192,7 @@ fn gen_winrt_params(writer: &Writer, params: &[metadata::SignatureParam]) -> Tok
     result
 }
 
-fn gen_winrt_abi_args(writer: &Writer, params: &[metadata::SignatureParam]) -> TokenStream {
+fn gen_winrt_abi_args(writer: &Writer<'_>, params: &[metadata::SignatureParam<'_>]) -> TokenStream {
     let mut tokens = TokenStream::new();
     for param in params {
         let name = writer.param_name(param.def);
@@ -227,8 +234,8 @@ fn gen_winrt_abi_args(writer: &Writer, params: &[metadata::SignatureParam]) -> T
 }
 
 pub fn gen_upcall(
-    writer: &Writer,
-    sig: &metadata::Signature,
+    writer: &Writer<'_>,
+    sig: &metadata::Signature<'_>,
     inner: TokenStream,
     this: bool,
 ) -> TokenStream {
@@ -314,7 +321,7 @@ pub fn gen_upcall(
     }
 }
 
-fn gen_winrt_invoke_arg(writer: &Writer, param: &metadata::SignatureParam) -> TokenStream {
+fn gen_winrt_invoke_arg(writer: &Writer<'_>, param: &metadata::SignatureParam<'_>) -> TokenStream {
     let name = writer.param_name(param.def);
     let abi_size_name: TokenStream = format!("{}_array_size", param.def.name()).into();
 
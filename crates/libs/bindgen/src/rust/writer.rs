@@ -2,10 +2,10 @@ use super::*;
 use metadata::HasAttributes;
 
 #[derive(Clone)]
-pub struct Writer {
-    pub reader: &'static metadata::Reader,
+pub struct Writer<'a> {
+    pub reader: &'a metadata::Reader<'a>,
     pub output: String,
-    pub namespace: &'static str,
+    pub namespace: &'a str,
     pub implement: bool, // TODO: ideally we can use this to generate implementation traits on the fly and
     // and have a single interface definition macro for consumption that expands to include
     // impl traits when the `implement` cfg flag is set and then this writer option would be
@@ -20,13 +20,33 @@ pub struct Writer {
     pub no_inner_attributes: bool, // skips the inner attributes at the start of the file
     pub no_bindgen_comment: bool, // skips the bindgen comment at the start of the file
     pub vtbl: bool,    // include minimal vtbl layout support for interfaces
-    pub prepend: std::collections::HashMap<metadata::TypeDef, String>,
+    pub docs: bool,    // include doc comments from metadata
+    pub decode_flags: bool, // decode flags into their names when formatting
+    pub rust_enums: bool, // include Rust enums that may be converted to and from non-flags enums
+    pub raw_dylib: bool, // link functions with `raw-dylib` rather than depending on `windows-targets`
+    pub prepend: std::collections::HashMap<metadata::TypeDef<'a>, String>,
+    /// Customizations for specific types, functions, and fields keyed by their fully qualified names.
+    pub overrides: std::collections::HashMap<String, Override>,
     /// If this is not empty, then it is passed to rustfmt in a `--config` argument.
     pub rustfmt_config: String,
+    /// If this is not empty, then standalone bindings also write a report to this path explaining why each item
+    /// was included.
+    pub explain: String,
+    /// Structs that standalone bindings emit as `c_void` aliases rather than chasing their dependencies.
+    pub opaque: std::collections::HashSet<String>,
+    /// Structs that standalone bindings omit entirely, referring to them as `c_void` instead.
+    pub exclude: std::collections::HashSet<String>,
 }
 
-impl Writer {
-    pub fn new(reader: &'static metadata::Reader, output: &str) -> Self {
+#[derive(Clone, Default)]
+pub struct Override {
+    pub rename: Option<String>,
+    pub derive: Vec<String>,
+    pub attributes: Vec<String>,
+}
+
+impl<'a> Writer<'a> {
+    pub fn new(reader: &'a metadata::Reader<'a>, output: &str) -> Self {
         Self {
             reader,
             output: output.to_string(),
@@ -39,9 +59,64 @@ impl Writer {
             no_inner_attributes: false,
             no_bindgen_comment: false,
             vtbl: false,
+            docs: false,
+            decode_flags: false,
+            rust_enums: false,
+            raw_dylib: false,
             prepend: Default::default(),
+            overrides: Default::default(),
             rustfmt_config: String::new(),
+            explain: String::new(),
+            opaque: Default::default(),
+            exclude: Default::default(),
+        }
+    }
+
+    pub fn docs<R: HasAttributes<'a>>(&self, row: R) -> TokenStream {
+        if self.docs {
+            metadata::row_doc_attributes(row)
+        } else {
+            quote! {}
+        }
+    }
+
+    //
+    // Overrides
+    //
+
+    pub fn type_def_rename(&self, def: metadata::TypeDef<'a>) -> &str {
+        self.rename(&def.type_name().to_string(), def.name())
+    }
+    pub fn field_rename(&self, def: metadata::TypeDef<'a>, field: metadata::Field<'a>) -> &str {
+        self.rename(
+            &format!("{}.{}", def.type_name(), field.name()),
+            field.name(),
+        )
+    }
+    pub fn function_rename(&self, namespace: &str, def: metadata::MethodDef<'a>) -> &str {
+        self.rename(&format!("{namespace}.{}", def.name()), def.name())
+    }
+    fn rename<'b>(&'b self, full_name: &str, name: &'b str) -> &'b str {
+        self.overrides
+            .get(full_name)
+            .and_then(|value| value.rename.as_deref())
+            .unwrap_or(name)
+    }
+    /// The extra derives and attributes to apply to the given item, if any.
+    pub fn override_attributes(&self, full_name: &str) -> TokenStream {
+        let mut tokens = quote! {};
+
+        if let Some(value) = self.overrides.get(full_name) {
+            if !value.derive.is_empty() {
+                tokens.combine(&format!("#[derive({})]", value.derive.join(", ")).into());
+            }
+
+            for attribute in &value.attributes {
+                tokens.combine(&attribute.as_str().into());
+            }
         }
+
+        tokens
     }
 
     //
@@ -50,31 +125,36 @@ impl Writer {
 
     pub fn type_def_name(
         &self,
-        def: metadata::TypeDef,
-        generics: &[metadata::Type],
+        def: metadata::TypeDef<'_>,
+        generics: &[metadata::Type<'_>],
     ) -> TokenStream {
         self.type_def_name_imp(def, generics, "")
     }
     pub fn type_def_vtbl_name(
         &self,
-        def: metadata::TypeDef,
-        generics: &[metadata::Type],
+        def: metadata::TypeDef<'_>,
+        generics: &[metadata::Type<'_>],
     ) -> TokenStream {
         self.type_def_name_imp(def, generics, "_Vtbl")
     }
     pub fn type_def_name_imp(
         &self,
-        def: metadata::TypeDef,
-        generics: &[metadata::Type],
+        def: metadata::TypeDef<'_>,
+        generics: &[metadata::Type<'_>],
         suffix: &str,
     ) -> TokenStream {
         let type_name = def.type_name();
 
+        // Only pointers to excluded types are permitted so they remain ABI compatible.
+        if suffix.is_empty() && self.exclude.contains(&type_name.to_string()) {
+            return quote! { core::ffi::c_void };
+        }
+
         if type_name.namespace().is_empty() {
             to_ident(&self.scoped_name(def))
         } else {
             let mut namespace = self.namespace(type_name.namespace());
-            let mut name = to_ident(type_name.name());
+            let mut name = to_ident(self.type_def_rename(def));
             name.push_str(suffix);
 
             if generics.is_empty() || self.sys {
@@ -92,7 +172,7 @@ impl Writer {
             }
         }
     }
-    pub fn type_def(&self, def: metadata::TypeDef) -> TokenStream {
+    pub fn type_def(&self, def: metadata::TypeDef<'_>) -> TokenStream {
         let tokens = match def.kind() {
             metadata::TypeKind::Class => classes::writer(self, def),
             metadata::TypeKind::Interface => interfaces::writer(self, def),
@@ -114,7 +194,7 @@ impl Writer {
     // Type
     //
 
-    pub fn type_default_name(&self, ty: &metadata::Type) -> TokenStream {
+    pub fn type_default_name(&self, ty: &metadata::Type<'_>) -> TokenStream {
         if let metadata::Type::WinrtArray(ty) = ty {
             self.type_default_name(ty)
         } else {
@@ -130,7 +210,7 @@ impl Writer {
         }
     }
 
-    pub(crate) fn type_name(&self, ty: &metadata::Type) -> TokenStream {
+    pub(crate) fn type_name(&self, ty: &metadata::Type<'_>) -> TokenStream {
         match ty {
             metadata::Type::Void => quote! { core::ffi::c_void },
             metadata::Type::Bool => quote! { bool },
@@ -247,13 +327,13 @@ impl Writer {
             rest => unimplemented!("{rest:?}"),
         }
     }
-    pub fn type_vtbl_name(&self, ty: &metadata::Type) -> TokenStream {
+    pub fn type_vtbl_name(&self, ty: &metadata::Type<'_>) -> TokenStream {
         match ty {
             metadata::Type::TypeDef(def, generics) => self.type_def_vtbl_name(*def, generics),
             rest => unimplemented!("{rest:?}"),
         }
     }
-    pub fn type_abi_name(&self, ty: &metadata::Type) -> TokenStream {
+    pub fn type_abi_name(&self, ty: &metadata::Type<'_>) -> TokenStream {
         if self.sys {
             return match ty {
                 metadata::Type::PrimitiveOrEnum(ty, _) => self.type_default_name(ty),
@@ -337,7 +417,7 @@ impl Writer {
     // Constraints
     //
 
-    pub fn generic_phantoms(&self, generics: &[metadata::Type]) -> TokenStream {
+    pub fn generic_phantoms(&self, generics: &[metadata::Type<'_>]) -> TokenStream {
         let mut tokens = TokenStream::new();
         for generic in generics {
             let generic = self.type_name(generic);
@@ -345,7 +425,7 @@ impl Writer {
         }
         tokens
     }
-    pub fn generic_named_phantoms(&self, generics: &[metadata::Type]) -> Vec<TokenStream> {
+    pub fn generic_named_phantoms(&self, generics: &[metadata::Type<'_>]) -> Vec<TokenStream> {
         generics
             .iter()
             .map(|generic| {
@@ -354,7 +434,7 @@ impl Writer {
             })
             .collect()
     }
-    pub fn generic_constraints(&self, generics: &[metadata::Type]) -> TokenStream {
+    pub fn generic_constraints(&self, generics: &[metadata::Type<'_>]) -> TokenStream {
         let mut tokens = TokenStream::new();
         for generic in generics {
             let generic = self.type_name(generic);
@@ -362,7 +442,7 @@ impl Writer {
         }
         tokens
     }
-    pub fn generic_names(&self, generics: &[metadata::Type]) -> TokenStream {
+    pub fn generic_names(&self, generics: &[metadata::Type<'_>]) -> TokenStream {
         let mut tokens = TokenStream::new();
         for generic in generics {
             let generic = self.type_name(generic);
@@ -373,15 +453,15 @@ impl Writer {
     /// The signature params which are generic (along with their relative index)
     pub fn generic_params<'b>(
         &'b self,
-        params: &'b [metadata::SignatureParam],
-    ) -> impl Iterator<Item = (usize, &metadata::SignatureParam)> + 'b {
+        params: &'b [metadata::SignatureParam<'_>],
+    ) -> impl Iterator<Item = (usize, &'b metadata::SignatureParam<'b>)> + 'b {
         params
             .iter()
             .filter(move |param| param.is_convertible())
             .enumerate()
     }
     /// The generic param names (i.e., `T` in `fn foo<T>()`)
-    pub fn constraint_generics(&self, params: &[metadata::SignatureParam]) -> TokenStream {
+    pub fn constraint_generics(&self, params: &[metadata::SignatureParam<'_>]) -> TokenStream {
         let mut generics = self
             .generic_params(params)
             .map(|(position, _)| -> TokenStream { format!("P{position}").into() })
@@ -394,7 +474,7 @@ impl Writer {
         }
     }
     /// A `where` clause for some constrained generic params
-    pub fn where_clause(&self, params: &[metadata::SignatureParam]) -> TokenStream {
+    pub fn where_clause(&self, params: &[metadata::SignatureParam<'_>]) -> TokenStream {
         let constraints = self.param_constraints(params);
 
         if !constraints.is_empty() {
@@ -403,7 +483,7 @@ impl Writer {
             quote!()
         }
     }
-    fn param_constraints(&self, params: &[metadata::SignatureParam]) -> TokenStream {
+    fn param_constraints(&self, params: &[metadata::SignatureParam<'_>]) -> TokenStream {
         let mut tokens = TokenStream::new();
         let gen_name = |position| {
             let name: TokenStream = format!("P{position}").into();
@@ -423,7 +503,7 @@ impl Writer {
     // Cfg
     //
 
-    pub(crate) fn cfg_features(&self, cfg: &cfg::Cfg) -> TokenStream {
+    pub(crate) fn cfg_features(&self, cfg: &cfg::Cfg<'_>) -> TokenStream {
         let arches = &cfg.arches;
         let arch = match arches.len() {
             0 => quote! {},
@@ -452,8 +532,8 @@ impl Writer {
         quote! { #arch #features }
     }
 
-    fn cfg_features_imp(&self, cfg: &cfg::Cfg, namespace: &str) -> Vec<&'static str> {
-        let mut compact = Vec::<&'static str>::new();
+    fn cfg_features_imp<'b>(&self, cfg: &cfg::Cfg<'b>, namespace: &str) -> Vec<&'b str> {
+        let mut compact = Vec::<&str>::new();
         if self.package {
             for feature in cfg.types.keys() {
                 if !feature.is_empty()
@@ -477,7 +557,7 @@ impl Writer {
         compact
     }
 
-    fn cfg_not_features(&self, cfg: &cfg::Cfg) -> TokenStream {
+    fn cfg_not_features(&self, cfg: &cfg::Cfg<'_>) -> TokenStream {
         let features = self.cfg_features_imp(cfg, self.namespace);
         if features.is_empty() {
             quote! {}
@@ -547,7 +627,7 @@ impl Writer {
             "windows_core::".into()
         }
     }
-    fn scoped_name(&self, def: metadata::TypeDef) -> String {
+    fn scoped_name(&self, def: metadata::TypeDef<'_>) -> String {
         if let Some(enclosing_type) = def.enclosing_type() {
             for (index, nested_type) in self.reader.nested_types(enclosing_type).enumerate() {
                 if nested_type.name() == def.name() {
@@ -555,9 +635,9 @@ impl Writer {
                 }
             }
         }
-        def.name().to_string()
+        self.type_def_rename(def).to_string()
     }
-    pub fn value(&self, value: &metadata::Value) -> TokenStream {
+    pub fn value(&self, value: &metadata::Value<'_>) -> TokenStream {
         match value {
             metadata::Value::Bool(value) => quote! { #value },
             metadata::Value::U8(value) => quote! { #value },
@@ -583,7 +663,7 @@ impl Writer {
             rest => unimplemented!("{rest:?}"),
         }
     }
-    pub fn typed_value(&self, value: &metadata::Value) -> TokenStream {
+    pub fn typed_value(&self, value: &metadata::Value<'_>) -> TokenStream {
         let literal = self.value(value);
         match value {
             metadata::Value::Bool(_) => quote! { bool = #literal },
@@ -618,7 +698,7 @@ impl Writer {
 
     pub fn agile(
         &self,
-        def: metadata::TypeDef,
+        def: metadata::TypeDef<'_>,
         ident: &TokenStream,
         constraints: &TokenStream,
         features: &TokenStream,
@@ -636,8 +716,8 @@ impl Writer {
     }
     pub fn async_get(
         &self,
-        def: metadata::TypeDef,
-        generics: &[metadata::Type],
+        def: metadata::TypeDef<'_>,
+        generics: &[metadata::Type<'_>],
         ident: &TokenStream,
         constraints: &TokenStream,
         _phantoms: &TokenStream,
@@ -702,8 +782,8 @@ impl Writer {
     }
     pub fn interface_winrt_trait(
         &self,
-        def: metadata::TypeDef,
-        generics: &[metadata::Type],
+        def: metadata::TypeDef<'_>,
+        generics: &[metadata::Type<'_>],
         ident: &TokenStream,
         constraints: &TokenStream,
         _phantoms: &TokenStream,
@@ -771,8 +851,8 @@ impl Writer {
     }
     pub fn runtime_name_trait(
         &self,
-        def: metadata::TypeDef,
-        _generics: &[metadata::Type],
+        def: metadata::TypeDef<'_>,
+        _generics: &[metadata::Type<'_>],
         name: &TokenStream,
         constraints: &TokenStream,
         features: &TokenStream,
@@ -800,8 +880,8 @@ impl Writer {
 
     pub fn interface_trait(
         &self,
-        def: metadata::TypeDef,
-        generics: &[metadata::Type],
+        def: metadata::TypeDef<'_>,
+        generics: &[metadata::Type<'_>],
         ident: &TokenStream,
         constraints: &TokenStream,
         features: &TokenStream,
@@ -853,8 +933,8 @@ impl Writer {
     }
     pub fn interface_vtbl(
         &self,
-        def: metadata::TypeDef,
-        generics: &[metadata::Type],
+        def: metadata::TypeDef<'_>,
+        generics: &[metadata::Type<'_>],
         constraints: &TokenStream,
         features: &TokenStream,
     ) -> TokenStream {
@@ -921,9 +1001,9 @@ impl Writer {
     }
     pub fn vtbl_signature(
         &self,
-        def: metadata::TypeDef,
+        def: metadata::TypeDef<'_>,
         named_params: bool,
-        signature: &metadata::Signature,
+        signature: &metadata::Signature<'_>,
     ) -> TokenStream {
         let is_winrt = def
             .flags()
@@ -1051,12 +1131,12 @@ impl Writer {
             quote! { (*mut core::ffi::c_void, #udt_return_type #(#params)* #trailing_return_type) #return_type }
         }
     }
-    pub fn param_name(&self, param: metadata::Param) -> TokenStream {
+    pub fn param_name(&self, param: metadata::Param<'_>) -> TokenStream {
         // In Rust, function parameters cannot be named the same as structs. This avoids some collisions that occur in the win32 metadata.
         // See Icmp6SendEcho2 for an example.
         to_ident(&param.name().to_lowercase())
     }
-    pub fn return_sig(&self, signature: &metadata::Signature) -> TokenStream {
+    pub fn return_sig(&self, signature: &metadata::Signature<'_>) -> TokenStream {
         match &signature.return_type {
             metadata::Type::Void if signature.def.has_attribute("DoesNotReturnAttribute") => {
                 " -> !".into()
@@ -1070,7 +1150,7 @@ impl Writer {
     }
     pub fn win32_args(
         &self,
-        params: &[metadata::SignatureParam],
+        params: &[metadata::SignatureParam<'_>],
         kind: metadata::SignatureKind,
     ) -> TokenStream {
         let mut tokens = quote! {};
@@ -1150,7 +1230,7 @@ impl Writer {
     }
     pub fn win32_params(
         &self,
-        params: &[metadata::SignatureParam],
+        params: &[metadata::SignatureParam<'_>],
         kind: metadata::SignatureKind,
     ) -> TokenStream {
         let mut tokens = quote! {};
@@ -1255,8 +1335,8 @@ impl Writer {
 
     pub fn impl_signature(
         &self,
-        def: metadata::TypeDef,
-        signature: &metadata::Signature,
+        def: metadata::TypeDef<'_>,
+        signature: &metadata::Signature<'_>,
     ) -> TokenStream {
         if def
             .flags()
@@ -1334,7 +1414,7 @@ impl Writer {
     }
     fn winrt_produce_type(
         &self,
-        param: &metadata::SignatureParam,
+        param: &metadata::SignatureParam<'_>,
         include_param_names: bool,
     ) -> TokenStream {
         let default_type = self.type_default_name(&param.ty);
@@ -1366,7 +1446,7 @@ impl Writer {
             sig
         }
     }
-    fn win32_produce_type(&self, param: &metadata::SignatureParam) -> TokenStream {
+    fn win32_produce_type(&self, param: &metadata::SignatureParam<'_>) -> TokenStream {
         let name = self.param_name(param.def);
         let kind = self.type_default_name(&param.ty);
 
@@ -1393,7 +1473,7 @@ fn const_ptrs(pointers: usize) -> TokenStream {
     "*const ".repeat(pointers).into()
 }
 
-pub fn cfg_features(cfg: &cfg::Cfg) -> Vec<String> {
+pub fn cfg_features(cfg: &cfg::Cfg<'_>) -> Vec<String> {
     let mut compact = Vec::<&'static str>::new();
 
     for feature in cfg.types.keys() {
@@ -1463,7 +1543,7 @@ fn gen_const_ptrs(pointers: usize) -> TokenStream {
     "*const ".repeat(pointers).into()
 }
 
-fn type_def_async_kind(row: metadata::TypeDef) -> metadata::AsyncKind {
+fn type_def_async_kind(row: metadata::TypeDef<'_>) -> metadata::AsyncKind {
     match row.type_name() {
         metadata::TypeName::IAsyncAction => metadata::AsyncKind::Action,
         metadata::TypeName::IAsyncActionWithProgress => metadata::AsyncKind::ActionWithProgress,
@@ -1475,7 +1555,7 @@ fn type_def_async_kind(row: metadata::TypeDef) -> metadata::AsyncKind {
     }
 }
 
-fn type_def_is_agile(row: metadata::TypeDef) -> bool {
+fn type_def_is_agile(row: metadata::TypeDef<'_>) -> bool {
     for attribute in row.attributes() {
         match attribute.name() {
             "AgileAttribute" => return true,
@@ -2,7 +2,7 @@ use super::*;
 use metadata::{AsRow, HasAttributes};
 
 pub fn from_reader(
-    reader: &metadata::Reader,
+    reader: &metadata::Reader<'_>,
     config: std::collections::BTreeMap<&str, &str>,
     output: &str,
 ) -> Result<()> {
@@ -86,13 +86,17 @@ pub fn from_reader(
                 Signature: signature,
             });
 
+            let field_ref = writer.tables.Field.len() as u32 - 1;
+
             if let Some(constant) = field.constant() {
                 writer.tables.Constant.push(Constant {
                     Type: constant.usize(0) as u16,
-                    Parent: HasConstant::Field(writer.tables.Field.len() as u32 - 1),
+                    Parent: HasConstant::Field(field_ref),
                     Value: writer.blobs.insert(&constant.blob(2)),
                 })
             }
+
+            write_attributes(&mut writer, HasAttribute::Field(field_ref), field);
         }
 
         for method in def.methods() {
@@ -112,6 +116,8 @@ pub fn from_reader(
                 ParamList: writer.tables.Param.len() as u32,
             });
 
+            let method_ref = writer.tables.MethodDef.len() as u32 - 1;
+
             for param in method.params() {
                 writer.tables.Param.push(Param {
                     Flags: param.flags().0,
@@ -119,69 +125,67 @@ pub fn from_reader(
                     Name: writer.strings.insert(param.name()),
                 });
             }
-        }
-
-        for attribute in def.attributes() {
-            let metadata::AttributeType::MemberRef(attribute_ctor) = attribute.ty();
-            assert_eq!(attribute_ctor.name(), ".ctor");
-            let metadata::MemberRefParent::TypeRef(attribute_type) = attribute_ctor.parent();
-
-            let attribute_type_ref = if let TypeDefOrRef::TypeRef(type_ref) =
-                writer.insert_type_ref(attribute_type.namespace(), attribute_type.name())
-            {
-                MemberRefParent::TypeRef(type_ref)
-            } else {
-                panic!()
-            };
 
-            let signature = attribute_ctor.signature();
-            let return_type = winmd_type(&signature.return_type);
-            let param_types: Vec<Type> = signature.params.iter().map(winmd_type).collect();
-            let signature =
-                writer.insert_method_sig(signature.call_flags, &return_type, &param_types);
-
-            writer.tables.MemberRef.push(MemberRef {
-                Class: attribute_type_ref,
-                Name: writer.strings.insert(".ctor"),
-                Signature: signature,
-            });
+            write_attributes(&mut writer, HasAttribute::MethodDef(method_ref), method);
+        }
 
-            let mut values = 1u16.to_le_bytes().to_vec(); // prolog
-            let args = attribute.args();
-            let mut named_arg_count = false;
+        write_attributes(&mut writer, HasAttribute::TypeDef(def_ref), def);
+    }
 
-            for (index, (name, value)) in args.iter().enumerate() {
-                value_blob(value, &mut values);
+    // TODO: In theory, `config` could instruct this function to balance the types across a number of winmd files
+    // like mdmerge supports for namespace-splitting.
+    write_to_file(output, writer.into_stream()).map_err(|err| err.with_path(output))
+}
 
-                if !named_arg_count && !name.is_empty() {
-                    named_arg_count = true;
-                    let named_arg_count = (args.len() - index) as u16;
-                    values.extend_from_slice(&named_arg_count.to_le_bytes());
-                    break;
-                }
+fn write_attributes<'a, R: HasAttributes<'a>>(writer: &mut Writer, parent: HasAttribute, row: R) {
+    for attribute in row.attributes() {
+        let metadata::AttributeType::MemberRef(attribute_ctor) = attribute.ty();
+        assert_eq!(attribute_ctor.name(), ".ctor");
+        let metadata::MemberRefParent::TypeRef(attribute_type) = attribute_ctor.parent();
+
+        let signature = attribute_ctor.signature();
+        let return_type = winmd_type(&signature.return_type);
+        let param_types: Vec<Type> = signature.params.iter().map(winmd_type).collect();
+        let signature = writer.insert_method_sig(signature.call_flags, &return_type, &param_types);
+
+        let constructor = writer.insert_member_ref(
+            attribute_type.namespace(),
+            attribute_type.name(),
+            ".ctor",
+            signature,
+        );
+
+        let mut values = 1u16.to_le_bytes().to_vec(); // prolog
+        let args = attribute.args();
+        let mut named_arg_count = false;
+
+        for (index, (name, value)) in args.iter().enumerate() {
+            value_blob(value, &mut values);
+
+            if !named_arg_count && !name.is_empty() {
+                named_arg_count = true;
+                let named_arg_count = (args.len() - index) as u16;
+                values.extend_from_slice(&named_arg_count.to_le_bytes());
+                break;
             }
+        }
 
-            if !named_arg_count {
-                values.extend_from_slice(&0u16.to_le_bytes());
-            }
+        if !named_arg_count {
+            values.extend_from_slice(&0u16.to_le_bytes());
+        }
 
-            let values = writer.blobs.insert(&values);
+        let values = writer.blobs.insert(&values);
 
-            writer.tables.CustomAttribute.push(CustomAttribute {
-                Parent: HasAttribute::TypeDef(def_ref),
-                Type: AttributeType::MemberRef(writer.tables.MemberRef.len() as u32 - 1),
-                Value: values,
-            });
-        }
+        writer.tables.CustomAttribute.push(CustomAttribute {
+            Parent: parent,
+            Type: AttributeType::MemberRef(constructor),
+            Value: values,
+        });
     }
-
-    // TODO: In theory, `config` could instruct this function to balance the types across a number of winmd files
-    // like mdmerge supports for namespace-splitting.
-    write_to_file(output, writer.into_stream()).map_err(|err| err.with_path(output))
 }
 
 // TODO: need a Blob type for writing
-fn value_blob(value: &metadata::Value, blob: &mut Vec<u8>) {
+fn value_blob(value: &metadata::Value<'_>, blob: &mut Vec<u8>) {
     match value {
         metadata::Value::Bool(value) => {
             if *value {
@@ -210,7 +214,7 @@ fn value_blob(value: &metadata::Value, blob: &mut Vec<u8>) {
 }
 
 // TODO: keep the basic type conversion
-fn winmd_type(ty: &metadata::Type) -> Type {
+fn winmd_type(ty: &metadata::Type<'_>) -> Type {
     match ty {
         metadata::Type::Void => Type::Void,
         metadata::Type::Bool => Type::Bool,
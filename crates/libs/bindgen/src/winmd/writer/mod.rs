@@ -21,8 +21,9 @@ pub struct Writer {
     pub tables: Tables,
     pub scopes: HashMap<String, ResolutionScope>,
     // TODO: is this faster than jsut using a single HashMap with a (String,String) key?
-    pub type_refs: HashMap<String, HashMap<String, TypeDefOrRef>>,
+    pub type_refs: HashMap<String, HashMap<String, u32>>,
     pub type_specs: HashMap<Type, TypeDefOrRef>,
+    pub member_refs: HashMap<(u32, String, u32), u32>,
 }
 
 impl Writer {
@@ -34,6 +35,7 @@ impl Writer {
             scopes: Default::default(),
             type_refs: Default::default(),
             type_specs: Default::default(),
+            member_refs: Default::default(),
         };
 
         writer.tables.TypeDef.push(TypeDef {
@@ -143,6 +145,10 @@ impl Writer {
     }
 
     pub fn insert_type_ref(&mut self, namespace: &str, name: &str) -> TypeDefOrRef {
+        TypeDefOrRef::TypeRef(self.insert_type_ref_row(namespace, name))
+    }
+
+    fn insert_type_ref_row(&mut self, namespace: &str, name: &str) -> u32 {
         if let Some(key) = self.type_refs.get(namespace) {
             if let Some(reference) = key.get(name) {
                 return *reference;
@@ -151,11 +157,11 @@ impl Writer {
 
         let scope = self.insert_scope(namespace);
 
-        let reference = TypeDefOrRef::TypeRef(self.tables.TypeRef.push2(TypeRef {
+        let reference = self.tables.TypeRef.push2(TypeRef {
             TypeName: self.strings.insert(name),
             TypeNamespace: self.strings.insert(namespace),
             ResolutionScope: scope,
-        }));
+        });
         self.type_refs
             .entry(namespace.to_string())
             .or_default()
@@ -163,6 +169,31 @@ impl Writer {
         reference
     }
 
+    // Member references, such as the constructor of a custom attribute, are shared by every row that refers to them
+    // rather than duplicated for each use.
+    pub fn insert_member_ref(
+        &mut self,
+        namespace: &str,
+        name: &str,
+        member: &str,
+        signature: u32,
+    ) -> u32 {
+        let class = self.insert_type_ref_row(namespace, name);
+        let key = (class, member.to_string(), signature);
+
+        if let Some(reference) = self.member_refs.get(&key) {
+            return *reference;
+        }
+
+        let reference = self.tables.MemberRef.push2(MemberRef {
+            Class: MemberRefParent::TypeRef(class),
+            Name: self.strings.insert(member),
+            Signature: signature,
+        });
+        self.member_refs.insert(key, reference);
+        reference
+    }
+
     pub fn insert_type_spec(&mut self, ty: Type) -> TypeDefOrRef {
         if let Some(key) = self.type_specs.get(&ty) {
             return *key;
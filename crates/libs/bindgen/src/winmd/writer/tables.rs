@@ -169,7 +169,7 @@ pub struct TypeSpec {
 }
 
 impl Tables {
-    pub fn into_stream(self) -> Vec<u8> {
+    pub fn into_stream(mut self) -> Vec<u8> {
         if [
             self.AssemblyRef.len(),
             self.ClassLayout.len(),
@@ -360,6 +360,9 @@ impl Tables {
             buffer.write_u32(x.Value);
         }
 
+        // Attributes may be added for fields and methods after those of later types so they must be sorted by parent.
+        self.CustomAttribute.sort_by_key(|x| x.Parent.encode());
+
         for x in self.CustomAttribute {
             buffer.write_code(x.Parent.encode(), has_custom_attribute);
             buffer.write_code(x.Type.encode(), custom_attribute_type);
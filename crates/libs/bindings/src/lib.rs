@@ -0,0 +1,153 @@
+/*!
+Learn more about Rust for Windows here: <https://github.com/microsoft/windows-rs>
+*/
+
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+
+/// Generates bindings at compile time rather than committing generated files or writing a build script.
+///
+/// Paths are relative to the directory containing the crate's Cargo.toml file. Filters and paths may be given as a
+/// single string or a list of strings, followed by any of the flags supported by `--config`.
+///
+/// # Example
+/// ```rust,ignore
+/// windows_bindings::windows_bindings!(
+///     in = "metadata/Contoso.winmd",
+///     filter = ["Contoso.Widgets", "!Contoso.Widgets.Internal"],
+///     sys
+/// );
+///
+/// fn main() {
+///     let widget = Contoso::Widgets::WIDGET { id: 1 };
+/// }
+/// ```
+#[proc_macro]
+pub fn windows_bindings(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let args = syn::parse_macro_input!(input as Args);
+
+    match args.gen_tokens() {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+struct Args {
+    input: Vec<syn::LitStr>,
+    filter: Vec<syn::LitStr>,
+    config: Vec<syn::Ident>,
+    span: proc_macro2::Span,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let span = input.span();
+        let mut args = Self {
+            input: vec![],
+            filter: vec![],
+            config: vec![],
+            span,
+        };
+
+        while !input.is_empty() {
+            // `in` is a keyword so it isn't parsed as a regular identifier.
+            let name = syn::Ident::parse_any(input)?;
+
+            if input.peek(syn::Token![=]) {
+                input.parse::<syn::Token![=]>()?;
+                let values = parse_strings(input)?;
+
+                match name.to_string().as_str() {
+                    "in" => args.input.extend(values),
+                    "filter" => args.filter.extend(values),
+                    _ => {
+                        return Err(syn::Error::new(
+                            name.span(),
+                            format!("unexpected argument `{name}`"),
+                        ))
+                    }
+                }
+            } else {
+                args.config.push(name);
+            }
+
+            if input.is_empty() {
+                break;
+            }
+
+            input.parse::<syn::Token![,]>()?;
+        }
+
+        Ok(args)
+    }
+}
+
+// Parses either a single string literal or a bracketed list of them.
+fn parse_strings(input: ParseStream<'_>) -> syn::Result<Vec<syn::LitStr>> {
+    if input.peek(syn::token::Bracket) {
+        let content;
+        syn::bracketed!(content in input);
+
+        Ok(content
+            .parse_terminated(|input| input.parse::<syn::LitStr>(), syn::Token![,])?
+            .into_iter()
+            .collect())
+    } else {
+        Ok(vec![input.parse()?])
+    }
+}
+
+impl Args {
+    fn gen_tokens(&self) -> syn::Result<proc_macro2::TokenStream> {
+        let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+        let root = std::path::Path::new(&root);
+
+        let inputs: Vec<String> = self
+            .input
+            .iter()
+            .map(|path| root.join(path.value()).to_string_lossy().into_owned())
+            .collect();
+
+        // Inner attributes can't be expanded by a macro so the lints are instead allowed on each item below.
+        let mut builder = windows_bindgen::Bindgen::new()
+            .no_inner_attributes(true)
+            .no_bindgen_comment(true);
+
+        for input in &inputs {
+            builder = builder.input(input);
+        }
+
+        for filter in &self.filter {
+            builder = builder.filter(filter.value());
+        }
+
+        for config in &self.config {
+            builder = builder.config(config.to_string());
+        }
+
+        let tokens = builder
+            .tokens()
+            .map_err(|error| syn::Error::new(self.span, error.to_string().trim_end()))?;
+
+        let file: syn::File = syn::parse2(tokens)?;
+
+        let items = file.items.iter().map(|item| {
+            quote! {
+                #[allow(non_snake_case, non_upper_case_globals, non_camel_case_types, dead_code, clippy::all)]
+                #item
+            }
+        });
+
+        // Including the inputs ensures that the bindings are regenerated whenever the metadata changes.
+        let dependencies = inputs
+            .iter()
+            .filter(|path| std::path::Path::new(path).is_file())
+            .map(|path| quote! { const _: &[u8] = include_bytes!(#path); });
+
+        Ok(quote! {
+            #(#dependencies)*
+            #(#items)*
+        })
+    }
+}
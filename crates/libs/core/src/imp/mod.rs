@@ -56,7 +56,8 @@ pub use required_hierarchy;
 #[doc(hidden)]
 #[macro_export]
 macro_rules! define_interface {
-    ($name:ident, $vtbl:ident, $iid:literal) => {
+    ($(#[$attr:meta])* $name:ident, $vtbl:ident, $iid:literal) => {
+        $(#[$attr])*
         #[repr(transparent)]
         #[derive(::core::cmp::PartialEq, ::core::cmp::Eq, ::core::clone::Clone)]
         pub struct $name(::windows_core::IUnknown);
@@ -72,7 +73,8 @@ macro_rules! define_interface {
             }
         }
     };
-    ($name:ident, $vtbl:ident) => {
+    ($(#[$attr:meta])* $name:ident, $vtbl:ident) => {
+        $(#[$attr])*
         #[repr(transparent)]
         #[derive(::core::cmp::PartialEq, ::core::cmp::Eq, ::core::clone::Clone)]
         pub struct $name(::std::ptr::NonNull<::std::ffi::c_void>);
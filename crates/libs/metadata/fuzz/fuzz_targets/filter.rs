@@ -0,0 +1,40 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use metadata::*;
+
+// The input is a list of filter rules, one per line with a leading `!` for exclusions, followed by a nul byte and
+// the metadata itself.
+fuzz_target!(|data: &[u8]| {
+    let Some(split) = data.iter().position(|byte| *byte == 0) else {
+        return;
+    };
+
+    let Ok(rules) = std::str::from_utf8(&data[..split]) else {
+        return;
+    };
+
+    let Ok(file) = File::new(&data[split + 1..]) else {
+        return;
+    };
+
+    let mut include = vec![];
+    let mut exclude = vec![];
+
+    for rule in rules.lines() {
+        match rule.strip_prefix('!') {
+            Some(rule) => exclude.push(rule),
+            None => include.push(rule),
+        }
+    }
+
+    let reader = Reader::filter(vec![file], &include, &exclude, &Default::default());
+
+    for namespace in reader.namespaces() {
+        _ = reader.includes_namespace(namespace);
+        _ = reader.namespace_items(namespace).count();
+    }
+
+    _ = reader.items().count();
+    _ = reader.unused().count();
+});
@@ -0,0 +1,56 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use metadata::*;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(file) = File::new(data) else {
+        return;
+    };
+
+    let reader = Reader::new(vec![file]);
+
+    for row in reader.table::<Attribute>() {
+        _ = row.args();
+    }
+
+    for row in reader.table::<Constant>() {
+        _ = row.value();
+    }
+
+    for row in reader.table::<Event>() {
+        _ = row.ty(&[]);
+    }
+
+    for row in reader.table::<Field>() {
+        _ = row.ty(None);
+    }
+
+    for row in reader.table::<InterfaceImpl>() {
+        _ = row.ty(&[]);
+    }
+
+    for row in reader.table::<MemberRef>() {
+        _ = row.signature();
+    }
+
+    for row in reader.table::<MethodDef>() {
+        _ = row.signature(&[]);
+    }
+
+    for row in reader.table::<Property>() {
+        _ = row.ty(&[]);
+    }
+
+    for row in reader.table::<TypeDef>() {
+        _ = row.underlying_type();
+
+        for field in row.fields() {
+            _ = field.ty(Some(row));
+        }
+    }
+
+    for row in reader.table::<TypeSpec>() {
+        _ = reader.type_from_ref(TypeDefOrRef::TypeSpec(row), None, &[]);
+    }
+});
@@ -0,0 +1,109 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use metadata::*;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(file) = File::new(data) else {
+        return;
+    };
+
+    let reader = Reader::new(vec![file]);
+
+    for row in reader.table::<AssemblyRef>() {
+        _ = row.index();
+    }
+
+    for row in reader.table::<Attribute>() {
+        _ = (row.parent(), row.ty(), row.name(), row.type_name());
+    }
+
+    for row in reader.table::<ClassLayout>() {
+        _ = row.packing_size();
+    }
+
+    for row in reader.table::<Constant>() {
+        _ = row.ty();
+    }
+
+    for row in reader.table::<Event>() {
+        _ = (row.flags(), row.name(), row.methods().count());
+        _ = (row.adder(), row.remover(), row.raiser());
+    }
+
+    for row in reader.table::<EventMap>() {
+        _ = (row.parent(), row.events().count());
+    }
+
+    for row in reader.table::<Field>() {
+        _ = (row.flags(), row.name(), row.constant());
+        _ = row.attributes().count();
+    }
+
+    for row in reader.table::<GenericParam>() {
+        _ = (row.number(), row.name());
+    }
+
+    for row in reader.table::<ImplMap>() {
+        _ = (row.flags(), row.scope().name(), row.import_name());
+    }
+
+    for row in reader.table::<InterfaceImpl>() {
+        _ = row.attributes().count();
+    }
+
+    for row in reader.table::<MemberRef>() {
+        _ = (row.parent(), row.name());
+    }
+
+    for row in reader.table::<MethodDef>() {
+        _ = (row.impl_flags(), row.flags(), row.name(), row.module_name());
+        _ = (row.params().count(), row.impl_map());
+        _ = row.attributes().count();
+    }
+
+    for row in reader.table::<MethodSemantics>() {
+        _ = (row.flags(), row.method().name(), row.association());
+    }
+
+    for row in reader.table::<ModuleRef>() {
+        _ = row.name();
+    }
+
+    for row in reader.table::<NestedClass>() {
+        _ = (row.inner().name(), row.outer().name());
+    }
+
+    for row in reader.table::<Param>() {
+        _ = (row.flags(), row.sequence(), row.name());
+    }
+
+    for row in reader.table::<Property>() {
+        _ = (row.flags(), row.name(), row.methods().count());
+        _ = (row.getter(), row.setter());
+    }
+
+    for row in reader.table::<PropertyMap>() {
+        _ = (row.parent(), row.properties().count());
+    }
+
+    for row in reader.table::<TypeDef>() {
+        _ = (row.flags(), row.type_name(), row.extends(), row.kind());
+        _ = (
+            row.methods().count(),
+            row.fields().count(),
+            row.generics().count(),
+        );
+        _ = (row.properties().count(), row.events().count());
+        _ = (
+            row.interface_impls().count(),
+            row.enclosing_type(),
+            row.class_layout(),
+        );
+        _ = reader.nested_types(row).count();
+    }
+
+    for row in reader.table::<TypeRef>() {
+        _ = (row.type_name(), row.resolution_scope());
+    }
+});
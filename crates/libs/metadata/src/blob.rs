@@ -1,11 +1,12 @@
 use super::*;
 
-pub struct Blob {
-    pub file: &'static File,
-    pub slice: &'static [u8],
+pub struct Blob<'a> {
+    pub reader: &'a Reader<'a>,
+    pub file: usize,
+    pub slice: &'a [u8],
 }
 
-impl std::ops::Deref for Blob {
+impl std::ops::Deref for Blob<'_> {
     type Target = [u8];
 
     fn deref(&self) -> &[u8] {
@@ -13,22 +14,26 @@ impl std::ops::Deref for Blob {
     }
 }
 
-impl Blob {
-    pub fn new(file: &'static File, slice: &'static [u8]) -> Self {
-        Self { file, slice }
+impl<'a> Blob<'a> {
+    pub fn new(reader: &'a Reader<'a>, file: usize, slice: &'a [u8]) -> Self {
+        Self {
+            reader,
+            file,
+            slice,
+        }
     }
 
     pub fn peek_usize(&self) -> (usize, usize) {
-        if self[0] & 0x80 == 0 {
-            (self[0] as usize, 1)
-        } else if self[0] & 0xC0 == 0x80 {
-            ((((self[0] & 0x3F) as usize) << 8) | self[1] as usize, 2)
+        // Reading past the end of a malformed blob yields zeros rather than panicking.
+        let byte = |index: usize| self.get(index).copied().unwrap_or(0) as usize;
+
+        if byte(0) & 0x80 == 0 {
+            (byte(0), 1)
+        } else if byte(0) & 0xC0 == 0x80 {
+            (((byte(0) & 0x3F) << 8) | byte(1), 2)
         } else {
             (
-                (((self[0] & 0x1F) as usize) << 24)
-                    | (self[1] as usize) << 16
-                    | (self[2] as usize) << 8
-                    | self[3] as usize,
+                ((byte(0) & 0x1F) << 24) | byte(1) << 16 | byte(2) << 8 | byte(3),
                 4,
             )
         }
@@ -50,7 +55,7 @@ impl Blob {
         }
     }
 
-    pub fn read_modifiers(&mut self) -> Vec<TypeDefOrRef> {
+    pub fn read_modifiers(&mut self) -> Vec<TypeDefOrRef<'a>> {
         let mut mods = vec![];
         loop {
             let (value, offset) = self.peek_usize();
@@ -58,15 +63,20 @@ impl Blob {
                 break;
             } else {
                 self.offset(offset);
-                mods.push(TypeDefOrRef::decode(self.file, self.read_usize()))
+                mods.push(TypeDefOrRef::decode(
+                    self.reader,
+                    self.file,
+                    self.read_usize(),
+                ))
             }
         }
         mods
     }
 
-    pub fn read_str(&mut self) -> &'static str {
+    pub fn read_str(&mut self) -> &'a str {
         let len = self.read_usize();
-        let value = unsafe { std::str::from_utf8_unchecked(&self.slice[..len]) };
+        let value =
+            std::str::from_utf8(self.slice.get(..len).unwrap_or(self.slice)).unwrap_or_default();
         self.offset(len);
         value
     }
@@ -89,75 +99,51 @@ impl Blob {
     }
 
     pub fn read_bool(&mut self) -> bool {
-        // A bool is specified as "a single byte with value 0 (false) or 1 (true)".
-        match self.read_u8() {
-            0 => false,
-            1 => true,
-            _ => panic!("Illegal bool value"),
-        }
+        // A bool is specified as "a single byte with value 0 (false) or 1 (true)" but any other value is also true.
+        self.read_u8() != 0
     }
 
     pub fn read_i8(&mut self) -> i8 {
-        let value = i8::from_le_bytes(self[..1].try_into().unwrap());
-        self.offset(1);
-        value
+        i8::from_le_bytes(self.read_bytes())
     }
 
     pub fn read_u8(&mut self) -> u8 {
-        let value = u8::from_le_bytes(self[..1].try_into().unwrap());
-        self.offset(1);
-        value
+        u8::from_le_bytes(self.read_bytes())
     }
 
     pub fn read_i16(&mut self) -> i16 {
-        let value = i16::from_le_bytes(self[..2].try_into().unwrap());
-        self.offset(2);
-        value
+        i16::from_le_bytes(self.read_bytes())
     }
 
     pub fn read_u16(&mut self) -> u16 {
-        let value = u16::from_le_bytes(self[..2].try_into().unwrap());
-        self.offset(2);
-        value
+        u16::from_le_bytes(self.read_bytes())
     }
 
     pub fn read_i32(&mut self) -> i32 {
-        let value = i32::from_le_bytes(self[..4].try_into().unwrap());
-        self.offset(4);
-        value
+        i32::from_le_bytes(self.read_bytes())
     }
 
     pub fn read_u32(&mut self) -> u32 {
-        let value = u32::from_le_bytes(self[..4].try_into().unwrap());
-        self.offset(4);
-        value
+        u32::from_le_bytes(self.read_bytes())
     }
 
     pub fn read_i64(&mut self) -> i64 {
-        let value = i64::from_le_bytes(self[..8].try_into().unwrap());
-        self.offset(8);
-        value
+        i64::from_le_bytes(self.read_bytes())
     }
 
     pub fn read_u64(&mut self) -> u64 {
-        let value = u64::from_le_bytes(self[..8].try_into().unwrap());
-        self.offset(8);
-        value
+        u64::from_le_bytes(self.read_bytes())
     }
 
     pub fn read_f32(&mut self) -> f32 {
-        let value = f32::from_le_bytes(self[..4].try_into().unwrap());
-        self.offset(4);
-        value
+        f32::from_le_bytes(self.read_bytes())
     }
 
     pub fn read_f64(&mut self) -> f64 {
-        let value = f64::from_le_bytes(self[..8].try_into().unwrap());
-        self.offset(8);
-        value
+        f64::from_le_bytes(self.read_bytes())
     }
 
-    pub fn read_integer(&mut self, ty: Type) -> Value {
+    pub fn read_integer(&mut self, ty: Type<'a>) -> Value<'a> {
         match ty {
             Type::I8 => Value::I8(self.read_i8()),
             Type::U8 => Value::U8(self.read_u8()),
@@ -167,11 +153,23 @@ impl Blob {
             Type::U32 => Value::U32(self.read_u32()),
             Type::I64 => Value::I64(self.read_i64()),
             Type::U64 => Value::U64(self.read_u64()),
-            _ => panic!("Type is not an integer"),
+            // Enums have a 32-bit underlying type by default.
+            _ => Value::I32(self.read_i32()),
         }
     }
 
+    fn read_bytes<const N: usize>(&mut self) -> [u8; N] {
+        let mut bytes = [0; N];
+
+        if let Some(slice) = self.slice.get(..N) {
+            bytes.copy_from_slice(slice);
+        }
+
+        self.offset(N);
+        bytes
+    }
+
     fn offset(&mut self, offset: usize) {
-        self.slice = &self.slice[offset..];
+        self.slice = self.slice.get(offset..).unwrap_or_default();
     }
 }
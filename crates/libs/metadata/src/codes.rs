@@ -1,34 +1,43 @@
 use super::*;
 
-pub trait Decode {
-    fn decode(file: &'static File, code: usize) -> Self;
+pub trait Decode<'a> {
+    fn decode(reader: &'a Reader<'a>, file: usize, code: usize) -> Self;
 }
 
+// A malformed code decodes to an out-of-range row, whose columns all read as zero, of the first table.
 macro_rules! code {
-    ($name:ident($size:literal) $(($table:ident, $code:literal))+) => {
+    ($name:ident($size:literal) ($first:ident, $first_code:literal) $(($table:ident, $code:literal))*) => {
         #[derive(Clone, Debug, Hash, PartialEq, Eq, Ord, PartialOrd)]
-        pub enum $name {
-            $($table($table),)*
+        pub enum $name<'a> {
+            $first($first<'a>),
+            $($table($table<'a>),)*
         }
-        impl Decode for $name {
-            fn decode(file: &'static File, code: usize) -> Self {
-                let (kind, row) = (code & ((1 << $size) - 1), (code >> $size) - 1);
+        impl<'a> Decode<'a> for $name<'a> {
+            fn decode(reader: &'a Reader<'a>, file: usize, code: usize) -> Self {
+                let (kind, row) = (code & ((1 << $size) - 1), (code >> $size).wrapping_sub(1));
                 match kind {
-                    $($code => Self::$table($table(Row::new(file, row))),)*
-                    rest => unimplemented!("{rest:?}"),
+                    $first_code => Self::$first($first(Row::new(reader, file, row))),
+                    $($code => Self::$table($table(Row::new(reader, file, row))),)*
+                    _ => Self::$first($first(Row::new(reader, file, usize::MAX))),
                 }
             }
         }
-        impl $name {
+        impl $name<'_> {
             pub fn encode(&self) -> usize {
                 match self {
-                    $(Self::$table(row) => (row.index() + 1) << $size | $code,)*
+                    Self::$first(row) => row.index().wrapping_add(1) << $size | $first_code,
+                    $(Self::$table(row) => row.index().wrapping_add(1) << $size | $code,)*
                 }
             }
         }
+        impl<'a> From<$first<'a>> for $name<'a> {
+            fn from(from: $first<'a>) -> Self {
+                Self::$first(from)
+            }
+        }
         $(
-            impl From<$table> for $name {
-                fn from(from: $table) -> Self {
+            impl<'a> From<$table<'a>> for $name<'a> {
+                fn from(from: $table<'a>) -> Self {
                     Self::$table(from)
                 }
             }
@@ -48,6 +57,8 @@ code! { HasAttribute(5)
     (Param, 4)
     (InterfaceImpl, 5)
     (MemberRef, 6)
+    (Property, 9)
+    (Event, 10)
     (TypeSpec, 13)
     (GenericParam, 19)
 }
@@ -56,6 +67,11 @@ code! { HasConstant(2)
     (Field, 0)
 }
 
+code! { HasSemantics(1)
+    (Event, 0)
+    (Property, 1)
+}
+
 code! { MemberForwarded(1)
     (MethodDef, 1)
 }
@@ -81,12 +97,13 @@ code! { ResolutionScope(2)
     (TypeRef, 3)
 }
 
-impl TypeDefOrRef {
-    pub fn type_name(&self) -> TypeName {
+impl<'a> TypeDefOrRef<'a> {
+    pub fn type_name(&self) -> TypeName<'a> {
         match self {
             Self::TypeDef(row) => row.type_name(),
             Self::TypeRef(row) => row.type_name(),
-            rest => unimplemented!("{rest:?}"),
+            // A type specification has no name of its own.
+            Self::TypeSpec(_) => TypeName("", ""),
         }
     }
 }
@@ -0,0 +1,75 @@
+/// Describes why a file could not be read as ECMA-335 metadata by naming the offending structure along with its byte
+/// offset within the file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FileError {
+    /// The file ends before the end of the structure.
+    Truncated {
+        structure: &'static str,
+        offset: usize,
+    },
+    /// The structure does not have the expected signature or size.
+    InvalidSignature {
+        structure: &'static str,
+        offset: usize,
+    },
+    /// The structure's relative virtual address, read from the given offset, does not fall within any section.
+    InvalidRva {
+        structure: &'static str,
+        offset: usize,
+    },
+    /// The structure, such as a stream or table, is valid but isn't supported by this reader.
+    Unsupported {
+        structure: &'static str,
+        offset: usize,
+    },
+    /// The structure is required but missing from the metadata found at the given offset.
+    Missing {
+        structure: &'static str,
+        offset: usize,
+    },
+}
+
+impl FileError {
+    /// The name of the structure that could not be read.
+    pub fn structure(&self) -> &'static str {
+        match self {
+            Self::Truncated { structure, .. }
+            | Self::InvalidSignature { structure, .. }
+            | Self::InvalidRva { structure, .. }
+            | Self::Unsupported { structure, .. }
+            | Self::Missing { structure, .. } => structure,
+        }
+    }
+
+    /// The byte offset within the file at which the structure was expected.
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::Truncated { offset, .. }
+            | Self::InvalidSignature { offset, .. }
+            | Self::InvalidRva { offset, .. }
+            | Self::Unsupported { offset, .. }
+            | Self::Missing { offset, .. } => *offset,
+        }
+    }
+}
+
+impl std::error::Error for FileError {}
+
+impl std::fmt::Display for FileError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let problem = match self {
+            Self::Truncated { .. } => "is truncated",
+            Self::InvalidSignature { .. } => "has an invalid signature",
+            Self::InvalidRva { .. } => "has an invalid relative virtual address",
+            Self::Unsupported { .. } => "is not supported",
+            Self::Missing { .. } => "is missing",
+        };
+
+        write!(
+            fmt,
+            "{} {problem} at offset {:#x}",
+            self.structure(),
+            self.offset()
+        )
+    }
+}
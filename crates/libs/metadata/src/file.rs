@@ -1,138 +1,207 @@
 use super::*;
 
-pub struct File {
-    pub reader: *const Reader,
-    pub bytes: Vec<u8>,
+pub struct File<'a> {
+    pub bytes: Cow<'a, [u8]>,
     pub strings: usize,
     pub blobs: usize,
-    pub tables: [Table; 17],
+    pub tables: [Table; 22],
 }
 
-impl std::fmt::Debug for File {
+impl std::fmt::Debug for File<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         std::write!(f, "{:?}", self.bytes.as_ptr())
     }
 }
 
-impl std::hash::Hash for File {
+impl std::hash::Hash for File<'_> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.bytes.as_ptr().hash(state);
     }
 }
 
-impl PartialEq for File {
+impl PartialEq for File<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.bytes.as_ptr() == other.bytes.as_ptr()
     }
 }
 
-impl Eq for File {}
+impl Eq for File<'_> {}
 
-impl Ord for File {
+impl Ord for File<'_> {
     fn cmp(&self, other: &Self) -> Ordering {
         self.bytes.as_ptr().cmp(&other.bytes.as_ptr())
     }
 }
 
-impl PartialOrd for File {
+impl PartialOrd for File<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-unsafe impl Sync for File {}
-
-impl File {
-    pub fn new(bytes: Vec<u8>) -> Option<Self> {
+impl<'a> File<'a> {
+    /// Reads the metadata from the given bytes without copying them. This may be an owned buffer or a borrowed slice,
+    /// such as the contents of a memory-mapped file, that outlives the `File`.
+    pub fn new(bytes: impl Into<Cow<'a, [u8]>>) -> Result<Self, FileError> {
         let mut result = File {
-            bytes,
-            reader: std::ptr::null(),
+            bytes: bytes.into(),
             strings: 0,
             blobs: 0,
             tables: Default::default(),
         };
 
-        let dos = result.bytes.view_as::<IMAGE_DOS_HEADER>(0)?;
+        let dos = result
+            .bytes
+            .read::<IMAGE_DOS_HEADER>(0, "IMAGE_DOS_HEADER")?;
 
-        if dos.e_magic != IMAGE_DOS_SIGNATURE
-            || result.bytes.copy_as::<u32>(dos.e_lfanew as usize)? != IMAGE_NT_SIGNATURE
-        {
-            return None;
+        if dos.e_magic != IMAGE_DOS_SIGNATURE {
+            return Err(FileError::InvalidSignature {
+                structure: "IMAGE_DOS_HEADER",
+                offset: 0,
+            });
         }
 
-        let file_offset = dos.e_lfanew as usize + std::mem::size_of::<u32>();
-        let file = result.bytes.view_as::<IMAGE_FILE_HEADER>(file_offset)?;
+        let nt_offset = dos.e_lfanew as usize;
+
+        if result.bytes.read::<u32>(nt_offset, "IMAGE_NT_HEADERS")? != IMAGE_NT_SIGNATURE {
+            return Err(FileError::InvalidSignature {
+                structure: "IMAGE_NT_HEADERS",
+                offset: nt_offset,
+            });
+        }
+
+        let file_offset = nt_offset + std::mem::size_of::<u32>();
+        let file = result
+            .bytes
+            .read::<IMAGE_FILE_HEADER>(file_offset, "IMAGE_FILE_HEADER")?;
 
         let optional_offset = file_offset + std::mem::size_of::<IMAGE_FILE_HEADER>();
 
-        let (com_virtual_address, sections) = match result.bytes.copy_as::<u16>(optional_offset)? {
+        let (com_virtual_address, sections_offset) = match result
+            .bytes
+            .read::<u16>(optional_offset, "IMAGE_OPTIONAL_HEADER")?
+        {
             IMAGE_NT_OPTIONAL_HDR32_MAGIC => {
                 let optional = result
                     .bytes
-                    .view_as::<IMAGE_OPTIONAL_HEADER32>(optional_offset)?;
+                    .read::<IMAGE_OPTIONAL_HEADER32>(optional_offset, "IMAGE_OPTIONAL_HEADER32")?;
                 (
                     optional.DataDirectory[IMAGE_DIRECTORY_ENTRY_COM_DESCRIPTOR as usize]
                         .VirtualAddress,
-                    result.bytes.view_as_slice_of::<IMAGE_SECTION_HEADER>(
-                        optional_offset + std::mem::size_of::<IMAGE_OPTIONAL_HEADER32>(),
-                        file.NumberOfSections as usize,
-                    )?,
+                    optional_offset + std::mem::size_of::<IMAGE_OPTIONAL_HEADER32>(),
                 )
             }
             IMAGE_NT_OPTIONAL_HDR64_MAGIC => {
                 let optional = result
                     .bytes
-                    .view_as::<IMAGE_OPTIONAL_HEADER64>(optional_offset)?;
+                    .read::<IMAGE_OPTIONAL_HEADER64>(optional_offset, "IMAGE_OPTIONAL_HEADER64")?;
                 (
                     optional.DataDirectory[IMAGE_DIRECTORY_ENTRY_COM_DESCRIPTOR as usize]
                         .VirtualAddress,
-                    result.bytes.view_as_slice_of::<IMAGE_SECTION_HEADER>(
-                        optional_offset + std::mem::size_of::<IMAGE_OPTIONAL_HEADER64>(),
-                        file.NumberOfSections as usize,
-                    )?,
+                    optional_offset + std::mem::size_of::<IMAGE_OPTIONAL_HEADER64>(),
                 )
             }
-            _ => return None,
+            _ => {
+                return Err(FileError::Unsupported {
+                    structure: "IMAGE_OPTIONAL_HEADER",
+                    offset: optional_offset,
+                })
+            }
         };
 
-        let clr = result.bytes.view_as::<IMAGE_COR20_HEADER>(offset_from_rva(
-            section_from_rva(sections, com_virtual_address)?,
-            com_virtual_address,
-        ))?;
+        let sections = (0..file.NumberOfSections as usize)
+            .map(|index| {
+                result.bytes.read::<IMAGE_SECTION_HEADER>(
+                    sections_offset + index * std::mem::size_of::<IMAGE_SECTION_HEADER>(),
+                    "IMAGE_SECTION_HEADER",
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let clr_offset =
+            offset_from_rva(&sections, com_virtual_address).ok_or(FileError::InvalidRva {
+                structure: "IMAGE_COR20_HEADER",
+                offset: optional_offset,
+            })?;
+
+        let clr = result
+            .bytes
+            .read::<IMAGE_COR20_HEADER>(clr_offset, "IMAGE_COR20_HEADER")?;
 
         if clr.cb != std::mem::size_of::<IMAGE_COR20_HEADER>() as u32 {
-            return None;
+            return Err(FileError::InvalidSignature {
+                structure: "IMAGE_COR20_HEADER",
+                offset: clr_offset,
+            });
         }
 
-        let metadata_offset = offset_from_rva(
-            section_from_rva(sections, clr.MetaData.VirtualAddress)?,
-            clr.MetaData.VirtualAddress,
-        );
-        let metadata = result.bytes.view_as::<METADATA_HEADER>(metadata_offset)?;
+        let metadata_offset = offset_from_rva(&sections, clr.MetaData.VirtualAddress).ok_or(
+            FileError::InvalidRva {
+                structure: "METADATA_HEADER",
+                offset: clr_offset,
+            },
+        )?;
+
+        let metadata = result
+            .bytes
+            .read::<METADATA_HEADER>(metadata_offset, "METADATA_HEADER")?;
 
         if metadata.signature != METADATA_SIGNATURE {
-            return None;
+            return Err(FileError::InvalidSignature {
+                structure: "METADATA_HEADER",
+                offset: metadata_offset,
+            });
         }
 
         // The METADATA_HEADER struct is not a fixed size so have to offset a little more carefully.
         let mut view = metadata_offset + metadata.length as usize + 20;
-        let mut tables_data: (usize, usize) = (0, 0);
+        let mut tables_data: Option<(usize, usize)> = None;
+        let mut strings = None;
+        let mut blobs = None;
+
+        for _ in 0..result.bytes.read::<u16>(
+            metadata_offset + metadata.length as usize + 18,
+            "METADATA_HEADER",
+        )? {
+            let stream_offset =
+                metadata_offset + result.bytes.read::<u32>(view, "STREAM_HEADER")? as usize;
+            let stream_len = result.bytes.read::<u32>(view + 4, "STREAM_HEADER")? as usize;
+            let stream_name = result
+                .bytes
+                .view_as_str(view + 8)
+                .ok_or(FileError::Truncated {
+                    structure: "STREAM_HEADER",
+                    offset: view,
+                })?;
+
+            let structure = match stream_name {
+                b"#Strings" => "#Strings",
+                b"#Blob" => "#Blob",
+                b"#~" => "#~",
+                b"#GUID" => "#GUID",
+                b"#US" => "#US",
+                _ => {
+                    return Err(FileError::Unsupported {
+                        structure: "STREAM_HEADER",
+                        offset: view,
+                    })
+                }
+            };
 
-        for _ in 0..result
-            .bytes
-            .copy_as::<u16>(metadata_offset + metadata.length as usize + 18)?
-        {
-            let stream_offset = result.bytes.copy_as::<u32>(view)? as usize;
-            let stream_len = result.bytes.copy_as::<u32>(view + 4)? as usize;
-            let stream_name = result.bytes.view_as_str(view + 8)?;
-            match stream_name {
-                b"#Strings" => result.strings = metadata_offset + stream_offset,
-                b"#Blob" => result.blobs = metadata_offset + stream_offset,
-                b"#~" => tables_data = (metadata_offset + stream_offset, stream_len),
-                b"#GUID" => {}
-                b"#US" => {}
-                rest => unimplemented!("{rest:?}"),
+            if stream_offset + stream_len > result.bytes.len() {
+                return Err(FileError::Truncated {
+                    structure,
+                    offset: stream_offset,
+                });
+            }
+
+            match structure {
+                "#Strings" => strings = Some(stream_offset),
+                "#Blob" => blobs = Some(stream_offset),
+                "#~" => tables_data = Some((stream_offset, stream_len)),
+                _ => {}
             }
+
             let mut padding = 4 - stream_name.len() % 4;
             if padding == 0 {
                 padding = 4;
@@ -140,11 +209,20 @@ impl File {
             view += 8 + stream_name.len() + padding;
         }
 
-        let heap_sizes = result.bytes.copy_as::<u8>(tables_data.0 + 6)?;
+        let missing = |structure| FileError::Missing {
+            structure,
+            offset: metadata_offset,
+        };
+
+        result.strings = strings.ok_or_else(|| missing("#Strings"))?;
+        result.blobs = blobs.ok_or_else(|| missing("#Blob"))?;
+        let tables_data = tables_data.ok_or_else(|| missing("#~"))?;
+
+        let heap_sizes = result.bytes.read::<u8>(tables_data.0 + 6, "#~")?;
         let string_index_size = if (heap_sizes & 1) == 1 { 4 } else { 2 };
         let guid_index_size = if (heap_sizes >> 1 & 1) == 1 { 4 } else { 2 };
         let blob_index_size = if (heap_sizes >> 2 & 1) == 1 { 4 } else { 2 };
-        let valid_bits = result.bytes.copy_as::<u64>(tables_data.0 + 8)?;
+        let valid_bits = result.bytes.read::<u64>(tables_data.0 + 8, "#~")?;
         view = tables_data.0 + 24;
 
         // These tables are unused by the reader, but needed temporarily to calculate sizes and offsets for subsequent tables.
@@ -155,8 +233,6 @@ impl File {
         let mut unused_assembly_ref_os = Table::default();
         let mut unused_assembly_ref_processor = Table::default();
         let mut unused_decl_security = Table::default();
-        let mut unused_event = Table::default();
-        let mut unused_event_map = Table::default();
         let mut unused_exported_type = Table::default();
         let mut unused_field_layout = Table::default();
         let mut unused_field_marshal = Table::default();
@@ -165,10 +241,7 @@ impl File {
         let mut unused_generic_param_constraint = Table::default();
         let mut unused_manifest_resource = Table::default();
         let mut unused_method_impl = Table::default();
-        let mut unused_method_semantics = Table::default();
         let mut unused_method_spec = Table::default();
-        let mut unused_property = Table::default();
-        let mut unused_property_map = Table::default();
         let mut unused_standalone_sig = Table::default();
 
         for i in 0..64 {
@@ -176,7 +249,7 @@ impl File {
                 continue;
             }
 
-            let len = result.bytes.copy_as::<u32>(view)? as usize;
+            let len = result.bytes.read::<u32>(view, "#~")? as usize;
             view += 4;
 
             match i {
@@ -195,11 +268,11 @@ impl File {
                 0x0f => result.tables[ClassLayout::TABLE].len = len,
                 0x10 => unused_field_layout.len = len,
                 0x11 => unused_standalone_sig.len = len,
-                0x12 => unused_event_map.len = len,
-                0x14 => unused_event.len = len,
-                0x15 => unused_property_map.len = len,
-                0x17 => unused_property.len = len,
-                0x18 => unused_method_semantics.len = len,
+                0x12 => result.tables[EventMap::TABLE].len = len,
+                0x14 => result.tables[Event::TABLE].len = len,
+                0x15 => result.tables[PropertyMap::TABLE].len = len,
+                0x17 => result.tables[Property::TABLE].len = len,
+                0x18 => result.tables[MethodSemantics::TABLE].len = len,
                 0x19 => unused_method_impl.len = len,
                 0x1a => result.tables[ModuleRef::TABLE].len = len,
                 0x1b => result.tables[TypeSpec::TABLE].len = len,
@@ -218,7 +291,12 @@ impl File {
                 0x2a => result.tables[GenericParam::TABLE].len = len,
                 0x2b => unused_method_spec.len = len,
                 0x2c => unused_generic_param_constraint.len = len,
-                _ => unreachable!(),
+                _ => {
+                    return Err(FileError::Unsupported {
+                        structure: "#~",
+                        offset: view - 4,
+                    })
+                }
             };
         }
 
@@ -231,7 +309,7 @@ impl File {
         let has_constant = coded_index_size(&[
             tables[Field::TABLE].len,
             tables[Param::TABLE].len,
-            unused_property.len,
+            tables[Property::TABLE].len,
         ]);
         let has_field_marshal =
             coded_index_size(&[tables[Field::TABLE].len, tables[Param::TABLE].len]);
@@ -247,7 +325,8 @@ impl File {
             tables[MethodDef::TABLE].len,
             tables[TypeSpec::TABLE].len,
         ]);
-        let has_semantics = coded_index_size(&[unused_event.len, unused_property.len]);
+        let has_semantics =
+            coded_index_size(&[tables[Event::TABLE].len, tables[Property::TABLE].len]);
         let method_def_or_ref =
             coded_index_size(&[tables[MethodDef::TABLE].len, tables[MemberRef::TABLE].len]);
         let member_forwarded =
@@ -282,8 +361,8 @@ impl File {
             tables[InterfaceImpl::TABLE].len,
             tables[MemberRef::TABLE].len,
             tables[Module::TABLE].len,
-            unused_property.len,
-            unused_event.len,
+            tables[Property::TABLE].len,
+            tables[Event::TABLE].len,
             unused_standalone_sig.len,
             tables[ModuleRef::TABLE].len,
             tables[TypeSpec::TABLE].len,
@@ -349,15 +428,15 @@ impl File {
             0,
         );
         unused_decl_security.set_columns(2, has_decl_security, blob_index_size, 0, 0, 0);
-        unused_event_map.set_columns(
+        result.tables[EventMap::TABLE].set_columns(
             result.tables[TypeDef::TABLE].index_width(),
-            unused_event.index_width(),
+            result.tables[Event::TABLE].index_width(),
             0,
             0,
             0,
             0,
         );
-        unused_event.set_columns(2, string_index_size, type_def_or_ref, 0, 0, 0);
+        result.tables[Event::TABLE].set_columns(2, string_index_size, type_def_or_ref, 0, 0, 0);
         unused_exported_type.set_columns(
             4,
             4,
@@ -428,7 +507,7 @@ impl File {
             0,
             0,
         );
-        unused_method_semantics.set_columns(
+        result.tables[MethodSemantics::TABLE].set_columns(
             2,
             result.tables[MethodDef::TABLE].index_width(),
             has_semantics,
@@ -455,10 +534,10 @@ impl File {
             0,
         );
         result.tables[Param::TABLE].set_columns(2, 2, string_index_size, 0, 0, 0);
-        unused_property.set_columns(2, string_index_size, blob_index_size, 0, 0, 0);
-        unused_property_map.set_columns(
+        result.tables[Property::TABLE].set_columns(2, string_index_size, blob_index_size, 0, 0, 0);
+        result.tables[PropertyMap::TABLE].set_columns(
             result.tables[TypeDef::TABLE].index_width(),
-            unused_property.index_width(),
+            result.tables[Property::TABLE].index_width(),
             0,
             0,
             0,
@@ -498,11 +577,11 @@ impl File {
         result.tables[ClassLayout::TABLE].set_data(&mut view);
         unused_field_layout.set_data(&mut view);
         unused_standalone_sig.set_data(&mut view);
-        unused_event_map.set_data(&mut view);
-        unused_event.set_data(&mut view);
-        unused_property_map.set_data(&mut view);
-        unused_property.set_data(&mut view);
-        unused_method_semantics.set_data(&mut view);
+        result.tables[EventMap::TABLE].set_data(&mut view);
+        result.tables[Event::TABLE].set_data(&mut view);
+        result.tables[PropertyMap::TABLE].set_data(&mut view);
+        result.tables[Property::TABLE].set_data(&mut view);
+        result.tables[MethodSemantics::TABLE].set_data(&mut view);
         unused_method_impl.set_data(&mut view);
         result.tables[ModuleRef::TABLE].set_data(&mut view);
         result.tables[TypeSpec::TABLE].set_data(&mut view);
@@ -520,11 +599,25 @@ impl File {
         result.tables[NestedClass::TABLE].set_data(&mut view);
         result.tables[GenericParam::TABLE].set_data(&mut view);
 
-        Some(result)
+        // Every row of every table must lie within the table stream so that reading a row is always in bounds.
+        if view > tables_data.0 + tables_data.1 {
+            return Err(FileError::Truncated {
+                structure: "#~",
+                offset: tables_data.0,
+            });
+        }
+
+        Ok(result)
     }
 
     pub fn usize(&self, row: usize, table: usize, column: usize) -> usize {
         let table = &self.tables[table];
+
+        // The tables are known to lie within the file, so this also keeps the offset in bounds.
+        if row >= table.len {
+            return 0;
+        }
+
         let column = &table.columns[column];
         let offset = table.offset + row * table.width + column.offset;
         match column.width {
@@ -578,81 +671,38 @@ impl File {
         }
         first
     }
-
-    pub fn table<R: AsRow>(&'static self) -> RowIterator<R> {
-        RowIterator::new(self, 0..self.tables[R::TABLE].len)
-    }
 }
 
-fn section_from_rva(sections: &[IMAGE_SECTION_HEADER], rva: u32) -> Option<&IMAGE_SECTION_HEADER> {
-    sections.iter().find(|&s| {
-        rva >= s.VirtualAddress && rva < s.VirtualAddress + unsafe { s.Misc.VirtualSize }
-    })
-}
+fn offset_from_rva(sections: &[IMAGE_SECTION_HEADER], rva: u32) -> Option<usize> {
+    let section = sections.iter().find(|s| {
+        rva >= s.VirtualAddress && (rva - s.VirtualAddress) < unsafe { s.Misc.VirtualSize }
+    })?;
 
-fn offset_from_rva(section: &IMAGE_SECTION_HEADER, rva: u32) -> usize {
-    (rva - section.VirtualAddress + section.PointerToRawData) as usize
+    Some((rva - section.VirtualAddress) as usize + section.PointerToRawData as usize)
 }
 
 trait View {
-    fn view_as<T>(&self, offset: usize) -> Option<&T>;
-    fn view_as_slice_of<T>(&self, offset: usize, len: usize) -> Option<&[T]>;
     fn copy_as<T: Copy>(&self, offset: usize) -> Option<T>;
+    fn read<T: Copy>(&self, offset: usize, structure: &'static str) -> Result<T, FileError>;
     fn view_as_str(&self, offset: usize) -> Option<&[u8]>;
-    fn is_proper_length<T>(&self, offset: usize) -> Option<()>;
-    fn is_proper_length_and_alignment<T>(&self, offset: usize, count: usize) -> Option<*const T>;
 }
 
 impl View for [u8] {
-    fn view_as<T>(&self, offset: usize) -> Option<&T> {
-        unsafe { Some(&*self.is_proper_length_and_alignment(offset, 1)?) }
-    }
+    fn copy_as<T: Copy>(&self, offset: usize) -> Option<T> {
+        let bytes = self.get(offset..offset.checked_add(std::mem::size_of::<T>())?)?;
 
-    fn view_as_slice_of<T>(&self, offset: usize, len: usize) -> Option<&[T]> {
-        unsafe {
-            Some(std::slice::from_raw_parts(
-                self.is_proper_length_and_alignment(offset, len)?,
-                len,
-            ))
-        }
+        // Safety: the bytes are in bounds and `T` is only ever a plain integer or header struct.
+        unsafe { Some(std::ptr::read_unaligned(bytes.as_ptr() as *const T)) }
     }
 
-    fn copy_as<T>(&self, offset: usize) -> Option<T> {
-        self.is_proper_length::<T>(offset)?;
-
-        unsafe {
-            let mut data = std::mem::MaybeUninit::zeroed().assume_init();
-            core::ptr::copy_nonoverlapping(
-                self[offset..].as_ptr(),
-                &mut data as *mut T as *mut u8,
-                std::mem::size_of::<T>(),
-            );
-            Some(data)
-        }
+    fn read<T: Copy>(&self, offset: usize, structure: &'static str) -> Result<T, FileError> {
+        self.copy_as(offset)
+            .ok_or(FileError::Truncated { structure, offset })
     }
 
     fn view_as_str(&self, offset: usize) -> Option<&[u8]> {
-        let buffer = &self[offset..];
+        let buffer = self.get(offset..)?;
         let index = buffer.iter().position(|c| *c == b'\0')?;
-        Some(&self[offset..offset + index])
-    }
-
-    fn is_proper_length<T>(&self, offset: usize) -> Option<()> {
-        if offset + std::mem::size_of::<T>() <= self.len() {
-            Some(())
-        } else {
-            None
-        }
-    }
-
-    fn is_proper_length_and_alignment<T>(&self, offset: usize, count: usize) -> Option<*const T> {
-        self.is_proper_length::<T>(offset * count)?;
-        let ptr = &self[offset] as *const u8 as *const T;
-
-        if ptr.align_offset(std::mem::align_of::<T>()) == 0 {
-            Some(ptr)
-        } else {
-            None
-        }
+        Some(&buffer[..index])
     }
 }
@@ -4,6 +4,7 @@ Learn more about Rust for Windows here: <https://github.com/microsoft/windows-rs
 
 #![doc(hidden)]
 
+use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::collections::*;
 
@@ -12,6 +13,7 @@ mod bindings;
 mod blob;
 mod codes;
 mod column;
+mod error;
 mod file;
 mod filter;
 mod reader;
@@ -26,6 +28,7 @@ pub use bindings::*;
 pub use blob::*;
 pub use codes::*;
 use column::*;
+pub use error::*;
 pub use file::*;
 use filter::*;
 pub use r#type::*;
@@ -36,7 +39,7 @@ pub use tables::*;
 pub use type_name::*;
 
 #[repr(C)]
-#[derive(Default)]
+#[derive(Default, Clone, Copy)]
 pub struct METADATA_HEADER {
     pub signature: u32,
     pub major_version: u16,
@@ -80,7 +83,7 @@ pub fn coded_index_size(tables: &[usize]) -> usize {
 }
 
 #[derive(Debug)]
-pub enum Value {
+pub enum Value<'a> {
     Bool(bool),
     U8(u8),
     I8(i8),
@@ -93,18 +96,18 @@ pub enum Value {
     F32(f32),
     F64(f64),
     String(String),
-    TypeName(TypeName),
-    EnumDef(TypeDef, Box<Self>),
+    TypeName(TypeName<'a>),
+    EnumDef(TypeDef<'a>, Box<Self>),
 }
 
 #[derive(Debug)]
-pub struct MethodDefSig {
+pub struct MethodDefSig<'a> {
     pub call_flags: MethodCallAttributes,
-    pub return_type: Type,
-    pub params: Vec<Type>,
+    pub return_type: Type<'a>,
+    pub params: Vec<Type<'a>>,
 }
 
-impl MethodDefSig {
+impl MethodDefSig<'_> {
     pub fn size(&self) -> usize {
         self.params
             .iter()
@@ -1,20 +1,33 @@
 use super::*;
 
 #[derive(Clone)]
-pub enum Item {
-    Type(TypeDef),
-    Const(Field),
+pub enum Item<'a> {
+    Type(TypeDef<'a>),
+    Const(Field<'a>),
     // TODO: get rid of the trailing String - that's just a hack to get around a silly Win32 metadata deficiency where parsing method signatures
     // requires knowing which namespace the method's surrounding interface was defined in.
-    Fn(MethodDef, &'static str),
+    Fn(MethodDef<'a>, &'a str),
 }
 
-pub struct Reader {
+// Rows borrow from the reader so the reader's own index refers to them by file and row index instead.
+#[derive(Clone, Copy)]
+enum Entry {
+    Type(usize, usize),
+    Const(usize, usize),
+    Fn(usize, usize),
+}
+
+type Items = BTreeMap<String, BTreeMap<String, Vec<Entry>>>;
+type Nested = HashMap<(usize, usize), BTreeMap<String, (usize, usize)>>;
+
+pub struct Reader<'a> {
+    files: Vec<File<'a>>,
+
     // TODO: get rid of inner Vec - that's just a hack to support multi-arch structs in Win32 metadata.
-    items: BTreeMap<&'static str, BTreeMap<&'static str, Vec<Item>>>,
+    items: Items,
 
     // TODO: riddle should just avoid nested structs
-    nested: HashMap<TypeDef, BTreeMap<&'static str, TypeDef>>,
+    nested: Nested,
 
     // The reader needs to store the filter since standalone code generation needs more than just the filtered items
     // in order to chase dependencies automatically. This is why `Reader::filter` can't just filter everything up front.
@@ -23,89 +36,109 @@ pub struct Reader {
     sys: bool,
 }
 
-impl Reader {
-    pub fn new(files: Vec<File>) -> &'static Self {
+impl<'a> Reader<'a> {
+    pub fn new(files: Vec<File<'a>>) -> Self {
         let mut config = BTreeMap::new();
         config.insert("sys", "");
         Self::filter(files, &[], &[], &config)
     }
 
     pub fn filter(
-        files: Vec<File>,
+        files: Vec<File<'a>>,
         include: &[&str],
         exclude: &[&str],
         config: &BTreeMap<&str, &str>,
-    ) -> &'static Self {
-        let reader: &'static mut Reader = Box::leak(Box::new(Self {
+    ) -> Self {
+        let mut reader = Self {
+            files,
             items: Default::default(),
             nested: Default::default(),
             filter: Filter::new(include, exclude),
             sys: config.contains_key("sys"),
-        }));
+        };
 
-        for mut file in files {
-            file.reader = reader as *mut Reader;
-            let file = Box::leak(Box::new(file));
+        let (items, nested) = reader.index();
+        reader.items = items;
+        reader.nested = nested;
+        reader
+    }
 
-            for def in file.table::<TypeDef>() {
-                let namespace = def.namespace();
+    fn index(&self) -> (Items, Nested) {
+        let mut items = Items::new();
+        let mut nested = Nested::new();
 
-                if namespace.is_empty() {
-                    continue;
-                }
+        for def in self.table::<TypeDef<'_>>() {
+            let namespace = def.namespace();
 
-                let namespace_items = reader.items.entry(namespace).or_default();
-                let name = def.name();
+            if namespace.is_empty() {
+                continue;
+            }
 
-                if name == "Apis" {
-                    for method in def.methods() {
-                        namespace_items
-                            .entry(method.name())
-                            .or_default()
-                            .push(Item::Fn(method, namespace));
-                    }
+            let namespace_items = items.entry(namespace.to_string()).or_default();
+            let name = def.name();
 
-                    for field in def.fields() {
-                        namespace_items
-                            .entry(field.name())
-                            .or_default()
-                            .push(Item::Const(field));
-                    }
-                } else {
+            if name == "Apis" {
+                for method in def.methods() {
                     namespace_items
-                        .entry(name)
+                        .entry(method.name().to_string())
                         .or_default()
-                        .push(Item::Type(def));
+                        .push(Entry::Fn(method.0.file, method.0.index));
+                }
 
-                    // TODO: these should all be fields on the Apis class so we don't have to go looking for all of these as well.
-                    if def.extends() == Some(TypeName::Enum)
-                        && !def.flags().contains(TypeAttributes::WindowsRuntime)
-                        && !def.has_attribute("ScopedEnumAttribute")
+                for field in def.fields() {
+                    namespace_items
+                        .entry(field.name().to_string())
+                        .or_default()
+                        .push(Entry::Const(field.0.file, field.0.index));
+                }
+            } else {
+                namespace_items
+                    .entry(name.to_string())
+                    .or_default()
+                    .push(Entry::Type(def.0.file, def.0.index));
+
+                // TODO: these should all be fields on the Apis class so we don't have to go looking for all of these as well.
+                if def.extends() == Some(TypeName::Enum)
+                    && !def.flags().contains(TypeAttributes::WindowsRuntime)
+                    && !def.has_attribute("ScopedEnumAttribute")
+                {
+                    for field in def
+                        .fields()
+                        .filter(|field| field.flags().contains(FieldAttributes::Literal))
                     {
-                        for field in def
-                            .fields()
-                            .filter(|field| field.flags().contains(FieldAttributes::Literal))
-                        {
-                            namespace_items
-                                .entry(field.name())
-                                .or_default()
-                                .push(Item::Const(field));
-                        }
+                        namespace_items
+                            .entry(field.name().to_string())
+                            .or_default()
+                            .push(Entry::Const(field.0.file, field.0.index));
                     }
                 }
             }
+        }
 
-            for key in file.table::<NestedClass>() {
-                let inner = key.inner();
-                reader
-                    .nested
-                    .entry(key.outer())
-                    .or_default()
-                    .insert(inner.name(), inner);
-            }
+        for key in self.table::<NestedClass<'_>>() {
+            let inner = key.inner();
+            let outer = key.outer();
+            nested
+                .entry((outer.0.file, outer.0.index))
+                .or_default()
+                .insert(inner.name().to_string(), (inner.0.file, inner.0.index));
         }
 
-        reader
+        (items, nested)
+    }
+
+    pub fn files(&self) -> &[File<'a>] {
+        &self.files
+    }
+
+    /// Returns every row of the given table in each of the reader's files.
+    pub fn table<R: AsRow<'a> + 'a>(&'a self) -> impl Iterator<Item = R> + 'a {
+        self.files
+            .iter()
+            .enumerate()
+            .flat_map(move |(index, file)| {
+                RowIterator::new(self, index, 0..file.tables[R::TABLE].len)
+            })
     }
 
     pub fn includes_namespace(&self, namespace: &str) -> bool {
@@ -113,10 +146,10 @@ impl Reader {
     }
 
     pub fn namespaces(&self) -> impl Iterator<Item = &str> + '_ {
-        self.items.keys().copied()
+        self.items.keys().map(|namespace| namespace.as_str())
     }
 
-    pub fn items(&self) -> impl Iterator<Item = Item> + '_ {
+    pub fn items(&'a self) -> impl Iterator<Item = Item<'a>> + 'a {
         self.items
             .iter()
             .filter(move |(namespace, _)| self.filter.includes_namespace(namespace))
@@ -124,12 +157,13 @@ impl Reader {
                 items
                     .iter()
                     .filter(move |(name, _)| self.filter.includes_type_name(namespace, name))
+                    .flat_map(move |(_, items)| {
+                        items.iter().map(move |entry| self.item(namespace, *entry))
+                    })
             })
-            .flat_map(move |(_, items)| items)
-            .cloned()
     }
 
-    pub fn namespace_items(&self, namespace: &str) -> impl Iterator<Item = Item> + '_ {
+    pub fn namespace_items(&'a self, namespace: &str) -> impl Iterator<Item = Item<'a>> + 'a {
         self.items
             .get_key_value(namespace)
             .into_iter()
@@ -137,9 +171,10 @@ impl Reader {
                 items
                     .iter()
                     .filter(move |(name, _)| self.filter.includes_type_name(namespace, name))
+                    .flat_map(move |(_, items)| {
+                        items.iter().map(move |entry| self.item(namespace, *entry))
+                    })
             })
-            .flat_map(move |(_, items)| items)
-            .cloned()
     }
 
     pub fn unused(&self) -> impl Iterator<Item = &str> + '_ {
@@ -182,16 +217,29 @@ impl Reader {
         true
     }
 
-    fn get_item(&self, namespace: &str, name: &str) -> impl Iterator<Item = Item> + '_ {
-        if let Some(items) = self.items.get(namespace) {
-            if let Some(items) = items.get(name) {
-                return Some(items.iter().cloned()).into_iter().flatten();
-            }
+    fn item(&'a self, namespace: &'a str, entry: Entry) -> Item<'a> {
+        match entry {
+            Entry::Type(file, index) => Item::Type(TypeDef(Row::new(self, file, index))),
+            Entry::Const(file, index) => Item::Const(Field(Row::new(self, file, index))),
+            Entry::Fn(file, index) => Item::Fn(MethodDef(Row::new(self, file, index)), namespace),
         }
-        None.into_iter().flatten()
     }
 
-    pub fn get_type_def(&self, namespace: &str, name: &str) -> impl Iterator<Item = TypeDef> + '_ {
+    fn get_item(&'a self, namespace: &str, name: &str) -> impl Iterator<Item = Item<'a>> + 'a {
+        self.items
+            .get_key_value(namespace)
+            .and_then(|(namespace, items)| Some((namespace, items.get(name)?)))
+            .into_iter()
+            .flat_map(move |(namespace, items)| {
+                items.iter().map(move |entry| self.item(namespace, *entry))
+            })
+    }
+
+    pub fn get_type_def(
+        &'a self,
+        namespace: &str,
+        name: &str,
+    ) -> impl Iterator<Item = TypeDef<'a>> + 'a {
         self.get_item(namespace, name).filter_map(|item| {
             if let Item::Type(def) = item {
                 Some(def)
@@ -202,10 +250,10 @@ impl Reader {
     }
 
     pub fn get_method_def(
-        &self,
+        &'a self,
         namespace: &str,
         name: &str,
-    ) -> impl Iterator<Item = (MethodDef, &'static str)> + '_ {
+    ) -> impl Iterator<Item = (MethodDef<'a>, &'a str)> + 'a {
         self.get_item(namespace, name).filter_map(|item| {
             if let Item::Fn(def, namespace) = item {
                 Some((def, namespace))
@@ -215,15 +263,17 @@ impl Reader {
         })
     }
 
-    pub fn nested_types(&self, type_def: TypeDef) -> impl Iterator<Item = TypeDef> + '_ {
+    pub fn nested_types(&'a self, type_def: TypeDef<'a>) -> impl Iterator<Item = TypeDef<'a>> + 'a {
         self.nested
-            .get(&type_def)
-            .map(|map| map.values().copied())
+            .get(&(type_def.0.file, type_def.0.index))
             .into_iter()
-            .flatten()
+            .flat_map(move |map| {
+                map.values()
+                    .map(move |(file, index)| TypeDef(Row::new(self, *file, *index)))
+            })
     }
 
-    pub fn remap_type(&self, name: TypeName) -> Option<TypeName> {
+    pub fn remap_type(&self, name: TypeName<'a>) -> Option<TypeName<'a>> {
         match name {
             TypeName::D2D_MATRIX_3X2_F => Some(TypeName::Matrix3x2),
             TypeName::D3DMATRIX => Some(TypeName::Matrix4x4),
@@ -232,7 +282,7 @@ impl Reader {
         }
     }
 
-    pub fn core_type(&self, name: TypeName) -> Option<Type> {
+    pub fn core_type(&self, name: TypeName<'a>) -> Option<Type<'a>> {
         match name {
             TypeName::HSTRING => Some(Type::String),
             TypeName::IInspectable => Some(Type::Object),
@@ -253,14 +303,14 @@ impl Reader {
     }
 
     pub fn type_from_ref(
-        &self,
-        code: TypeDefOrRef,
-        enclosing: Option<TypeDef>,
-        generics: &[Type],
-    ) -> Type {
+        &'a self,
+        code: TypeDefOrRef<'a>,
+        enclosing: Option<TypeDef<'a>>,
+        generics: &[Type<'a>],
+    ) -> Type<'a> {
         if let TypeDefOrRef::TypeSpec(def) = code {
             let mut blob = def.blob(0);
-            return self.type_from_blob_impl(&mut blob, None, generics);
+            return self.type_from_blob_impl(&mut blob, None, generics, 0);
         }
 
         let mut full_name = code.type_name();
@@ -276,18 +326,13 @@ impl Reader {
         // TODO: this needs to be deferred via a TypeName's optional nested type name?
         if let Some(outer) = enclosing {
             if full_name.namespace().is_empty() {
-                let Some(inner) = self
+                if let Some((file, index)) = self
                     .nested
-                    .get(&outer)
+                    .get(&(outer.0.file, outer.0.index))
                     .and_then(|nested| nested.get(full_name.name()))
-                else {
-                    panic!(
-                        "Nested type not found: {}.{}",
-                        outer.type_name(),
-                        full_name.name()
-                    );
-                };
-                return Type::TypeDef(*inner, Vec::new());
+                {
+                    return Type::TypeDef(TypeDef(Row::new(self, *file, *index)), Vec::new());
+                }
             }
         }
 
@@ -303,11 +348,21 @@ impl Reader {
     }
 
     pub fn type_from_blob(
-        &self,
-        blob: &mut Blob,
-        enclosing: Option<TypeDef>,
-        generics: &[Type],
-    ) -> Type {
+        &'a self,
+        blob: &mut Blob<'a>,
+        enclosing: Option<TypeDef<'a>>,
+        generics: &[Type<'a>],
+    ) -> Type<'a> {
+        self.type_from_blob_depth(blob, enclosing, generics, 0)
+    }
+
+    fn type_from_blob_depth(
+        &'a self,
+        blob: &mut Blob<'a>,
+        enclosing: Option<TypeDef<'a>>,
+        generics: &[Type<'a>],
+        depth: usize,
+    ) -> Type<'a> {
         // Used by WinRT to indicate that a struct input parameter is passed by reference rather than by value on the ABI.
         let is_const = blob
             .read_modifiers()
@@ -330,7 +385,7 @@ impl Reader {
             pointers += 1;
         }
 
-        let kind = self.type_from_blob_impl(blob, enclosing, generics);
+        let kind = self.type_from_blob_impl(blob, enclosing, generics, depth);
 
         if pointers > 0 {
             Type::MutPtr(Box::new(kind), pointers)
@@ -348,11 +403,17 @@ impl Reader {
     }
 
     fn type_from_blob_impl(
-        &self,
-        blob: &mut Blob,
-        enclosing: Option<TypeDef>,
-        generics: &[Type],
-    ) -> Type {
+        &'a self,
+        blob: &mut Blob<'a>,
+        enclosing: Option<TypeDef<'a>>,
+        generics: &[Type<'a>],
+        depth: usize,
+    ) -> Type<'a> {
+        // Types in well-formed metadata are never nested this deeply, so this only guards against stack overflow.
+        if depth > 64 {
+            return Type::Void;
+        }
+
         let code = blob.read_usize();
 
         if let Some(code) = Type::from_code(code) {
@@ -360,17 +421,19 @@ impl Reader {
         }
 
         match code as u8 {
-            ELEMENT_TYPE_VALUETYPE | ELEMENT_TYPE_CLASS => self.type_from_ref(
-                TypeDefOrRef::decode(blob.file, blob.read_usize()),
-                enclosing,
-                generics,
-            ),
+            ELEMENT_TYPE_VALUETYPE | ELEMENT_TYPE_CLASS => {
+                match TypeDefOrRef::decode(blob.reader, blob.file, blob.read_usize()) {
+                    // A type specification may not be referred to in this way and could refer back to itself.
+                    TypeDefOrRef::TypeSpec(_) => Type::Void,
+                    code => self.type_from_ref(code, enclosing, generics),
+                }
+            }
             ELEMENT_TYPE_VAR => generics
                 .get(blob.read_usize())
                 .unwrap_or(&Type::Void)
                 .clone(),
             ELEMENT_TYPE_ARRAY => {
-                let kind = self.type_from_blob(blob, enclosing, generics);
+                let kind = self.type_from_blob_depth(blob, enclosing, generics, depth + 1);
                 let _rank = blob.read_usize();
                 let _count = blob.read_usize();
                 let bounds = blob.read_usize();
@@ -379,20 +442,26 @@ impl Reader {
             ELEMENT_TYPE_GENERICINST => {
                 blob.read_usize(); // ELEMENT_TYPE_VALUETYPE or ELEMENT_TYPE_CLASS
 
-                let type_name = TypeDefOrRef::decode(blob.file, blob.read_usize()).type_name();
-                let def = self
-                    .get_type_def(type_name.namespace(), type_name.name())
-                    .next()
-                    .unwrap_or_else(|| panic!("Type not found: {}", type_name));
-                let mut args = Vec::with_capacity(blob.read_usize());
+                let type_name =
+                    TypeDefOrRef::decode(blob.reader, blob.file, blob.read_usize()).type_name();
+                let count = blob.read_usize();
+                let mut args = vec![];
 
-                for _ in 0..args.capacity() {
-                    args.push(self.type_from_blob_impl(blob, enclosing, generics));
+                // A malformed argument count may be far larger than the number of arguments in the blob.
+                while args.len() < count && !blob.is_empty() {
+                    args.push(self.type_from_blob_impl(blob, enclosing, generics, depth + 1));
                 }
 
-                Type::TypeDef(def, args)
+                if let Some(def) = self
+                    .get_type_def(type_name.namespace(), type_name.name())
+                    .next()
+                {
+                    Type::TypeDef(def, args)
+                } else {
+                    Type::Name(type_name)
+                }
             }
-            rest => unimplemented!("{rest:?}"),
+            _ => Type::Void,
         }
     }
 }
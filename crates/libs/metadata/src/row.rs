@@ -1,36 +1,83 @@
 use super::*;
 
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Ord, PartialOrd)]
-pub struct Row {
-    pub file: &'static File,
+/// A row in one of the reader's files, identified by the index of the file within the reader and the index of the row
+/// within its table.
+#[derive(Copy, Clone)]
+pub struct Row<'a> {
+    pub reader: &'a Reader<'a>,
+    pub file: usize,
     pub index: usize,
 }
 
-impl Row {
-    pub fn new(file: &'static File, index: usize) -> Self {
-        Self { file, index }
+impl<'a> Row<'a> {
+    pub fn new(reader: &'a Reader<'a>, file: usize, index: usize) -> Self {
+        Self {
+            reader,
+            file,
+            index,
+        }
     }
 
     fn next(&self) -> Self {
         Self {
+            reader: self.reader,
             file: self.file,
-            index: self.index + 1,
+            index: self.index.wrapping_add(1),
         }
     }
+
+    fn key(&self) -> (*const Reader<'a>, usize, usize) {
+        (self.reader, self.file, self.index)
+    }
+}
+
+impl std::fmt::Debug for Row<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Row")
+            .field("file", &self.file)
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+impl std::hash::Hash for Row<'_> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        (self.file, self.index).hash(state);
+    }
+}
+
+impl PartialEq for Row<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Row<'_> {}
+
+impl Ord for Row<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl PartialOrd for Row<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
-pub trait AsRow: Copy {
+pub trait AsRow<'a>: Copy {
     const TABLE: usize;
-    fn to_row(&self) -> Row;
-    fn from_row(row: Row) -> Self;
+    fn to_row(&self) -> Row<'a>;
+    fn from_row(row: Row<'a>) -> Self;
 
-    fn file(&self) -> &'static File {
-        self.to_row().file
+    fn file(&self) -> &'a File<'a> {
+        let row = self.to_row();
+        &row.reader.files()[row.file]
     }
 
-    fn reader(&self) -> &'static Reader {
-        // Safety: At this point the File is already pointing to a valid Reader.
-        unsafe { &*self.file().reader }
+    fn reader(&self) -> &'a Reader<'a> {
+        self.to_row().reader
     }
 
     fn index(&self) -> usize {
@@ -45,60 +92,74 @@ pub trait AsRow: Copy {
         self.file().usize(self.index(), Self::TABLE, column)
     }
 
-    fn str(&self, column: usize) -> &'static str {
+    fn str(&self, column: usize) -> &'a str {
         let file = self.file();
-        let offset = file.strings + self.usize(column);
-        let bytes = &file.bytes[offset..];
-        let nul_pos = bytes
-            .iter()
-            .position(|&c| c == 0)
-            .expect("expected null-terminated C-string");
-        std::str::from_utf8(&bytes[..nul_pos]).expect("expected valid utf-8 C-string")
+        let bytes = file
+            .bytes
+            .get(file.strings + self.usize(column)..)
+            .unwrap_or_default();
+        let bytes = bytes.split(|&c| c == 0).next().unwrap_or_default();
+        std::str::from_utf8(bytes).unwrap_or_default()
     }
 
-    fn row(&self, column: usize) -> Row {
-        Row::new(self.file(), self.usize(column) - 1)
+    // A null or malformed index refers to an out-of-range row whose columns all read as zero.
+    fn row(&self, column: usize) -> Row<'a> {
+        let row = self.to_row();
+        Row::new(row.reader, row.file, self.usize(column).wrapping_sub(1))
     }
 
-    fn decode<T: Decode>(&self, column: usize) -> T {
-        T::decode(self.file(), self.usize(column))
+    fn decode<T: Decode<'a>>(&self, column: usize) -> T {
+        let row = self.to_row();
+        T::decode(row.reader, row.file, self.usize(column))
     }
 
-    fn blob(&self, column: usize) -> Blob {
+    fn blob(&self, column: usize) -> Blob<'a> {
+        let row = self.to_row();
         let file = self.file();
-        let offset = file.blobs + self.usize(column);
-        let initial_byte = file.bytes[offset];
+        let bytes = file
+            .bytes
+            .get(file.blobs + self.usize(column)..)
+            .unwrap_or_default();
+        let initial_byte = bytes.first().copied().unwrap_or(0);
 
         let (blob_size, blob_size_bytes) = match initial_byte >> 5 {
             0..=3 => (initial_byte & 0x7f, 1),
             4..=5 => (initial_byte & 0x3f, 2),
             6 => (initial_byte & 0x1f, 4),
-            rest => unimplemented!("{rest:?}"),
+            _ => return Blob::new(row.reader, row.file, &[]),
         };
 
         let mut blob_size = blob_size as usize;
 
-        for byte in &file.bytes[offset + 1..offset + blob_size_bytes] {
+        for byte in bytes.get(1..blob_size_bytes).unwrap_or_default() {
             blob_size = blob_size.checked_shl(8).unwrap_or(0) + (*byte as usize);
         }
 
-        let offset = offset + blob_size_bytes;
-        Blob::new(file, &file.bytes[offset..offset + blob_size])
+        Blob::new(
+            row.reader,
+            row.file,
+            bytes
+                .get(blob_size_bytes..blob_size_bytes + blob_size)
+                .unwrap_or_default(),
+        )
     }
 
-    fn list<R: AsRow>(&self, column: usize) -> RowIterator<R> {
+    fn list<R: AsRow<'a>>(&self, column: usize) -> RowIterator<'a, R> {
+        let row = self.to_row();
         let file = self.file();
-        let first = self.usize(column) - 1;
+        let len = file.tables[R::TABLE].len;
+        let first = self.usize(column).saturating_sub(1);
         let next = self.next();
         let last = if next.index() < file.tables[Self::TABLE].len {
-            next.usize(column) - 1
+            next.usize(column).saturating_sub(1)
         } else {
-            file.tables[R::TABLE].len
+            len
         };
-        RowIterator::new(file, first..last)
+        RowIterator::new(row.reader, row.file, first.min(len)..last.min(len))
     }
 
-    fn equal_range<L: AsRow>(&self, column: usize, value: usize) -> RowIterator<L> {
+    fn equal_range<L: AsRow<'a>>(&self, column: usize, value: usize) -> RowIterator<'a, L> {
+        let row = self.to_row();
         let file = self.file();
         let mut first = 0;
         let mut last = file.tables[L::TABLE].len;
@@ -130,19 +191,21 @@ pub trait AsRow: Copy {
             }
         }
 
-        RowIterator::new(file, first..last)
+        RowIterator::new(row.reader, row.file, first..last)
     }
 }
 
-pub struct RowIterator<R: AsRow> {
-    file: &'static File,
+pub struct RowIterator<'a, R: AsRow<'a>> {
+    reader: &'a Reader<'a>,
+    file: usize,
     rows: std::ops::Range<usize>,
     phantom: std::marker::PhantomData<R>,
 }
 
-impl<R: AsRow> RowIterator<R> {
-    pub fn new(file: &'static File, rows: std::ops::Range<usize>) -> Self {
+impl<'a, R: AsRow<'a>> RowIterator<'a, R> {
+    pub fn new(reader: &'a Reader<'a>, file: usize, rows: std::ops::Range<usize>) -> Self {
         Self {
+            reader,
             file,
             rows,
             phantom: std::marker::PhantomData,
@@ -150,28 +213,28 @@ impl<R: AsRow> RowIterator<R> {
     }
 }
 
-impl<R: AsRow> Iterator for RowIterator<R> {
+impl<'a, R: AsRow<'a>> Iterator for RowIterator<'a, R> {
     type Item = R;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.rows
             .next()
-            .map(|row| R::from_row(Row::new(self.file, row)))
+            .map(|row| R::from_row(Row::new(self.reader, self.file, row)))
     }
 }
 
-pub trait HasAttributes {
-    fn attributes(&self) -> RowIterator<Attribute>;
-    fn find_attribute(&self, name: &str) -> Option<Attribute>;
+pub trait HasAttributes<'a> {
+    fn attributes(&self) -> RowIterator<'a, Attribute<'a>>;
+    fn find_attribute(&self, name: &str) -> Option<Attribute<'a>>;
     fn has_attribute(&self, name: &str) -> bool;
 }
 
-impl<R: AsRow + Into<HasAttribute>> HasAttributes for R {
-    fn attributes(&self) -> RowIterator<Attribute> {
-        self.equal_range(0, Into::<HasAttribute>::into(*self).encode())
+impl<'a, R: AsRow<'a> + Into<HasAttribute<'a>>> HasAttributes<'a> for R {
+    fn attributes(&self) -> RowIterator<'a, Attribute<'a>> {
+        self.equal_range(0, Into::<HasAttribute<'a>>::into(*self).encode())
     }
 
-    fn find_attribute(&self, name: &str) -> Option<Attribute> {
+    fn find_attribute(&self, name: &str) -> Option<Attribute<'a>> {
         self.attributes().find(|attribute| attribute.name() == name)
     }
 
@@ -6,13 +6,13 @@ macro_rules! tables {
     ($(($name:ident, $table:literal))+) => {
         $(
         #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Ord, PartialOrd)]
-        pub struct $name(pub Row);
-        impl AsRow for $name {
+        pub struct $name<'a>(pub Row<'a>);
+        impl<'a> AsRow<'a> for $name<'a> {
             const TABLE: usize = $table;
-            fn to_row(&self) -> Row {
+            fn to_row(&self) -> Row<'a> {
                 self.0
             }
-            fn from_row(row: Row) -> Self {
+            fn from_row(row: Row<'a>) -> Self {
                 $name(row)
             }
         }
@@ -25,54 +25,62 @@ tables! {
     (Attribute, 1)
     (ClassLayout, 16)
     (Constant, 0)
+    (Event, 17)
+    (EventMap, 18)
     (Field, 2)
     (GenericParam, 3)
     (ImplMap, 11)
     (InterfaceImpl, 4)
     (MemberRef, 5)
     (MethodDef, 6)
+    (MethodSemantics, 21)
     (Module, 14)
     (ModuleRef, 12)
     (NestedClass, 13)
     (Param, 7)
+    (Property, 19)
+    (PropertyMap, 20)
     (TypeDef, 8)
     (TypeRef, 9)
     (TypeSpec, 10)
 }
 
-impl Attribute {
-    pub fn parent(&self) -> HasAttribute {
+impl<'a> Attribute<'a> {
+    pub fn parent(&self) -> HasAttribute<'a> {
         self.decode(0)
     }
 
-    pub fn ty(&self) -> AttributeType {
+    pub fn ty(&self) -> AttributeType<'a> {
         self.decode(1)
     }
 
-    pub fn name(&self) -> &'static str {
+    pub fn name(&self) -> &'a str {
         let AttributeType::MemberRef(ctor) = self.ty();
         let MemberRefParent::TypeRef(ty) = ctor.parent();
         ty.name()
     }
 
-    pub fn type_name(&self) -> TypeName {
+    pub fn type_name(&self) -> TypeName<'a> {
         let AttributeType::MemberRef(ctor) = self.ty();
         let MemberRefParent::TypeRef(ty) = ctor.parent();
         ty.type_name()
     }
 
-    pub fn args(&self) -> Vec<(&'static str, Value)> {
+    pub fn args(&self) -> Vec<(&'a str, Value<'a>)> {
         let AttributeType::MemberRef(member) = self.ty();
         let mut sig = member.blob(2);
         let mut values = self.blob(2);
-        let prolog = values.read_u16();
-        std::debug_assert_eq!(prolog, 1);
-        let this_and_gen_param_count = sig.read_usize();
-        std::debug_assert_eq!(this_and_gen_param_count, 32);
+        let mut args = vec![];
+
+        // Arguments are read up to the first one that can't be decoded so that a malformed attribute is truncated
+        // rather than causing a panic.
+        if values.read_u16() != 1 {
+            return args;
+        }
+
+        let _this_and_gen_param_count = sig.read_usize();
         let fixed_arg_count = sig.read_usize();
-        let ret_type = sig.read_usize();
-        std::debug_assert_eq!(ret_type, 1);
-        let mut args = Vec::with_capacity(fixed_arg_count);
+        let _ret_type = sig.read_usize();
         let reader = self.reader();
 
         for _ in 0..fixed_arg_count {
@@ -91,14 +99,13 @@ impl Attribute {
                 Type::TypeDef(def, _) => {
                     Value::EnumDef(def, Box::new(values.read_integer(def.underlying_type())))
                 }
-                rest => unimplemented!("{rest:?}"),
+                _ => return args,
             };
 
             args.push(("", arg));
         }
 
         let named_arg_count = values.read_u16();
-        args.reserve(named_arg_count as usize);
 
         for _ in 0..named_arg_count {
             let _id = values.read_u8();
@@ -113,37 +120,37 @@ impl Attribute {
                 0x50 => Value::TypeName(TypeName::parse(values.read_str())),
                 0x55 => {
                     let type_name = TypeName::parse(name);
-                    let def = reader
+                    let Some(def) = reader
                         .get_type_def(type_name.namespace(), type_name.name())
                         .next()
-                        .expect("Type not found");
+                    else {
+                        return args;
+                    };
                     name = values.read_str();
                     Value::EnumDef(def, Box::new(values.read_integer(def.underlying_type())))
                 }
-                rest => unimplemented!("{rest:?}"),
+                _ => return args,
             };
             args.push((name, arg));
         }
 
-        debug_assert_eq!(sig.slice.len(), 0);
-        debug_assert_eq!(values.slice.len(), 0);
-
         args
     }
 }
 
-impl ClassLayout {
+impl ClassLayout<'_> {
     pub fn packing_size(&self) -> usize {
         self.usize(0)
     }
 }
 
-impl Constant {
-    pub fn ty(&self) -> Type {
-        Type::from_code(self.usize(0)).expect("Constant type not found")
+impl<'a> Constant<'a> {
+    pub fn ty(&self) -> Type<'a> {
+        // A null reference, the only other kind of constant allowed by ECMA-335, has no value type.
+        Type::from_code(self.usize(0)).unwrap_or(Type::Void)
     }
 
-    pub fn value(&self) -> Value {
+    pub fn value(&self) -> Value<'a> {
         let mut blob = self.blob(2);
 
         match self.ty() {
@@ -158,27 +165,76 @@ impl Constant {
             Type::F32 => Value::F32(blob.read_f32()),
             Type::F64 => Value::F64(blob.read_f64()),
             Type::String => Value::String(blob.read_string()),
-            rest => unimplemented!("{rest:?}"),
+            Type::Bool => Value::Bool(blob.read_bool()),
+            Type::Char => Value::U16(blob.read_u16()),
+            // Null references and malformed constants are read as zero.
+            _ => Value::I32(blob.read_i32()),
         }
     }
 }
 
-impl Field {
+impl<'a> Event<'a> {
+    pub fn flags(&self) -> EventAttributes {
+        EventAttributes(self.usize(0) as u16)
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.str(1)
+    }
+
+    pub fn ty(&self, generics: &[Type<'a>]) -> Type<'a> {
+        self.reader().type_from_ref(self.decode(2), None, generics)
+    }
+
+    pub fn methods(&self) -> RowIterator<'a, MethodSemantics<'a>> {
+        self.equal_range(2, HasSemantics::Event(*self).encode())
+    }
+
+    pub fn adder(&self) -> Option<MethodDef<'a>> {
+        self.method(MethodSemanticsAttributes::AddOn)
+    }
+
+    pub fn remover(&self) -> Option<MethodDef<'a>> {
+        self.method(MethodSemanticsAttributes::RemoveOn)
+    }
+
+    pub fn raiser(&self) -> Option<MethodDef<'a>> {
+        self.method(MethodSemanticsAttributes::Fire)
+    }
+
+    fn method(&self, semantics: MethodSemanticsAttributes) -> Option<MethodDef<'a>> {
+        self.methods()
+            .find(|method| method.flags().contains(semantics))
+            .map(|method| method.method())
+    }
+}
+
+impl<'a> EventMap<'a> {
+    pub fn parent(&self) -> TypeDef<'a> {
+        TypeDef(self.row(0))
+    }
+
+    pub fn events(&self) -> RowIterator<'a, Event<'a>> {
+        self.list(1)
+    }
+}
+
+impl<'a> Field<'a> {
     pub fn flags(&self) -> FieldAttributes {
         FieldAttributes(self.usize(0) as u16)
     }
 
-    pub fn name(&self) -> &'static str {
+    pub fn name(&self) -> &'a str {
         self.str(1)
     }
 
-    pub fn constant(&self) -> Option<Constant> {
+    pub fn constant(&self) -> Option<Constant<'a>> {
         self.equal_range(1, HasConstant::Field(*self).encode())
             .next()
     }
 
     // TODO: enclosing craziness is only needed for nested structs - get rid of those in riddle and this goes away.
-    pub fn ty(&self, enclosing: Option<TypeDef>) -> Type {
+    pub fn ty(&self, enclosing: Option<TypeDef<'a>>) -> Type<'a> {
         let mut blob = self.blob(2);
         blob.read_usize();
         blob.read_modifiers();
@@ -192,63 +248,51 @@ impl Field {
     }
 }
 
-impl GenericParam {
+impl<'a> GenericParam<'a> {
     pub fn number(&self) -> u16 {
         self.usize(0) as u16
     }
 
-    pub fn name(&self) -> &'static str {
+    pub fn name(&self) -> &'a str {
         self.str(3)
     }
 }
 
-impl ImplMap {
+impl<'a> ImplMap<'a> {
     pub fn flags(&self) -> PInvokeAttributes {
         PInvokeAttributes(self.usize(0))
     }
 
-    pub fn scope(&self) -> ModuleRef {
+    pub fn scope(&self) -> ModuleRef<'a> {
         ModuleRef(self.row(3))
     }
 
-    pub fn import_name(&self) -> &'static str {
+    pub fn import_name(&self) -> &'a str {
         self.str(2)
     }
 }
 
-impl InterfaceImpl {
-    pub fn ty(&self, generics: &[Type]) -> Type {
+impl<'a> InterfaceImpl<'a> {
+    pub fn ty(&self, generics: &[Type<'a>]) -> Type<'a> {
         self.reader().type_from_ref(self.decode(1), None, generics)
     }
 }
 
-impl MemberRef {
-    pub fn parent(&self) -> MemberRefParent {
+impl<'a> MemberRef<'a> {
+    pub fn parent(&self) -> MemberRefParent<'a> {
         self.decode(0)
     }
 
-    pub fn name(&self) -> &'static str {
+    pub fn name(&self) -> &'a str {
         self.str(1)
     }
 
-    pub fn signature(&self) -> MethodDefSig {
-        let reader = self.reader();
-        let mut blob = self.blob(2);
-        let call_flags = MethodCallAttributes(blob.read_usize() as u8);
-        let params = blob.read_usize();
-        let return_type = reader.type_from_blob(&mut blob, None, &[]);
-
-        MethodDefSig {
-            call_flags,
-            return_type,
-            params: (0..params)
-                .map(|_| reader.type_from_blob(&mut blob, None, &[]))
-                .collect(),
-        }
+    pub fn signature(&self) -> MethodDefSig<'a> {
+        method_def_sig(self.reader(), self.blob(2), &[])
     }
 }
 
-impl MethodDef {
+impl<'a> MethodDef<'a> {
     pub fn impl_flags(&self) -> MethodImplAttributes {
         MethodImplAttributes(self.usize(1) as u16)
     }
@@ -257,57 +301,59 @@ impl MethodDef {
         MethodAttributes(self.usize(2) as u16)
     }
 
-    pub fn name(&self) -> &'static str {
+    pub fn name(&self) -> &'a str {
         self.str(3)
     }
 
-    pub fn params(&self) -> RowIterator<Param> {
+    pub fn params(&self) -> RowIterator<'a, Param<'a>> {
         self.list(5)
     }
 
-    pub fn impl_map(&self) -> Option<ImplMap> {
+    pub fn impl_map(&self) -> Option<ImplMap<'a>> {
         self.equal_range(1, MemberForwarded::MethodDef(*self).encode())
             .next()
     }
 
-    pub fn module_name(&self) -> &'static str {
+    pub fn module_name(&self) -> &'a str {
         self.impl_map().map_or("", |map| map.scope().name())
     }
 
-    pub fn signature(&self, generics: &[Type]) -> MethodDefSig {
-        let reader = self.reader();
-        let mut blob = self.blob(4);
-        let call_flags = MethodCallAttributes(blob.read_usize() as u8);
-        let params = blob.read_usize();
-        let return_type = reader.type_from_blob(&mut blob, None, generics);
-
-        MethodDefSig {
-            call_flags,
-            return_type,
-            params: (0..params)
-                .map(|_| reader.type_from_blob(&mut blob, None, generics))
-                .collect(),
-        }
+    pub fn signature(&self, generics: &[Type<'a>]) -> MethodDefSig<'a> {
+        method_def_sig(self.reader(), self.blob(4), generics)
+    }
+}
+
+impl<'a> MethodSemantics<'a> {
+    pub fn flags(&self) -> MethodSemanticsAttributes {
+        MethodSemanticsAttributes(self.usize(0) as u16)
+    }
+
+    pub fn method(&self) -> MethodDef<'a> {
+        MethodDef(self.row(1))
+    }
+
+    pub fn association(&self) -> HasSemantics<'a> {
+        self.decode(2)
     }
 }
 
-impl ModuleRef {
-    pub fn name(&self) -> &'static str {
+impl<'a> ModuleRef<'a> {
+    pub fn name(&self) -> &'a str {
         self.str(0)
     }
 }
 
-impl NestedClass {
-    pub fn inner(&self) -> TypeDef {
+impl<'a> NestedClass<'a> {
+    pub fn inner(&self) -> TypeDef<'a> {
         TypeDef(self.row(0))
     }
 
-    pub fn outer(&self) -> TypeDef {
+    pub fn outer(&self) -> TypeDef<'a> {
         TypeDef(self.row(1))
     }
 }
 
-impl Param {
+impl<'a> Param<'a> {
     pub fn flags(&self) -> ParamAttributes {
         ParamAttributes(self.usize(0) as u16)
     }
@@ -316,66 +362,138 @@ impl Param {
         self.usize(1) as u16
     }
 
-    pub fn name(&self) -> &'static str {
+    pub fn name(&self) -> &'a str {
         self.str(2)
     }
 }
 
-impl TypeDef {
+impl<'a> Property<'a> {
+    pub fn flags(&self) -> PropertyAttributes {
+        PropertyAttributes(self.usize(0) as u16)
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.str(1)
+    }
+
+    pub fn ty(&self, generics: &[Type<'a>]) -> Type<'a> {
+        let mut blob = self.blob(2);
+        let _flags = blob.read_usize();
+        let _params = blob.read_usize();
+        self.reader().type_from_blob(&mut blob, None, generics)
+    }
+
+    pub fn methods(&self) -> RowIterator<'a, MethodSemantics<'a>> {
+        self.equal_range(2, HasSemantics::Property(*self).encode())
+    }
+
+    pub fn getter(&self) -> Option<MethodDef<'a>> {
+        self.method(MethodSemanticsAttributes::Getter)
+    }
+
+    pub fn setter(&self) -> Option<MethodDef<'a>> {
+        self.method(MethodSemanticsAttributes::Setter)
+    }
+
+    fn method(&self, semantics: MethodSemanticsAttributes) -> Option<MethodDef<'a>> {
+        self.methods()
+            .find(|method| method.flags().contains(semantics))
+            .map(|method| method.method())
+    }
+}
+
+impl<'a> PropertyMap<'a> {
+    pub fn parent(&self) -> TypeDef<'a> {
+        TypeDef(self.row(0))
+    }
+
+    pub fn properties(&self) -> RowIterator<'a, Property<'a>> {
+        self.list(1)
+    }
+}
+
+impl<'a> TypeDef<'a> {
     pub fn flags(&self) -> TypeAttributes {
         TypeAttributes(self.usize(0) as u32)
     }
 
-    pub fn name(&self) -> &'static str {
+    pub fn name(&self) -> &'a str {
         trim_tick(self.str(1))
     }
 
-    pub fn namespace(&self) -> &'static str {
+    pub fn namespace(&self) -> &'a str {
         self.str(2)
     }
 
-    pub fn type_name(&self) -> TypeName {
+    pub fn type_name(&self) -> TypeName<'a> {
         TypeName(self.namespace(), self.name())
     }
 
-    pub fn extends(&self) -> Option<TypeName> {
-        let extends = self.usize(3);
-
-        if extends == 0 {
+    pub fn extends(&self) -> Option<TypeName<'a>> {
+        if self.usize(3) == 0 {
             return None;
         }
 
-        Some(TypeDefOrRef::decode(self.file(), extends).type_name())
+        Some(self.decode::<TypeDefOrRef<'_>>(3).type_name())
     }
 
-    pub fn methods(&self) -> RowIterator<MethodDef> {
+    pub fn methods(&self) -> RowIterator<'a, MethodDef<'a>> {
         self.list(5)
     }
 
-    pub fn fields(&self) -> RowIterator<Field> {
+    pub fn fields(&self) -> RowIterator<'a, Field<'a>> {
         self.list(4)
     }
 
-    pub fn generics(&self) -> RowIterator<GenericParam> {
+    // Unlike most tables, the PropertyMap and EventMap tables are not required to be sorted by parent.
+    pub fn properties(&self) -> RowIterator<'a, Property<'a>> {
+        let row = self.to_row();
+
+        self.reader()
+            .table::<PropertyMap<'_>>()
+            .find(|map| map.parent() == *self)
+            .map_or_else(
+                || RowIterator::new(row.reader, row.file, 0..0),
+                |map| map.properties(),
+            )
+    }
+
+    pub fn events(&self) -> RowIterator<'a, Event<'a>> {
+        let row = self.to_row();
+
+        self.reader()
+            .table::<EventMap<'_>>()
+            .find(|map| map.parent() == *self)
+            .map_or_else(
+                || RowIterator::new(row.reader, row.file, 0..0),
+                |map| map.events(),
+            )
+    }
+
+    pub fn generics(&self) -> RowIterator<'a, GenericParam<'a>> {
         self.equal_range(2, TypeOrMethodDef::TypeDef(*self).encode())
     }
 
-    pub fn interface_impls(&self) -> RowIterator<InterfaceImpl> {
-        self.equal_range(0, self.index() + 1)
+    pub fn interface_impls(&self) -> RowIterator<'a, InterfaceImpl<'a>> {
+        self.equal_range(0, self.index().wrapping_add(1))
     }
 
-    pub fn enclosing_type(&self) -> Option<TypeDef> {
-        self.equal_range::<NestedClass>(0, self.index() + 1)
+    pub fn enclosing_type(&self) -> Option<TypeDef<'a>> {
+        self.equal_range::<NestedClass<'_>>(0, self.index().wrapping_add(1))
             .next()
             .map(|row| TypeDef(row.row(1)))
     }
 
-    pub fn class_layout(&self) -> Option<ClassLayout> {
-        self.equal_range(2, self.index() + 1).next()
+    pub fn class_layout(&self) -> Option<ClassLayout<'a>> {
+        self.equal_range(2, self.index().wrapping_add(1)).next()
     }
 
-    pub fn underlying_type(&self) -> Type {
-        let field = self.fields().next().expect("Field not found");
+    pub fn underlying_type(&self) -> Type<'a> {
+        // Enums have a 32-bit underlying type by default.
+        let Some(field) = self.fields().next() else {
+            return Type::I32;
+        };
+
         if let Some(constant) = field.constant() {
             constant.ty()
         } else {
@@ -431,24 +549,45 @@ impl TypeDef {
     }
 }
 
-impl TypeRef {
-    pub fn name(&self) -> &'static str {
+impl<'a> TypeRef<'a> {
+    pub fn name(&self) -> &'a str {
         trim_tick(self.str(1))
     }
 
-    pub fn namespace(&self) -> &'static str {
+    pub fn namespace(&self) -> &'a str {
         self.str(2)
     }
 
-    pub fn type_name(&self) -> TypeName {
+    pub fn type_name(&self) -> TypeName<'a> {
         TypeName(self.namespace(), self.name())
     }
 
-    pub fn resolution_scope(&self) -> ResolutionScope {
+    pub fn resolution_scope(&self) -> ResolutionScope<'a> {
         self.decode(0)
     }
 }
 
+fn method_def_sig<'a>(
+    reader: &'a Reader<'a>,
+    mut blob: Blob<'a>,
+    generics: &[Type<'a>],
+) -> MethodDefSig<'a> {
+    let call_flags = MethodCallAttributes(blob.read_usize() as u8);
+    let params = blob.read_usize();
+    let return_type = reader.type_from_blob(&mut blob, None, generics);
+
+    MethodDefSig {
+        call_flags,
+        return_type,
+        // A malformed parameter count may be far larger than the number of parameters in the blob.
+        params: (0..params)
+            .map_while(|_| {
+                (!blob.is_empty()).then(|| reader.type_from_blob(&mut blob, None, generics))
+            })
+            .collect(),
+    }
+}
+
 fn trim_tick(name: &str) -> &str {
     if name.as_bytes().iter().rev().nth(1) == Some(&b'`') {
         &name[..name.len() - 2]
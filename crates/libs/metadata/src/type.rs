@@ -2,7 +2,7 @@ use super::*;
 
 // Note: Type::Name(TypeName) is preferred since we can use it in pattern matching whereas Type::TypeDef can't be used in that way.
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Ord, PartialOrd)]
-pub enum Type {
+pub enum Type<'a> {
     // Primitives in ECMA-335
     Void,
     Bool,
@@ -22,11 +22,11 @@ pub enum Type {
     String, // TODO: Win32 should use System.String when referring to an HSTRING
     Object, // TODO: Win32 should use System.Object when referring to an IInspectable
 
-    Name(TypeName),
-    Const(TypeName),
+    Name(TypeName<'a>),
+    Const(TypeName<'a>),
 
-    GenericParam(GenericParam),
-    TypeDef(TypeDef, Vec<Self>), // TODO: store generics inside TypeDef to simplify this
+    GenericParam(GenericParam<'a>),
+    TypeDef(TypeDef<'a>, Vec<Self>), // TODO: store generics inside TypeDef to simplify this
 
     MutPtr(Box<Self>, usize),
     ConstPtr(Box<Self>, usize),
@@ -39,7 +39,7 @@ pub enum Type {
     PrimitiveOrEnum(Box<Self>, Box<Self>),
 }
 
-impl Type {
+impl Type<'_> {
     /// Creates a `Type` object from an `ELEMENT_TYPE` (see ECMA-335) type constant, typically
     /// used to indicate the type of a constant or primitive type signature.
     pub fn from_code(code: usize) -> Option<Self> {
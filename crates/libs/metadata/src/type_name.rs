@@ -1,9 +1,9 @@
 #![allow(non_upper_case_globals)]
 
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Ord, PartialOrd)]
-pub struct TypeName(pub &'static str, pub &'static str);
+pub struct TypeName<'a>(pub &'a str, pub &'a str);
 
-impl TypeName {
+impl<'a> TypeName<'a> {
     pub const Enum: Self = Self("System", "Enum");
     pub const Delegate: Self = Self("System", "MulticastDelegate");
     pub const Struct: Self = Self("System", "ValueType");
@@ -53,23 +53,23 @@ impl TypeName {
     pub const VARIANT: Self = Self("Windows.Win32.System.Variant", "VARIANT");
     pub const PROPVARIANT: Self = Self("Windows.Win32.System.Com.StructuredStorage", "PROPVARIANT");
 
-    pub fn parse(full_name: &'static str) -> Self {
-        let index = full_name
-            .rfind('.')
-            .expect("Expected full name separated with `.`");
-        Self(&full_name[0..index], &full_name[index + 1..])
+    pub fn parse(full_name: &'a str) -> Self {
+        match full_name.rsplit_once('.') {
+            Some((namespace, name)) => Self(namespace, name),
+            None => Self("", full_name),
+        }
     }
 
-    pub fn namespace(&self) -> &'static str {
+    pub fn namespace(&self) -> &'a str {
         self.0
     }
 
-    pub fn name(&self) -> &'static str {
+    pub fn name(&self) -> &'a str {
         self.1
     }
 }
 
-impl std::fmt::Display for TypeName {
+impl std::fmt::Display for TypeName<'_> {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(fmt, "{}.{}", self.0, self.1)
     }
@@ -12,6 +12,7 @@ macro_rules! link {
         #[link(name = $library, kind = "raw-dylib", modifiers = "+verbatim", import_name_type = "undecorated")]
         extern $abi {
             $(#[link_name=$link_name])?
+            $(#[$doc])?
             pub fn $($function)*;
         }
     )
@@ -25,6 +26,7 @@ macro_rules! link {
         #[link(name = $library, kind = "raw-dylib", modifiers = "+verbatim")]
         extern "C" {
             $(#[link_name=$link_name])?
+            $(#[$doc])?
             pub fn $($function)*;
         }
     )
@@ -38,6 +40,7 @@ macro_rules! link {
         #[link(name = "windows.0.52.0")]
         extern $abi {
             $(#[link_name=$link_name])?
+            $(#[$doc])?
             pub fn $($function)*;
         }
     )
@@ -49,6 +52,7 @@ macro_rules! link {
 macro_rules! link {
     ($library:literal $abi:literal $($link_name:literal)? $(#[$doc:meta])? fn $($function:tt)*) => (
         extern $abi {
+            $(#[$doc])?
             pub fn $($function)*;
         }
     )
@@ -0,0 +1,25 @@
+mod sys {
+    windows_bindings::windows_bindings!(in = "src/test.rdl", filter = "Test", sys);
+}
+
+mod flat {
+    windows_bindings::windows_bindings!(
+        in = ["src/test.rdl"],
+        filter = ["Test", "!Test.Rect"],
+        flatten,
+    );
+}
+
+#[test]
+fn test() {
+    let rect = sys::Rect {
+        origin: sys::Point { x: 1, y: 2 },
+        width: 3,
+        height: 4,
+    };
+
+    assert_eq!(rect.origin.x + rect.origin.y + rect.width + rect.height, 10);
+
+    let point = flat::Point { x: 5, y: 6 };
+    assert_eq!(point, flat::Point { x: 5, y: 6 });
+}
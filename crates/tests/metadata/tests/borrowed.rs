@@ -0,0 +1,30 @@
+use metadata::*;
+use std::borrow::Cow;
+
+const WDK: &[u8] = include_bytes!("../../../libs/bindgen/default/Windows.Wdk.winmd");
+
+#[test]
+fn borrowed() {
+    // Stands in for a memory-mapped file that only lives as long as this function.
+    let bytes = WDK.to_vec();
+    let file = File::new(bytes.as_slice()).unwrap();
+
+    assert!(matches!(file.bytes, Cow::Borrowed(_)));
+    assert_eq!(file.bytes.as_ptr(), bytes.as_ptr());
+
+    let reader = Reader::new(vec![file]);
+
+    let def = reader
+        .get_type_def("Windows.Wdk.Foundation", "DRIVER_OBJECT")
+        .next()
+        .unwrap();
+
+    assert_eq!(def.name(), "DRIVER_OBJECT");
+    assert_eq!(def.kind(), TypeKind::Struct);
+}
+
+#[test]
+fn owned() {
+    let file = File::new(WDK.to_vec()).unwrap();
+    assert!(matches!(file.bytes, Cow::Owned(_)));
+}
@@ -0,0 +1,51 @@
+use metadata::*;
+
+const WDK: &[u8] = include_bytes!("../../../libs/bindgen/default/Windows.Wdk.winmd");
+
+#[test]
+fn empty() {
+    let error = File::new(vec![]).unwrap_err();
+
+    assert_eq!(
+        error,
+        FileError::Truncated {
+            structure: "IMAGE_DOS_HEADER",
+            offset: 0
+        }
+    );
+
+    assert_eq!(
+        error.to_string(),
+        "IMAGE_DOS_HEADER is truncated at offset 0x0"
+    );
+}
+
+#[test]
+fn signature() {
+    let mut bytes = WDK.to_vec();
+    bytes[0] = 0;
+
+    assert_eq!(
+        File::new(bytes).unwrap_err(),
+        FileError::InvalidSignature {
+            structure: "IMAGE_DOS_HEADER",
+            offset: 0
+        }
+    );
+}
+
+#[test]
+fn truncated() {
+    assert!(File::new(WDK.to_vec()).is_ok());
+
+    // Prefixes of a valid file fail with an error rather than a panic. The file ends with section padding, so only
+    // prefixes that cut into the metadata itself are checked.
+    for len in (0..WDK.len() * 9 / 10).step_by(WDK.len() / 100) {
+        let error = File::new(WDK[..len].to_vec()).unwrap_err();
+        assert!(matches!(error, FileError::Truncated { .. }), "{error}");
+        assert!(error.offset() <= len);
+    }
+
+    let error = File::new(WDK[..WDK.len() / 2].to_vec()).unwrap_err();
+    assert_eq!(error.structure(), "#~");
+}
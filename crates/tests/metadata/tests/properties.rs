@@ -0,0 +1,66 @@
+use metadata::*;
+
+#[test]
+fn properties() {
+    let files = tool_lib::default_metadata();
+    let reader = Reader::new(files);
+
+    let def = reader
+        .get_type_def("Windows.Foundation", "IAsyncAction")
+        .next()
+        .unwrap();
+
+    let property = def
+        .properties()
+        .find(|property| property.name() == "Completed")
+        .unwrap();
+
+    assert!(
+        matches!(property.ty(&[]), Type::TypeDef(def, _) if def.name() == "AsyncActionCompletedHandler")
+    );
+    assert_eq!(property.getter().unwrap().name(), "get_Completed");
+    assert_eq!(property.setter().unwrap().name(), "put_Completed");
+
+    for method in property.methods() {
+        assert_eq!(method.association(), HasSemantics::Property(property));
+    }
+
+    let def = reader
+        .get_type_def("Windows.Foundation", "IMemoryBufferReference")
+        .next()
+        .unwrap();
+
+    let property = def.properties().next().unwrap();
+    assert_eq!(property.name(), "Capacity");
+    assert_eq!(property.ty(&[]), Type::U32);
+    assert!(property.setter().is_none());
+}
+
+#[test]
+fn events() {
+    let files = tool_lib::default_metadata();
+    let reader = Reader::new(files);
+
+    let def = reader
+        .get_type_def("Windows.Foundation", "IMemoryBufferReference")
+        .next()
+        .unwrap();
+
+    let event = def.events().next().unwrap();
+    assert_eq!(event.name(), "Closed");
+    assert!(
+        matches!(event.ty(&[]), Type::TypeDef(def, generics) if def.name() == "TypedEventHandler" && generics.len() == 2)
+    );
+    assert_eq!(event.adder().unwrap().name(), "add_Closed");
+    assert_eq!(event.remover().unwrap().name(), "remove_Closed");
+    assert!(event.raiser().is_none());
+
+    // Win32 metadata has neither properties nor events.
+    let def = reader
+        .get_type_def("Windows.Win32.Foundation", "RECT")
+        .next()
+        .unwrap();
+
+    assert_eq!(def.properties().count(), 0);
+    assert_eq!(def.events().count(), 0);
+}
@@ -0,0 +1,63 @@
+use metadata::*;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const WDK: &[u8] = include_bytes!("../../../libs/bindgen/default/Windows.Wdk.winmd");
+
+// Tracks the bytes currently allocated so the test can tell whether dropping a reader releases its files.
+struct Counter;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counter = Counter;
+
+fn load() {
+    let reader = Reader::filter(
+        vec![File::new(WDK.to_vec()).unwrap()],
+        &["Windows.Wdk.Foundation"],
+        &[],
+        &Default::default(),
+    );
+
+    let def = reader
+        .get_type_def("Windows.Wdk.Foundation", "DRIVER_OBJECT")
+        .next()
+        .unwrap();
+
+    assert_eq!(def.kind(), TypeKind::Struct);
+    assert!(reader.items().count() > 0);
+}
+
+#[test]
+fn test() {
+    load();
+    let baseline = ALLOCATED.load(Ordering::SeqCst);
+
+    // Each reader owns a copy of the file, so anything it failed to release would add up quickly.
+    for _ in 0..10 {
+        load();
+    }
+
+    assert!(ALLOCATED.load(Ordering::SeqCst) < baseline + WDK.len());
+
+    // A borrowed file may be modified again once the reader that borrowed it is gone.
+    let mut bytes = WDK.to_vec();
+    let reader = Reader::new(vec![File::new(bytes.as_slice()).unwrap()]);
+    assert!(reader.items().count() > 0);
+    drop(reader);
+    bytes[0] = 0;
+    assert!(File::new(bytes).is_err());
+}
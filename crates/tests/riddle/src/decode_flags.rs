@@ -0,0 +1,122 @@
+#![allow(
+    non_snake_case,
+    non_upper_case_globals,
+    non_camel_case_types,
+    dead_code,
+    clippy::all
+)]
+#[repr(transparent)]
+#[derive(PartialEq, Eq, Copy, Clone, Default)]
+pub struct NTCREATEFILE_CREATE_OPTIONS(pub u32);
+impl windows_core::TypeKind for NTCREATEFILE_CREATE_OPTIONS {
+    type TypeKind = windows_core::CopyType;
+}
+impl NTCREATEFILE_CREATE_OPTIONS {
+    const NAMES: &'static [(&'static str, Self)] = &[
+        ("FILE_DIRECTORY_FILE", Self(1u32)),
+        ("FILE_NON_DIRECTORY_FILE", Self(64u32)),
+        ("FILE_WRITE_THROUGH", Self(2u32)),
+        ("FILE_SEQUENTIAL_ONLY", Self(4u32)),
+        ("FILE_RANDOM_ACCESS", Self(2048u32)),
+        ("FILE_NO_INTERMEDIATE_BUFFERING", Self(8u32)),
+        ("FILE_SYNCHRONOUS_IO_ALERT", Self(16u32)),
+        ("FILE_SYNCHRONOUS_IO_NONALERT", Self(32u32)),
+        ("FILE_CREATE_TREE_CONNECTION", Self(128u32)),
+        ("FILE_NO_EA_KNOWLEDGE", Self(512u32)),
+        ("FILE_OPEN_REPARSE_POINT", Self(2097152u32)),
+        ("FILE_DELETE_ON_CLOSE", Self(4096u32)),
+        ("FILE_OPEN_BY_FILE_ID", Self(8192u32)),
+        ("FILE_OPEN_FOR_BACKUP_INTENT", Self(16384u32)),
+        ("FILE_RESERVE_OPFILTER", Self(1048576u32)),
+        ("FILE_OPEN_REQUIRING_OPLOCK", Self(65536u32)),
+        ("FILE_COMPLETE_IF_OPLOCKED", Self(256u32)),
+        ("FILE_OPEN_FOR_FREE_SPACE_QUERY", Self(8388608u32)),
+        (
+            "FILE_CONTAINS_EXTENDED_CREATE_INFORMATION",
+            Self(268435456u32),
+        ),
+        ("FILE_NO_COMPRESSION", Self(32768u32)),
+        ("FILE_DISALLOW_EXCLUSIVE", Self(131072u32)),
+        ("FILE_SESSION_AWARE", Self(262144u32)),
+        ("FILE_OPEN_NO_RECALL", Self(4194304u32)),
+    ];
+    pub fn iter_names(&self) -> impl Iterator<Item = (&'static str, Self)> {
+        let bits = self.0;
+        let mut remaining = self.0;
+        Self::NAMES.iter().copied().filter(move |(_, value)| {
+            if value.0 != 0 && bits & value.0 == value.0 && remaining & value.0 != 0 {
+                remaining &= !value.0;
+                true
+            } else {
+                false
+            }
+        })
+    }
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .find(|(flag, _)| *flag == name)
+            .map(|(_, value)| *value)
+    }
+}
+impl core::fmt::Display for NTCREATEFILE_CREATE_OPTIONS {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.0 == 0 {
+            return match Self::NAMES.iter().find(|(_, value)| value.0 == 0) {
+                Some((name, _)) => f.write_str(name),
+                None => f.write_str("0"),
+            };
+        }
+        let mut remaining = self.0;
+        let mut separator = "";
+        for (name, value) in self.iter_names() {
+            f.write_str(separator)?;
+            f.write_str(name)?;
+            remaining &= !value.0;
+            separator = " | ";
+        }
+        if remaining != 0 {
+            f.write_str(separator)?;
+            write!(f, "{:#x}", remaining)?;
+        }
+        Ok(())
+    }
+}
+impl core::fmt::Debug for NTCREATEFILE_CREATE_OPTIONS {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(self, f)
+    }
+}
+impl NTCREATEFILE_CREATE_OPTIONS {
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+impl core::ops::BitOr for NTCREATEFILE_CREATE_OPTIONS {
+    type Output = Self;
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+impl core::ops::BitAnd for NTCREATEFILE_CREATE_OPTIONS {
+    type Output = Self;
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
+impl core::ops::BitOrAssign for NTCREATEFILE_CREATE_OPTIONS {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0.bitor_assign(other.0)
+    }
+}
+impl core::ops::BitAndAssign for NTCREATEFILE_CREATE_OPTIONS {
+    fn bitand_assign(&mut self, other: Self) {
+        self.0.bitand_assign(other.0)
+    }
+}
+impl core::ops::Not for NTCREATEFILE_CREATE_OPTIONS {
+    type Output = Self;
+    fn not(self) -> Self {
+        Self(self.0.not())
+    }
+}
@@ -0,0 +1,53 @@
+#![allow(
+    non_snake_case,
+    non_upper_case_globals,
+    non_camel_case_types,
+    dead_code,
+    clippy::all
+)]
+windows_core::imp::define_interface!(
+    #[doc = " Something that can be drawn."]
+    IShape,
+    IShape_Vtbl,
+    0
+);
+impl core::ops::Deref for IShape {
+    type Target = windows_core::IUnknown;
+    fn deref(&self) -> &Self::Target {
+        unsafe { core::mem::transmute(self) }
+    }
+}
+windows_core::imp::interface_hierarchy!(IShape, windows_core::IUnknown);
+impl IShape {
+    #[doc = " Draws the shape."]
+    pub unsafe fn Draw(&self, point: Point) -> windows_core::Result<()> {
+        (windows_core::Interface::vtable(self).Draw)(
+            windows_core::Interface::as_raw(self),
+            core::mem::transmute(point),
+        )
+        .ok()
+    }
+}
+#[repr(C)]
+pub struct IShape_Vtbl {
+    pub base__: windows_core::IUnknown_Vtbl,
+    pub Draw: unsafe extern "system" fn(*mut core::ffi::c_void, Point) -> windows_core::HRESULT,
+}
+#[doc = " A point."]
+#[doc = ""]
+#[doc = " More details."]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Point {
+    #[doc = " X coordinate."]
+    pub x: i32,
+    pub y: i32,
+}
+impl windows_core::TypeKind for Point {
+    type TypeKind = windows_core::CopyType;
+}
+impl Default for Point {
+    fn default() -> Self {
+        unsafe { core::mem::zeroed() }
+    }
+}
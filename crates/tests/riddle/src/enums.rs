@@ -0,0 +1,28 @@
+#![allow(
+    non_snake_case,
+    non_upper_case_globals,
+    non_camel_case_types,
+    dead_code,
+    clippy::all
+)]
+#[repr(transparent)]
+#[derive(PartialEq, Eq, Copy, Clone, Default)]
+pub struct Color(pub i32);
+impl Color {
+    pub const Red: Self = Self(0i32);
+    pub const Green: Self = Self(5i32);
+    pub const Blue: Self = Self(6i32);
+    pub const Minus: Self = Self(-1i32);
+}
+impl windows_core::TypeKind for Color {
+    type TypeKind = windows_core::CopyType;
+}
+impl core::fmt::Debug for Color {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Color").field(&self.0).finish()
+    }
+}
+impl windows_core::RuntimeType for Color {
+    const SIGNATURE: windows_core::imp::ConstBuffer =
+        windows_core::imp::ConstBuffer::from_slice(b"enum(Test.Color;i4)");
+}
@@ -1,14 +1,22 @@
 mod composition;
+pub mod decode_flags;
+mod docs;
+mod enums;
 mod generic_interfaces;
 mod module_attributes;
 mod nested_module;
 mod nested_struct;
+mod overrides;
 mod params;
+// The `raw-dylib` link kind is only supported when targeting Windows.
+#[cfg(windows)]
+mod raw_dylib;
+pub mod rust_enums;
 mod r#struct;
 mod win32_struct;
 mod winrt_struct;
 
-pub fn run_riddle(name: &str, dialect: &str, etc: &[&str]) -> Vec<windows_metadata::File> {
+pub fn run_riddle(name: &str, dialect: &str, etc: &[&str]) -> Vec<windows_metadata::File<'static>> {
     let rdl = format!("tests/{name}.rdl");
     let winmd = format!("tests/{name}.winmd");
     let rs = format!("src/{name}.rs");
@@ -69,7 +77,7 @@ pub fn run_riddle(name: &str, dialect: &str, etc: &[&str]) -> Vec<windows_metada
         std::fs::read(&winmd).unwrap_or_else(|e| panic!("Failed to read winmd: {winmd} : {e:?}"));
     files.push(
         windows_metadata::File::new(winmd_bytes)
-            .unwrap_or_else(|| panic!("failed to parse winmd: {winmd}")),
+            .unwrap_or_else(|e| panic!("failed to parse winmd: {winmd} : {e}")),
     );
     files
 }
@@ -0,0 +1,38 @@
+#![allow(
+    non_snake_case,
+    non_upper_case_globals,
+    non_camel_case_types,
+    dead_code,
+    clippy::all
+)]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+}
+impl windows_core::TypeKind for Position {
+    type TypeKind = windows_core::CopyType;
+}
+impl Default for Position {
+    fn default() -> Self {
+        unsafe { core::mem::zeroed() }
+    }
+}
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[must_use]
+pub struct Rect {
+    pub top_left: Position,
+    pub width: i32,
+    #[doc(hidden)]
+    pub height: i32,
+}
+impl windows_core::TypeKind for Rect {
+    type TypeKind = windows_core::CopyType;
+}
+impl Default for Rect {
+    fn default() -> Self {
+        unsafe { core::mem::zeroed() }
+    }
+}
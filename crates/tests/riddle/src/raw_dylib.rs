@@ -0,0 +1,60 @@
+#![allow(
+    non_snake_case,
+    non_upper_case_globals,
+    non_camel_case_types,
+    dead_code,
+    clippy::all
+)]
+#[cfg_attr(
+    target_arch = "x86",
+    link(
+        name = "fwpuclnt.dll",
+        kind = "raw-dylib",
+        modifiers = "+verbatim",
+        import_name_type = "undecorated"
+    )
+)]
+#[cfg_attr(
+    not(target_arch = "x86"),
+    link(name = "fwpuclnt.dll", kind = "raw-dylib", modifiers = "+verbatim")
+)]
+extern "system" {
+    pub fn FwpmFreeMemory0(p: *mut *mut core::ffi::c_void);
+}
+#[cfg_attr(
+    target_arch = "x86",
+    link(
+        name = "ndis.sys",
+        kind = "raw-dylib",
+        modifiers = "+verbatim",
+        import_name_type = "undecorated"
+    )
+)]
+#[cfg_attr(
+    not(target_arch = "x86"),
+    link(name = "ndis.sys", kind = "raw-dylib", modifiers = "+verbatim")
+)]
+extern "C" {
+    pub fn NdisWriteErrorLogEntry(
+        ndisadapterhandle: *const core::ffi::c_void,
+        errorcode: u32,
+        numberoferrorvalues: u32,
+        ...
+    );
+}
+#[cfg_attr(
+    target_arch = "x86",
+    link(
+        name = "ntoskrnl.exe",
+        kind = "raw-dylib",
+        modifiers = "+verbatim",
+        import_name_type = "undecorated"
+    )
+)]
+#[cfg_attr(
+    not(target_arch = "x86"),
+    link(name = "ntoskrnl.exe", kind = "raw-dylib", modifiers = "+verbatim")
+)]
+extern "system" {
+    pub fn FsRtlIncrementCcFastReadWait();
+}
@@ -0,0 +1,48 @@
+#![allow(
+    non_snake_case,
+    non_upper_case_globals,
+    non_camel_case_types,
+    dead_code,
+    clippy::all
+)]
+#[repr(transparent)]
+#[derive(PartialEq, Eq, Copy, Clone, Default)]
+pub struct D3DDDI_ROTATION(pub i32);
+impl windows_core::TypeKind for D3DDDI_ROTATION {
+    type TypeKind = windows_core::CopyType;
+}
+impl core::fmt::Debug for D3DDDI_ROTATION {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("D3DDDI_ROTATION").field(&self.0).finish()
+    }
+}
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum D3DDDI_ROTATIONEnum {
+    D3DDDI_ROTATION_IDENTITY,
+    D3DDDI_ROTATION_90,
+    D3DDDI_ROTATION_180,
+    D3DDDI_ROTATION_270,
+}
+impl core::convert::TryFrom<D3DDDI_ROTATION> for D3DDDI_ROTATIONEnum {
+    type Error = D3DDDI_ROTATION;
+    fn try_from(value: D3DDDI_ROTATION) -> Result<Self, Self::Error> {
+        match value {
+            D3DDDI_ROTATION(1i32) => Ok(Self::D3DDDI_ROTATION_IDENTITY),
+            D3DDDI_ROTATION(2i32) => Ok(Self::D3DDDI_ROTATION_90),
+            D3DDDI_ROTATION(3i32) => Ok(Self::D3DDDI_ROTATION_180),
+            D3DDDI_ROTATION(4i32) => Ok(Self::D3DDDI_ROTATION_270),
+            _ => Err(value),
+        }
+    }
+}
+impl From<D3DDDI_ROTATIONEnum> for D3DDDI_ROTATION {
+    fn from(value: D3DDDI_ROTATIONEnum) -> Self {
+        match value {
+            D3DDDI_ROTATIONEnum::D3DDDI_ROTATION_IDENTITY => Self(1i32),
+            D3DDDI_ROTATIONEnum::D3DDDI_ROTATION_90 => Self(2i32),
+            D3DDDI_ROTATIONEnum::D3DDDI_ROTATION_180 => Self(3i32),
+            D3DDDI_ROTATIONEnum::D3DDDI_ROTATION_270 => Self(4i32),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use windows_bindgen::Bindgen;
+
+#[test]
+fn generate() {
+    let bindings = Bindgen::new()
+        .input("tests/builder.rdl")
+        .filter("Test")
+        .exclude("Test.Size")
+        .sys(true)
+        .no_bindgen_comment(true)
+        .generate()
+        .unwrap();
+
+    assert!(bindings.contains("pub struct Point {"));
+    assert!(!bindings.contains("Size"));
+    assert!(!bindings.contains("windows_core"));
+}
+
+#[test]
+fn write() {
+    let rs = std::env::temp_dir().join("riddle_builder.rs");
+    let rs = rs.to_str().unwrap();
+
+    let builder = Bindgen::new()
+        .input("tests/builder.rdl")
+        .filter("Test")
+        .no_bindgen_comment(true);
+
+    builder.clone().output(rs).write().unwrap();
+
+    let output = std::fs::read_to_string(rs)
+        .unwrap_or_else(|e| panic!("Failed to read output: {rs} : {e:?}"));
+
+    assert_eq!(output, builder.generate().unwrap());
+
+    // The builder is equivalent to the command line options.
+    assert_eq!(
+        builder.args(),
+        [
+            "--in",
+            "tests/builder.rdl",
+            "--filter",
+            "Test",
+            "--config",
+            "no-bindgen-comment"
+        ]
+    );
+}
+
+#[test]
+fn tokens() {
+    let tokens = Bindgen::new()
+        .input("tests/builder.rdl")
+        .filter("Test.Point")
+        .flatten(true)
+        .no_inner_attributes(true)
+        .tokens()
+        .unwrap()
+        .to_string();
+
+    assert!(tokens.contains("pub struct Point"));
+    assert!(!tokens.contains("#!"));
+}
+
+#[test]
+fn errors() {
+    let error = Bindgen::new()
+        .input("tests/builder.rdl")
+        .generate()
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "error: at least one `--filter` must be specified\n"
+    );
+
+    let error = Bindgen::new()
+        .input("tests/builder.rdl")
+        .filter("Test")
+        .package(true)
+        .generate()
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "error: the `package` configuration value requires an output directory\n"
+    );
+
+    // Disabling an option removes it again.
+    let builder = Bindgen::new().sys(true).sys(false);
+    assert!(builder.args().is_empty());
+}
@@ -0,0 +1,49 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use windows_bindgen::Bindgen;
+
+// Tracks the bytes currently allocated so the test can tell whether generating bindings leaks the metadata.
+struct Counter;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counter = Counter;
+
+fn generate() {
+    let bindings = Bindgen::new()
+        .input("tests/builder.rdl")
+        .filter("Test")
+        .generate()
+        .unwrap();
+
+    assert!(bindings.contains("pub struct Point {"));
+}
+
+// The `windows_bindings!` macro generates bindings many times within a long-lived proc macro host, so each call
+// must release the metadata it reads.
+#[test]
+fn test() {
+    generate();
+    let baseline = ALLOCATED.load(Ordering::SeqCst);
+
+    for _ in 0..10 {
+        generate();
+    }
+
+    // Parsing RDL keeps a little source information for spans, but far less than a leaked reader and its index.
+    let growth = ALLOCATED.load(Ordering::SeqCst).saturating_sub(baseline);
+    assert!(growth < 16 * 1024, "{growth}");
+}
@@ -0,0 +1,100 @@
+#ifndef C_HEADER_H_
+#define C_HEADER_H_
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#ifndef GUID_DEFINED
+#define GUID_DEFINED
+typedef struct _GUID {
+    uint32_t Data1;
+    uint16_t Data2;
+    uint16_t Data3;
+    uint8_t Data4[8];
+} GUID;
+#endif
+
+#ifndef _HRESULT_DEFINED
+#define _HRESULT_DEFINED
+typedef int32_t HRESULT;
+#endif
+
+#ifndef __HSTRING__
+#define __HSTRING__
+typedef struct HSTRING__* HSTRING;
+#endif
+
+#ifndef RIDDLE_STRING_TYPES_DEFINED
+#define RIDDLE_STRING_TYPES_DEFINED
+typedef char* PSTR;
+typedef const char* PCSTR;
+typedef uint16_t* PWSTR;
+typedef const uint16_t* PCWSTR;
+typedef uint16_t* BSTR;
+#endif
+
+#ifndef __IUnknown_INTERFACE_DEFINED__
+#define __IUnknown_INTERFACE_DEFINED__
+typedef struct IUnknown IUnknown;
+typedef struct IUnknownVtbl {
+    HRESULT (__stdcall* QueryInterface)(IUnknown* This, const GUID* riid, void** ppvObject);
+    uint32_t (__stdcall* AddRef)(IUnknown* This);
+    uint32_t (__stdcall* Release)(IUnknown* This);
+} IUnknownVtbl;
+struct IUnknown {
+    const IUnknownVtbl* lpVtbl;
+};
+#endif
+
+#ifndef __IInspectable_INTERFACE_DEFINED__
+#define __IInspectable_INTERFACE_DEFINED__
+typedef struct IInspectable IInspectable;
+typedef struct IInspectableVtbl {
+    HRESULT (__stdcall* QueryInterface)(IInspectable* This, const GUID* riid, void** ppvObject);
+    uint32_t (__stdcall* AddRef)(IInspectable* This);
+    uint32_t (__stdcall* Release)(IInspectable* This);
+    HRESULT (__stdcall* GetIids)(IInspectable* This, uint32_t* iidCount, GUID** iids);
+    HRESULT (__stdcall* GetRuntimeClassName)(IInspectable* This, HSTRING* className);
+    HRESULT (__stdcall* GetTrustLevel)(IInspectable* This, int32_t* trustLevel);
+} IInspectableVtbl;
+struct IInspectable {
+    const IInspectableVtbl* lpVtbl;
+};
+#endif
+
+typedef struct Buffer Buffer;
+typedef struct Size Size;
+typedef struct IThing IThing;
+
+struct Buffer {
+    uint8_t data[16];
+    uint32_t len;
+};
+
+struct Size {
+    int32_t width;
+    int32_t height;
+};
+
+typedef struct IThingVtbl {
+    HRESULT (__stdcall* QueryInterface)(IThing* This, const GUID* riid, void** ppvObject);
+    uint32_t (__stdcall* AddRef)(IThing* This);
+    uint32_t (__stdcall* Release)(IThing* This);
+    HRESULT (__stdcall* Query)(IThing* This, GUID* id, int32_t* value);
+    Size* (__stdcall* Extent)(IThing* This, Size* result);
+    uint32_t (__stdcall* Name)(IThing* This, PWSTR value);
+} IThingVtbl;
+struct IThing {
+    const IThingVtbl* lpVtbl;
+};
+
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // C_HEADER_H_
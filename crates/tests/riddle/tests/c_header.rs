@@ -0,0 +1,82 @@
+fn golden(input: &str, h: &str, filter: &[&str]) {
+    let before =
+        std::fs::read_to_string(h).unwrap_or_else(|e| panic!("Failed to read input: {h} : {e:?}"));
+
+    let mut args = vec!["--in", input, "--out", h, "--filter"];
+    args.extend_from_slice(filter);
+    args.extend_from_slice(&["--config", "no-bindgen-comment"]);
+
+    windows_bindgen::bindgen(args)
+        .unwrap_or_else(|e| panic!("Failed to run bindgen: {input} : {e:?}"));
+
+    let after =
+        std::fs::read_to_string(h).unwrap_or_else(|e| panic!("Failed to read output: {h} : {e:?}"));
+
+    assert_eq!(before, after);
+}
+
+#[test]
+fn interface() {
+    golden("tests/c_header.rdl", "tests/c_header.h", &["Test"]);
+}
+
+#[test]
+fn wdk() {
+    golden(
+        "../../libs/bindgen/default/Windows.Wdk.winmd",
+        "tests/c_header_wdk.h",
+        &[
+            "Windows.Wdk.Foundation.POOL_TYPE",
+            "Windows.Wdk.Foundation.NonPagedPool",
+            "Windows.Wdk.Foundation.NTSTRSAFE_MAX_LENGTH",
+            "Windows.Wdk.Graphics.Direct3D.D3DKMDT_FREQUENCY_RANGE",
+            "Windows.Wdk.Graphics.Direct3D.DXGK_BRIGHTNESS_CAPS",
+            "Windows.Wdk.System.SystemServices.DbgPrint",
+        ],
+    );
+}
+
+#[test]
+fn undefined() {
+    let error = windows_bindgen::bindgen([
+        "--in",
+        "../../libs/bindgen/default/Windows.Wdk.winmd",
+        "--out",
+        "tests/c_header_undefined.h",
+        "--filter",
+        "Windows.Wdk.Foundation.NtClose",
+    ])
+    .unwrap_err();
+
+    // `NtClose` depends on types defined by the Win32 metadata, which isn't part of the input.
+    assert_eq!(
+        error.to_string(),
+        "error: type `Windows.Win32.Foundation.NTSTATUS` is not defined by the input metadata\n"
+    );
+}
+
+#[test]
+fn arch() {
+    let h = std::env::temp_dir().join("riddle_c_header_arch.h");
+    let h = h.to_str().unwrap();
+
+    windows_bindgen::bindgen([
+        "--out",
+        h,
+        "--filter",
+        "Windows.Win32.Networking.WinSock.WSADATA",
+        "Windows.Win32.UI.WindowsAndMessaging.GetWindowLongPtrW",
+    ])
+    .unwrap_or_else(|e| panic!("Failed to run bindgen: {h} : {e:?}"));
+
+    let output =
+        std::fs::read_to_string(h).unwrap_or_else(|e| panic!("Failed to read output: {h} : {e:?}"));
+
+    // `WSADATA` has a different layout on x86 whereas `GetWindowLongPtrW` is only exported on 64-bit targets.
+    assert!(output.contains("#if defined(_M_IX86)\nstruct WSADATA {"));
+    assert!(output.contains("#if defined(_M_AMD64) || defined(_M_ARM64)\nstruct WSADATA {"));
+    assert_eq!(output.matches("typedef struct WSADATA WSADATA;").count(), 1);
+    assert!(output.contains(
+        "#if defined(_M_AMD64) || defined(_M_ARM64)\nintptr_t __stdcall GetWindowLongPtrW("
+    ));
+}
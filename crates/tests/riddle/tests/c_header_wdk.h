@@ -0,0 +1,115 @@
+#ifndef C_HEADER_WDK_H_
+#define C_HEADER_WDK_H_
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#ifndef GUID_DEFINED
+#define GUID_DEFINED
+typedef struct _GUID {
+    uint32_t Data1;
+    uint16_t Data2;
+    uint16_t Data3;
+    uint8_t Data4[8];
+} GUID;
+#endif
+
+#ifndef _HRESULT_DEFINED
+#define _HRESULT_DEFINED
+typedef int32_t HRESULT;
+#endif
+
+#ifndef __HSTRING__
+#define __HSTRING__
+typedef struct HSTRING__* HSTRING;
+#endif
+
+#ifndef RIDDLE_STRING_TYPES_DEFINED
+#define RIDDLE_STRING_TYPES_DEFINED
+typedef char* PSTR;
+typedef const char* PCSTR;
+typedef uint16_t* PWSTR;
+typedef const uint16_t* PCWSTR;
+typedef uint16_t* BSTR;
+#endif
+
+#ifndef __IUnknown_INTERFACE_DEFINED__
+#define __IUnknown_INTERFACE_DEFINED__
+typedef struct IUnknown IUnknown;
+typedef struct IUnknownVtbl {
+    HRESULT (__stdcall* QueryInterface)(IUnknown* This, const GUID* riid, void** ppvObject);
+    uint32_t (__stdcall* AddRef)(IUnknown* This);
+    uint32_t (__stdcall* Release)(IUnknown* This);
+} IUnknownVtbl;
+struct IUnknown {
+    const IUnknownVtbl* lpVtbl;
+};
+#endif
+
+#ifndef __IInspectable_INTERFACE_DEFINED__
+#define __IInspectable_INTERFACE_DEFINED__
+typedef struct IInspectable IInspectable;
+typedef struct IInspectableVtbl {
+    HRESULT (__stdcall* QueryInterface)(IInspectable* This, const GUID* riid, void** ppvObject);
+    uint32_t (__stdcall* AddRef)(IInspectable* This);
+    uint32_t (__stdcall* Release)(IInspectable* This);
+    HRESULT (__stdcall* GetIids)(IInspectable* This, uint32_t* iidCount, GUID** iids);
+    HRESULT (__stdcall* GetRuntimeClassName)(IInspectable* This, HSTRING* className);
+    HRESULT (__stdcall* GetTrustLevel)(IInspectable* This, int32_t* trustLevel);
+} IInspectableVtbl;
+struct IInspectable {
+    const IInspectableVtbl* lpVtbl;
+};
+#endif
+
+typedef struct D3DKMDT_FREQUENCY_RANGE D3DKMDT_FREQUENCY_RANGE;
+typedef struct DXGK_BRIGHTNESS_CAPS DXGK_BRIGHTNESS_CAPS;
+typedef struct D3DDDI_RATIONAL D3DDDI_RATIONAL;
+
+typedef int32_t POOL_TYPE;
+
+struct D3DDDI_RATIONAL {
+    uint32_t Numerator;
+    uint32_t Denominator;
+};
+
+struct D3DKMDT_FREQUENCY_RANGE {
+    D3DDDI_RATIONAL MinVSyncFreq;
+    D3DDDI_RATIONAL MaxVSyncFreq;
+    D3DDDI_RATIONAL MinHSyncFreq;
+    D3DDDI_RATIONAL MaxHSyncFreq;
+};
+
+typedef union DXGK_BRIGHTNESS_CAPS_0 DXGK_BRIGHTNESS_CAPS_0;
+typedef struct DXGK_BRIGHTNESS_CAPS_0_0 DXGK_BRIGHTNESS_CAPS_0_0;
+#pragma pack(push, 1)
+struct DXGK_BRIGHTNESS_CAPS_0_0 {
+    uint32_t _bitfield;
+};
+#pragma pack(pop)
+
+#pragma pack(push, 1)
+union DXGK_BRIGHTNESS_CAPS_0 {
+    DXGK_BRIGHTNESS_CAPS_0_0 Anonymous;
+    uint32_t Value;
+};
+#pragma pack(pop)
+
+struct DXGK_BRIGHTNESS_CAPS {
+    DXGK_BRIGHTNESS_CAPS_0 Anonymous;
+};
+
+uint32_t __cdecl DbgPrint(PCSTR Format, ...);
+
+#define NTSTRSAFE_MAX_LENGTH 2147483646U
+#define NonPagedPool ((POOL_TYPE)0)
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // C_HEADER_WDK_H_
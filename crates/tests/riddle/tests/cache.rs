@@ -0,0 +1,108 @@
+#[test]
+fn test() {
+    let rdl = "tests/cache.rdl";
+    let winmd = "tests/cache.winmd";
+    let cache = "tests/cache.hash";
+
+    _ = std::fs::remove_file(winmd);
+    _ = std::fs::remove_file(cache);
+
+    let args = [
+        "--in", rdl, "--out", winmd, "--filter", "Test", "--cache", cache,
+    ];
+
+    let message = windows_bindgen::bindgen(args)
+        .unwrap_or_else(|e| panic!("Failed to run bindgen: {rdl} : {e:?}"));
+
+    assert!(message.starts_with("  Finished writing"));
+
+    let message = windows_bindgen::bindgen(args)
+        .unwrap_or_else(|e| panic!("Failed to run bindgen: {rdl} : {e:?}"));
+
+    assert!(message.starts_with("  Skipped writing"));
+
+    // Any change to the arguments invalidates the cache.
+    let message = windows_bindgen::bindgen([
+        "--in",
+        rdl,
+        "--out",
+        winmd,
+        "--filter",
+        "Test.Point",
+        "--cache",
+        cache,
+    ])
+    .unwrap_or_else(|e| panic!("Failed to run bindgen: {rdl} : {e:?}"));
+
+    assert!(message.starts_with("  Finished writing"));
+}
+
+#[test]
+fn invalidate() {
+    let dir = std::env::temp_dir().join("riddle_cache");
+    let winmd = std::env::temp_dir().join("riddle_cache.winmd");
+    let cache = std::env::temp_dir().join("riddle_cache.hash");
+
+    _ = std::fs::remove_dir_all(&dir);
+    _ = std::fs::remove_file(&winmd);
+    _ = std::fs::remove_file(&cache);
+
+    // The input is a directory so that the hash also covers files listed from a directory.
+    std::fs::create_dir(&dir).unwrap();
+    let rdl = dir.join("cache.rdl");
+    std::fs::copy("tests/cache.rdl", &rdl).unwrap();
+
+    let args = [
+        "--in",
+        dir.to_str().unwrap(),
+        "--out",
+        winmd.to_str().unwrap(),
+        "--filter",
+        "Test",
+        "--cache",
+        cache.to_str().unwrap(),
+    ];
+
+    let message = windows_bindgen::bindgen(args).unwrap();
+    assert!(message.starts_with("  Finished writing"));
+    let modified = std::fs::metadata(&winmd).unwrap().modified().unwrap();
+
+    // A cache hit leaves the output alone.
+    let message = windows_bindgen::bindgen(args).unwrap();
+    assert!(message.starts_with("  Skipped writing"));
+    assert_eq!(
+        std::fs::metadata(&winmd).unwrap().modified().unwrap(),
+        modified
+    );
+
+    // Any change to the contents of an input invalidates the cache.
+    let source = std::fs::read_to_string(&rdl).unwrap();
+    std::fs::write(&rdl, source.replace("y: i32", "y: i64")).unwrap();
+
+    let message = windows_bindgen::bindgen(args).unwrap();
+    assert!(message.starts_with("  Finished writing"));
+}
+
+#[test]
+fn inputs() {
+    let inputs = windows_bindgen::inputs([
+        "--in",
+        "tests/cache.rdl",
+        "tests/multi_file",
+        "--out",
+        "tests/inputs.winmd",
+        "--etc",
+        "tests/config_file/valid.toml",
+    ])
+    .unwrap();
+
+    assert_eq!(
+        inputs,
+        [
+            "tests/config_file/valid.toml",
+            "tests/cache.rdl",
+            "tests/multi_file",
+            "tests/config_file/test.rdl"
+        ]
+    );
+}
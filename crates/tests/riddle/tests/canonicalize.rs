@@ -0,0 +1,43 @@
+use windows_metadata::*;
+
+#[test]
+fn test() {
+    let rdl = "tests/canonicalize.rdl";
+    let winmd = "tests/canonicalize.winmd";
+
+    windows_bindgen::bindgen(["--in", rdl, "--out", winmd, "--filter", "Test"])
+        .unwrap_or_else(|e| panic!("Failed to run bindgen: {rdl} : {e:?}"));
+
+    let mut files = tool_lib::default_metadata();
+    let bytes =
+        std::fs::read(winmd).unwrap_or_else(|e| panic!("Failed to read winmd: {winmd} : {e:?}"));
+    files.push(File::new(bytes).unwrap_or_else(|e| panic!("failed to parse winmd: {winmd} : {e}")));
+    let reader = Reader::new(files);
+
+    let def = reader
+        .get_type_def("Test.Nested", "Rect")
+        .next()
+        .expect("Type missing");
+
+    let fields: Vec<Field> = def.fields().collect();
+    assert_eq!(fields.len(), 2);
+    assert_eq!(type_name(fields[0].ty(None)), ("Test", "Point"));
+    assert_eq!(type_name(fields[1].ty(None)), ("Test", "Size"));
+
+    let def = reader
+        .get_type_def("Test", "Shape")
+        .next()
+        .expect("Type missing");
+
+    let fields: Vec<Field> = def.fields().collect();
+    assert_eq!(fields.len(), 1);
+    assert_eq!(type_name(fields[0].ty(None)), ("Test.Nested", "Rect"));
+}
+
+fn type_name(ty: Type<'_>) -> (&str, &str) {
+    let Type::TypeDef(def, _) = ty else {
+        panic!("type expected")
+    };
+
+    (def.namespace(), def.name())
+}
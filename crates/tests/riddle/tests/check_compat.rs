@@ -0,0 +1,33 @@
+#[test]
+fn compatible() {
+    windows_bindgen::bindgen([
+        "--in",
+        "tests/compat/v2.rdl",
+        "--check-compat",
+        "tests/compat/v1.rdl",
+    ])
+    .unwrap_or_else(|e| panic!("Failed to run bindgen: {e}"));
+}
+
+#[test]
+fn incompatible() {
+    let error = windows_bindgen::bindgen([
+        "--in",
+        "tests/compat/v3.rdl",
+        "--check-compat",
+        "tests/compat/v2.rdl",
+    ])
+    .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "error: method `Test.IShape.Perimeter` was added
+
+error: field `Test.Point.z` was added
+
+error: interface `Test.IShape` was removed from `Test.Shape`
+
+error: struct `Test.Size` was removed
+"
+    );
+}
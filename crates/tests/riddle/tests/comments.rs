@@ -0,0 +1,39 @@
+#[test]
+fn test() {
+    let rdl = "tests/comments.rdl";
+
+    let before = std::fs::read_to_string(rdl)
+        .unwrap_or_else(|e| panic!("Failed to read input: {rdl} : {e:?}"));
+
+    windows_bindgen::bindgen(["--in", rdl, "--format"])
+        .unwrap_or_else(|e| panic!("Failed to format: {rdl} : {e:?}"));
+
+    let after = std::fs::read_to_string(rdl)
+        .unwrap_or_else(|e| panic!("Failed to read output: {rdl} : {e:?}"));
+
+    assert_eq!(before, after);
+}
+
+#[test]
+fn single_line() {
+    let rdl = std::env::temp_dir().join("riddle_comments.rdl");
+    let rdl = rdl.to_str().unwrap();
+
+    std::fs::write(
+        rdl,
+        "#![winrt]\n\nmod Test {\n    struct S { s: String, /* c */ c: u16 } // x\n}\n",
+    )
+    .unwrap_or_else(|e| panic!("Failed to write input: {rdl} : {e:?}"));
+
+    windows_bindgen::bindgen(["--in", rdl, "--format"])
+        .unwrap_or_else(|e| panic!("Failed to format: {rdl} : {e:?}"));
+
+    let after = std::fs::read_to_string(rdl)
+        .unwrap_or_else(|e| panic!("Failed to read output: {rdl} : {e:?}"));
+
+    // Comments stay with the field or item they follow even though the struct is now written across several lines.
+    assert_eq!(
+        after,
+        "#![winrt]\n\nmod Test {\n    struct S {\n        s: String, /* c */\n        c: u16,\n    } // x\n}\n"
+    );
+}
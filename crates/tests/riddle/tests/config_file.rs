@@ -0,0 +1,53 @@
+#[test]
+fn valid() {
+    windows_bindgen::bindgen(["--etc", "tests/config_file/valid.toml"])
+        .unwrap_or_else(|e| panic!("Failed to run bindgen: {e}"));
+
+    windows_bindgen::bindgen([
+        "--in",
+        "tests/config_file/test.rdl",
+        "--out",
+        "tests/config_file/args.rs",
+        "--filter",
+        "Test.Point",
+        "--config",
+        "flatten",
+        "sys",
+    ])
+    .unwrap_or_else(|e| panic!("Failed to run bindgen: {e}"));
+
+    let toml = std::fs::read_to_string("tests/config_file/toml.rs").unwrap();
+    let args = std::fs::read_to_string("tests/config_file/args.rs").unwrap();
+
+    assert!(toml.contains("pub struct Point"));
+    assert!(!toml.contains("pub struct Size"));
+    assert_eq!(toml, args);
+}
+
+#[test]
+fn unknown_option() {
+    let error = windows_bindgen::bindgen(["--etc", "tests/config_file/unknown_option.toml"])
+        .unwrap_err()
+        .to_string();
+
+    assert!(error.starts_with("error: unknown field `format`"));
+    assert!(error.contains("  --> tests/config_file/unknown_option.toml:5:1\n"));
+    assert!(error.ends_with("5 | format = true\n  | ^^^^^^\n"));
+}
+
+#[test]
+fn invalid_type() {
+    let error = windows_bindgen::bindgen(["--etc", "tests/config_file/invalid_type.toml"])
+        .unwrap_err()
+        .to_string();
+
+    assert_eq!(
+        error,
+        "error: overrides require a fully qualified name
+  --> tests/config_file/invalid_type.toml:3:8
+  |
+3 | [types.Point]
+  |        ^^^^^
+"
+    );
+}
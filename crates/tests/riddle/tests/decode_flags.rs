@@ -0,0 +1,44 @@
+use test_riddle::decode_flags::NTCREATEFILE_CREATE_OPTIONS;
+
+#[test]
+fn test() {
+    let rs = "src/decode_flags.rs";
+
+    windows_bindgen::bindgen([
+        "--out",
+        rs,
+        "--filter",
+        "Windows.Wdk.Storage.FileSystem.NTCREATEFILE_CREATE_OPTIONS",
+        "--config",
+        "flatten",
+        "no-bindgen-comment",
+        "decode-flags",
+    ])
+    .unwrap_or_else(|e| panic!("Failed to run bindgen: {rs} : {e:?}"));
+
+    let output = std::fs::read_to_string(rs)
+        .unwrap_or_else(|e| panic!("Failed to read output: {rs} : {e:?}"));
+
+    assert!(output.contains("pub fn iter_names(&self)"));
+    assert!(output.contains("impl core::fmt::Display for NTCREATEFILE_CREATE_OPTIONS"));
+}
+
+#[test]
+fn format() {
+    let directory = NTCREATEFILE_CREATE_OPTIONS::from_name("FILE_DIRECTORY_FILE").unwrap();
+    let write_through = NTCREATEFILE_CREATE_OPTIONS::from_name("FILE_WRITE_THROUGH").unwrap();
+    let options = directory | write_through | NTCREATEFILE_CREATE_OPTIONS(0x8000_0000);
+
+    assert_eq!(
+        format!("{options:?}"),
+        "FILE_DIRECTORY_FILE | FILE_WRITE_THROUGH | 0x80000000"
+    );
+    assert_eq!(options.to_string(), format!("{options:?}"));
+    assert_eq!(format!("{:?}", NTCREATEFILE_CREATE_OPTIONS(0)), "0");
+
+    assert!(options.contains(directory));
+    assert!(NTCREATEFILE_CREATE_OPTIONS::from_name("FILE_UNKNOWN").is_none());
+
+    let names: Vec<&str> = options.iter_names().map(|(name, _)| name).collect();
+    assert_eq!(names, ["FILE_DIRECTORY_FILE", "FILE_WRITE_THROUGH"]);
+}
@@ -0,0 +1,122 @@
+#[test]
+fn syntax_errors() {
+    let rdl = "tests/syntax_errors.rdl";
+
+    let error = windows_bindgen::bindgen(["--in", rdl, "--format"])
+        .expect_err("Expected syntax errors")
+        .to_string();
+
+    assert_eq!(error.matches("error:").count(), 3, "{error}");
+
+    assert!(error.contains(
+        r#"error: expected `,`
+  --> "#
+    ));
+
+    assert!(error.contains(
+        r#"syntax_errors.rdl:6:9
+  |
+6 |         y: i32,
+  |         ^
+"#
+    ));
+
+    assert!(error.contains(
+        r#"error: unnamed fields not supported
+  --> "#
+    ));
+
+    assert!(error.contains(
+        r#"syntax_errors.rdl:10:18
+   |
+10 |         fn Bar(a i32);
+   |                  ^^^
+"#
+    ));
+}
+
+#[test]
+fn semantic_errors() {
+    let rdl = "tests/semantic_errors.rdl";
+    let winmd = "tests/semantic_errors.winmd";
+
+    let error = windows_bindgen::bindgen(["--in", rdl, "--out", winmd, "--filter", "Test"])
+        .expect_err("Expected semantic errors")
+        .to_string();
+
+    assert_eq!(error.matches("error:").count(), 6, "{error}");
+
+    assert!(error.contains(
+        r#"semantic_errors.rdl:4:5
+  |
+4 |     #[deprecated]
+  |     ^^^^^^^^^^^^^
+"#
+    ));
+
+    for message in [
+        "error: unsupported attribute `deprecated`",
+        "error: duplicate field `x`",
+        "error: unresolved type `Unknown`",
+        "error: duplicate definition of `Test.Point`",
+        "error: `self` parameters not supported",
+        "error: too many leading `super` keywords",
+    ] {
+        assert!(error.contains(message), "{message}");
+    }
+}
+
+#[test]
+fn resolve_errors() {
+    let rdl = "tests/resolve_errors.rdl";
+    let winmd = "tests/resolve_errors.winmd";
+
+    let error = windows_bindgen::bindgen(["--in", rdl, "--out", winmd, "--filter", "Test"])
+        .expect_err("Expected resolve errors")
+        .to_string();
+
+    assert_eq!(error.matches("error:").count(), 4, "{error}");
+
+    for message in [
+        "error: glob imports are not supported",
+        "error: unresolved type `super::Pointt`",
+        "error: unresolved type `Nested::Lines`",
+        "error: unresolved type `Size`",
+    ] {
+        assert!(error.contains(message), "{message}");
+    }
+}
+
+#[test]
+fn multi_file_errors() {
+    let winmd = "tests/multi_file_errors.winmd";
+
+    let error = windows_bindgen::bindgen([
+        "--in",
+        "tests/multi_file",
+        "tests/multi_file_errors.rdl",
+        "--out",
+        winmd,
+        "--filter",
+        "Test",
+    ])
+    .expect_err("Expected errors")
+    .to_string();
+
+    assert_eq!(error.matches("error:").count(), 2, "{error}");
+
+    assert!(error.contains(
+        r#"multi_file_errors.rdl:4:5
+  |
+4 |     struct Point {
+  |     ^^^^^^^^^^^^^^
+"#
+    ));
+
+    for message in [
+        "error: duplicate definition of `Test.Point`",
+        "error: unresolved type `Unknown`",
+    ] {
+        assert!(error.contains(message), "{message}");
+    }
+}
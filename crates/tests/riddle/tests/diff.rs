@@ -0,0 +1,31 @@
+#[test]
+fn test() {
+    let old = "tests/diff/old.rdl";
+    let new = "tests/diff/new.rdl";
+    let expected = "tests/diff/expected.json";
+
+    let json = windows_bindgen::bindgen(["--in", old, "--diff", new, "--config", "classify"])
+        .unwrap_or_else(|e| panic!("Failed to run bindgen: {new} : {e:?}"));
+
+    assert_eq!(
+        json,
+        std::fs::read_to_string(expected)
+            .unwrap_or_else(|e| panic!("Failed to read expected: {expected} : {e:?}"))
+    );
+
+    // Changes are only classified on request.
+    let json = windows_bindgen::bindgen(["--in", old, "--diff", new])
+        .unwrap_or_else(|e| panic!("Failed to run bindgen: {new} : {e:?}"));
+
+    assert!(!json.contains("breaking"));
+}
+
+#[test]
+fn unchanged() {
+    let old = "tests/diff/old.rdl";
+
+    let json = windows_bindgen::bindgen(["--in", old, "--diff", old, "--config", "classify"])
+        .unwrap_or_else(|e| panic!("Failed to run bindgen: {old} : {e:?}"));
+
+    assert_eq!(json, "{\n  \"breaking\": false,\n  \"namespaces\": {}\n}");
+}
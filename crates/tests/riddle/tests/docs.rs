@@ -0,0 +1,28 @@
+#[test]
+fn test() {
+    let rdl = "tests/docs.rdl";
+    let rs = "src/docs.rs";
+
+    windows_bindgen::bindgen([
+        "--in",
+        rdl,
+        "--out",
+        rs,
+        "--filter",
+        "Test",
+        "--config",
+        "docs",
+        "no-bindgen-comment",
+    ])
+    .unwrap_or_else(|e| panic!("Failed to run bindgen: {rdl} : {e:?}"));
+
+    let output = std::fs::read_to_string(rs)
+        .unwrap_or_else(|e| panic!("Failed to read output: {rs} : {e:?}"));
+
+    assert!(output.contains("#[doc = \" A point.\"]\n#[doc = \"\"]\n#[doc = \" More details.\"]"));
+    assert!(output.contains("#[doc = \" X coordinate.\"]\n    pub x: i32,"));
+    assert!(output.contains(
+        "define_interface!(\n    #[doc = \" Something that can be drawn.\"]\n    IShape,"
+    ));
+    assert!(output.contains("#[doc = \" Draws the shape.\"]\n    pub unsafe fn Draw"));
+}
@@ -0,0 +1,44 @@
+use test_riddle::run_riddle;
+use windows_metadata::*;
+
+#[test]
+fn test() {
+    let files = run_riddle("enums", "winrt", &[]);
+    let reader = Reader::new(files);
+
+    let def = reader.get_type_def("Test", "Color").next().unwrap();
+    assert_eq!(def.kind(), TypeKind::Enum);
+
+    let fields: Vec<Field> = def.fields().collect();
+    assert_eq!(fields.len(), 5);
+    assert_eq!(fields[0].name(), "value__");
+    assert!(matches!(fields[0].ty(Some(def)), Type::I32));
+
+    let variants: Vec<(&str, Value)> = fields[1..]
+        .iter()
+        .map(|field| (field.name(), field.constant().unwrap().value()))
+        .collect();
+
+    assert!(matches!(
+        variants[..],
+        [
+            ("Red", Value::I32(0)),
+            ("Green", Value::I32(5)),
+            ("Blue", Value::I32(6)),
+            ("Minus", Value::I32(-1)),
+        ]
+    ));
+
+    // Each doc comment refers to the same `DocumentationAttribute` constructor.
+    let constructors: Vec<MemberRef> = def
+        .attributes()
+        .chain(fields.iter().flat_map(|field| field.attributes()))
+        .map(|attribute| {
+            let AttributeType::MemberRef(constructor) = attribute.ty();
+            constructor
+        })
+        .collect();
+
+    assert_eq!(constructors.len(), 3);
+    assert!(constructors.iter().all(|row| *row == constructors[0]));
+}
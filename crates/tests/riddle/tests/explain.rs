@@ -0,0 +1,90 @@
+use windows_bindgen::Bindgen;
+
+#[test]
+fn json() {
+    let rdl = "tests/explain.rdl";
+    let rs = std::env::temp_dir().join("riddle_explain.rs");
+    let json = std::env::temp_dir().join("riddle_explain.json");
+    let (rs, json) = (rs.to_str().unwrap(), json.to_str().unwrap());
+
+    windows_bindgen::bindgen([
+        "--in",
+        rdl,
+        "--out",
+        rs,
+        "--filter",
+        "Test.Line",
+        "--config",
+        "flatten",
+        &format!("explain={json}"),
+    ])
+    .unwrap_or_else(|e| panic!("Failed to run bindgen: {rdl} : {e:?}"));
+
+    let report = std::fs::read_to_string(json)
+        .unwrap_or_else(|e| panic!("Failed to read report: {json} : {e:?}"));
+
+    assert_eq!(
+        report,
+        r#"{
+  "Test.Coordinate": [
+    "Test.Point",
+    "Test.Line"
+  ],
+  "Test.Line": [],
+  "Test.Point": [
+    "Test.Line"
+  ]
+}"#
+    );
+}
+
+#[test]
+fn text() {
+    let txt = std::env::temp_dir().join("riddle_explain.txt");
+    let txt = txt.to_str().unwrap();
+
+    // Items matching the filter are never explained by another item.
+    Bindgen::new()
+        .input("tests/explain.rdl")
+        .filter("Test.Line")
+        .filter("Test.Coordinate")
+        .flatten(true)
+        .explain(txt)
+        .generate()
+        .unwrap();
+
+    let report = std::fs::read_to_string(txt)
+        .unwrap_or_else(|e| panic!("Failed to read report: {txt} : {e:?}"));
+
+    assert_eq!(
+        report,
+        "Test.Coordinate\nTest.Line\nTest.Point <- Test.Line\n"
+    );
+}
+
+#[test]
+fn requires_flatten() {
+    let error = Bindgen::new()
+        .input("tests/explain.rdl")
+        .filter("Test.Line")
+        .config("explain")
+        .generate()
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "error: the `explain` configuration value requires a path when there is no output\n"
+    );
+
+    let error = Bindgen::new()
+        .input("tests/explain.rdl")
+        .filter("Test.Line")
+        .explain("explain.txt")
+        .generate()
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "error: the `explain` configuration value requires the `flatten` configuration value\n"
+    );
+}
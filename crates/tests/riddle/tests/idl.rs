@@ -0,0 +1,47 @@
+#[test]
+fn test() {
+    let rdl = "tests/idl.rdl";
+    let idl = "tests/idl.idl";
+
+    let before = std::fs::read_to_string(idl)
+        .unwrap_or_else(|e| panic!("Failed to read input: {idl} : {e:?}"));
+
+    windows_bindgen::bindgen([
+        "--in",
+        rdl,
+        "--out",
+        idl,
+        "--filter",
+        "Test",
+        "--config",
+        "no-bindgen-comment",
+    ])
+    .unwrap_or_else(|e| panic!("Failed to run bindgen: {rdl} : {e:?}"));
+
+    let after = std::fs::read_to_string(idl)
+        .unwrap_or_else(|e| panic!("Failed to read output: {idl} : {e:?}"));
+
+    assert_eq!(before, after);
+}
+
+#[test]
+fn errors() {
+    let rdl = "tests/idl_errors.rdl";
+    let idl = "tests/idl_errors.idl";
+
+    let error = windows_bindgen::bindgen(["--in", rdl, "--out", idl, "--filter", "Test.IIterable"])
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "error: generic type `Test.IIterable` is not supported in MIDL\n"
+    );
+
+    let error = windows_bindgen::bindgen(["--in", rdl, "--out", idl, "--filter", "Test.Button"])
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "error: `Test.Button` cannot derive from sealed class `Test.Widget`\n"
+    );
+}
@@ -0,0 +1,80 @@
+fn list(query: &[&str]) -> String {
+    let mut args = vec!["--in", "tests/list.rdl", "--filter", "Test", "--list"];
+    args.extend_from_slice(query);
+
+    windows_bindgen::bindgen(args).unwrap_or_else(|e| panic!("Failed to run bindgen: {e}"))
+}
+
+#[test]
+fn namespaces() {
+    assert_eq!(list(&[]), "Test");
+}
+
+#[test]
+fn namespace() {
+    assert_eq!(
+        list(&["Test"]),
+        "interface IShape\nstruct Point\nstruct Rect\nclass Shape"
+    );
+}
+
+#[test]
+fn members() {
+    assert_eq!(
+        list(&["Test.IShape"]),
+        "interface Test.IShape
+  fn Bounds() -> Test.Rect
+  fn Contains(point: Test.Point) -> bool
+  fn Scale(factor: f64)"
+    );
+
+    assert_eq!(
+        list(&["Test.Shape"]),
+        "class Test.Shape\n  extends System.Object\n  implements Test.IShape"
+    );
+}
+
+#[test]
+fn uses() {
+    assert_eq!(
+        list(&["uses=Test.Point"]),
+        "fn Test.IShape.Contains(point: Test.Point) -> bool\nTest.Rect.origin: Test.Point"
+    );
+}
+
+#[test]
+fn json() {
+    assert_eq!(
+        list(&["Test.Rect", "--config", "json"]),
+        r#"{
+  "kind": "struct",
+  "name": "Test.Rect",
+  "interfaces": [],
+  "members": [
+    {
+      "kind": "field",
+      "name": "origin",
+      "signature": "Test.Point"
+    },
+    {
+      "kind": "field",
+      "name": "width",
+      "signature": "i32"
+    },
+    {
+      "kind": "field",
+      "name": "height",
+      "signature": "i32"
+    }
+  ]
+}"#
+    );
+}
+
+#[test]
+fn not_found() {
+    let error =
+        windows_bindgen::bindgen(["--in", "tests/list.rdl", "--list", "Test.Missing"]).unwrap_err();
+
+    assert_eq!(error.to_string(), "error: `Test.Missing` not found\n");
+}
@@ -0,0 +1,61 @@
+use windows_metadata::*;
+
+#[test]
+fn test() {
+    let rdl = "tests/multi_file";
+    let winmd = "tests/multi_file.winmd";
+
+    windows_bindgen::bindgen(["--in", rdl, "--out", winmd, "--filter", "Test"])
+        .unwrap_or_else(|e| panic!("Failed to run bindgen: {rdl} : {e:?}"));
+
+    let bytes =
+        std::fs::read(winmd).unwrap_or_else(|e| panic!("Failed to read winmd: {winmd} : {e:?}"));
+
+    // The files are compiled as a single unit so the output doesn't depend on the order of the inputs.
+    windows_bindgen::bindgen([
+        "--in",
+        "tests/multi_file/b.rdl",
+        "tests/multi_file/a.rdl",
+        "--out",
+        winmd,
+        "--filter",
+        "Test",
+    ])
+    .unwrap_or_else(|e| panic!("Failed to run bindgen: {rdl} : {e:?}"));
+
+    assert_eq!(
+        bytes,
+        std::fs::read(winmd).unwrap_or_else(|e| panic!("Failed to read winmd: {winmd} : {e:?}"))
+    );
+
+    let mut files = tool_lib::default_metadata();
+    files.push(File::new(bytes).unwrap_or_else(|e| panic!("failed to parse winmd: {winmd} : {e}")));
+    let reader = Reader::new(files);
+
+    let def = reader
+        .get_type_def("Test", "IShape")
+        .next()
+        .expect("Type missing");
+
+    let methods: Vec<MethodDef> = def.methods().collect();
+    assert_eq!(methods.len(), 1);
+
+    let Type::TypeDef(rect, _) = methods[0].signature(&[]).return_type else {
+        panic!("type expected")
+    };
+
+    assert_eq!(rect.namespace(), "Test.Geometry");
+    assert_eq!(rect.name(), "Rect");
+
+    let fields: Vec<Field> = rect.fields().collect();
+    assert_eq!(fields.len(), 2);
+
+    for (field, name) in fields.iter().zip(["Point", "Size"]) {
+        let Type::TypeDef(def, _) = field.ty(None) else {
+            panic!("type expected")
+        };
+
+        assert_eq!(def.namespace(), "Test");
+        assert_eq!(def.name(), name);
+    }
+}
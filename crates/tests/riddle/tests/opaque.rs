@@ -0,0 +1,76 @@
+use windows_bindgen::Bindgen;
+
+fn builder() -> Bindgen {
+    Bindgen::new()
+        .input("tests/opaque.rdl")
+        .flatten(true)
+        .sys(true)
+        .no_bindgen_comment(true)
+}
+
+#[test]
+fn test() {
+    let output = builder()
+        .filter("Test.Holder")
+        .config("opaque:Test.Big")
+        .config("exclude:Test.Hidden")
+        .generate()
+        .unwrap();
+
+    assert!(output.contains("pub type Big = core::ffi::c_void;"));
+    assert!(output.contains("pub big: *mut Big,"));
+    assert!(output.contains("pub hidden: *mut core::ffi::c_void,"));
+    assert!(!output.contains("Detail"));
+    assert!(!output.contains("Hidden"));
+}
+
+#[test]
+fn by_value() {
+    let error = builder()
+        .filter("Test.Wrapper")
+        .config("opaque:Test.Big")
+        .generate()
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "error: `Test.Big` cannot be opaque because `Test.Wrapper` uses it by value\n"
+    );
+
+    let error = builder()
+        .filter("Test.Wrapper")
+        .config("exclude:Test.Big")
+        .generate()
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "error: `Test.Big` cannot be excluded because `Test.Wrapper` uses it by value\n"
+    );
+}
+
+#[test]
+fn invalid() {
+    let error = builder()
+        .filter("Test.Holder")
+        .config("opaque:Test.Missing")
+        .generate()
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "error: `Test.Missing` does not name a struct\n"
+    );
+
+    let error = Bindgen::new()
+        .input("tests/opaque.rdl")
+        .filter("Test.Holder")
+        .config("exclude:Test.Big")
+        .generate()
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "error: the `exclude` configuration value requires the `flatten` configuration value\n"
+    );
+}
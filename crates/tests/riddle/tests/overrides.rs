@@ -0,0 +1,131 @@
+#[test]
+fn test() {
+    let rdl = "tests/overrides.rdl";
+    let rs = "src/overrides.rs";
+
+    windows_bindgen::bindgen([
+        "--in",
+        rdl,
+        "--out",
+        rs,
+        "--filter",
+        "Test",
+        "--config",
+        "no-bindgen-comment",
+        "rename:Test.Point=Position",
+        "rename:Test.Rect.origin=top_left",
+        "derive:Test.Point=Hash",
+        "attribute:Test.Rect=must_use",
+        "hidden:Test.Rect.height",
+    ])
+    .unwrap_or_else(|e| panic!("Failed to run bindgen: {rdl} : {e:?}"));
+
+    let output = std::fs::read_to_string(rs)
+        .unwrap_or_else(|e| panic!("Failed to read output: {rs} : {e:?}"));
+
+    assert!(output.contains("PartialEq, Hash)]\npub struct Position {"));
+    assert!(output.contains("#[must_use]\npub struct Rect {"));
+    assert!(output.contains("pub top_left: Position,"));
+    assert!(output.contains("#[doc(hidden)]\n    pub height: i32,"));
+    assert!(!output.contains("Point"));
+}
+
+#[test]
+fn unsupported() {
+    let error = windows_bindgen::bindgen([
+        "--in",
+        "tests/overrides.rdl",
+        "--out",
+        "src/overrides.rs",
+        "--filter",
+        "Test",
+        "--config",
+        "derive:Test.Rect.width=Hash",
+    ])
+    .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "error: `derive` cannot be applied to `Test.Rect.width`\n"
+    );
+}
+
+#[test]
+fn invalid() {
+    let error = |config| {
+        windows_bindgen::bindgen([
+            "--in",
+            "tests/overrides.rdl",
+            "--out",
+            "src/overrides.rs",
+            "--filter",
+            "Test",
+            "--config",
+            config,
+        ])
+        .unwrap_err()
+        .to_string()
+    };
+
+    assert_eq!(
+        error("derive:Test.Point=serde::"),
+        "error: `serde::` is not a valid derive for `derive:Test.Point`\n"
+    );
+
+    assert_eq!(
+        error("derive:Test.Point=Hash, Hash"),
+        "error: `Hash` is derived more than once for `derive:Test.Point`\n"
+    );
+
+    assert_eq!(
+        error("derive:Test.Point=core::marker::Copy"),
+        "error: `core::marker::Copy` is already derived for `Test.Point`\n"
+    );
+
+    assert_eq!(
+        error("attribute:Test.Rect=must_use]"),
+        "error: `must_use]` is not a valid attribute for `attribute:Test.Rect`\n"
+    );
+
+    assert_eq!(
+        error("attribute:Test.Rect=#[must_use] #[doc(hidden)]"),
+        "error: `#[must_use] #[doc(hidden)]` is not a valid attribute for `attribute:Test.Rect`\n"
+    );
+}
+
+#[test]
+fn sys_functions() {
+    let function = "Windows.Wdk.NetworkManagement.WindowsFilteringPlatform.FwpmFreeMemory0";
+
+    let builder = || {
+        windows_bindgen::Bindgen::new()
+            .input("../../libs/bindgen/default/Windows.Wdk.winmd")
+            .filter(function)
+            .flatten(true)
+            .sys(true)
+            .config(format!("must-use:{function}"))
+    };
+
+    // The attribute is passed to the `link!` macro, which accepts a single attribute.
+    let output = builder().generate().unwrap();
+    assert!(output.contains(r#""system" #[must_use] fn FwpmFreeMemory0("#));
+
+    let error = builder()
+        .config(format!("hidden:{function}"))
+        .generate()
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        format!("error: `{function}` cannot have more than one attribute, including its docs, with the `sys` configuration value\n")
+    );
+
+    // Functions declared in `raw-dylib` extern blocks take any number of attributes.
+    let output = builder()
+        .config(format!("hidden:{function}"))
+        .raw_dylib(true)
+        .generate()
+        .unwrap();
+
+    assert!(output.contains("#[doc(hidden)]\n    #[must_use]\n    pub fn FwpmFreeMemory0("));
+}
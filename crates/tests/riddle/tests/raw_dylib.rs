@@ -0,0 +1,51 @@
+#[test]
+fn test() {
+    let rs = "src/raw_dylib.rs";
+
+    windows_bindgen::bindgen([
+        "--out",
+        rs,
+        "--filter",
+        "Windows.Wdk.NetworkManagement.WindowsFilteringPlatform.FwpmFreeMemory0",
+        "Windows.Wdk.Storage.FileSystem.FsRtlIncrementCcFastReadWait",
+        "Windows.Wdk.NetworkManagement.Ndis.NdisWriteErrorLogEntry",
+        "--config",
+        "flatten",
+        "sys",
+        "raw-dylib",
+        "no-bindgen-comment",
+    ])
+    .unwrap_or_else(|e| panic!("Failed to run bindgen: {rs} : {e:?}"));
+
+    let output = std::fs::read_to_string(rs)
+        .unwrap_or_else(|e| panic!("Failed to read output: {rs} : {e:?}"));
+
+    assert!(!output.contains("windows_targets"));
+    assert!(output.contains(r#"kind = "raw-dylib""#));
+    assert!(output.contains(r#"import_name_type = "undecorated""#));
+    assert!(output.contains(r#"name = "fwpuclnt.dll""#));
+    assert!(output.contains(r#"name = "ntoskrnl.exe""#));
+    assert!(output.contains("pub fn FsRtlIncrementCcFastReadWait();"));
+
+    // The cdecl function gets its own block, and since ndis.sys exports undecorated names it must not be given the
+    // leading underscore of a decorated cdecl name on x86.
+    let ndis = &output[output.find(r#"name = "ndis.sys""#).unwrap()..];
+    let ndis = &ndis[..ndis.find('}').unwrap()];
+    assert!(ndis.contains(r#"import_name_type = "undecorated""#));
+    assert!(ndis.contains("extern \"C\" {\n    pub fn NdisWriteErrorLogEntry("));
+}
+
+#[test]
+fn requires_flatten() {
+    let error = windows_bindgen::Bindgen::new()
+        .filter("Windows.Wdk.Storage.FileSystem.FsRtlIncrementCcFastReadWait")
+        .sys(true)
+        .raw_dylib(true)
+        .generate()
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "error: the `raw-dylib` configuration value requires the `sys` and `flatten` configuration values\n"
+    );
+}
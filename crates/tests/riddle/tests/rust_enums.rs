@@ -0,0 +1,60 @@
+use test_riddle::rust_enums::{D3DDDI_ROTATIONEnum, D3DDDI_ROTATION};
+
+#[test]
+fn test() {
+    let rs = "src/rust_enums.rs";
+
+    windows_bindgen::bindgen([
+        "--out",
+        rs,
+        "--filter",
+        "Windows.Wdk.Graphics.Direct3D.D3DDDI_ROTATION",
+        "--config",
+        "flatten",
+        "no-bindgen-comment",
+        "rust-enums",
+    ])
+    .unwrap_or_else(|e| panic!("Failed to run bindgen: {rs} : {e:?}"));
+
+    let output = std::fs::read_to_string(rs)
+        .unwrap_or_else(|e| panic!("Failed to read output: {rs} : {e:?}"));
+
+    assert!(output.contains("#[non_exhaustive]\npub enum D3DDDI_ROTATIONEnum {"));
+}
+
+#[test]
+fn convert() {
+    let value = D3DDDI_ROTATION::from(D3DDDI_ROTATIONEnum::D3DDDI_ROTATION_90);
+    assert_eq!(value, D3DDDI_ROTATION(2));
+
+    let degrees = match D3DDDI_ROTATIONEnum::try_from(value) {
+        Ok(D3DDDI_ROTATIONEnum::D3DDDI_ROTATION_IDENTITY) => 0,
+        Ok(D3DDDI_ROTATIONEnum::D3DDDI_ROTATION_90) => 90,
+        Ok(D3DDDI_ROTATIONEnum::D3DDDI_ROTATION_180) => 180,
+        Ok(D3DDDI_ROTATIONEnum::D3DDDI_ROTATION_270) => 270,
+        // The enum is non-exhaustive outside of the crate that generated it.
+        _ => unreachable!(),
+    };
+
+    assert_eq!(degrees, 90);
+
+    assert_eq!(
+        D3DDDI_ROTATIONEnum::try_from(D3DDDI_ROTATION(123)),
+        Err(D3DDDI_ROTATION(123))
+    );
+}
+
+#[test]
+fn sys() {
+    let error = windows_bindgen::Bindgen::new()
+        .filter("Windows.Wdk.Graphics.Direct3D.D3DDDI_ROTATION")
+        .sys(true)
+        .rust_enums(true)
+        .generate()
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "error: cannot combine `rust-enums` and `sys` configuration values\n"
+    );
+}
@@ -7,18 +7,18 @@ pub enum CallingConvention {
     Cdecl,
 }
 
-pub fn default_metadata() -> Vec<metadata::File> {
+pub fn default_metadata() -> Vec<metadata::File<'static>> {
     vec![
         metadata::File::new(
-            std::include_bytes!("../../../libs/bindgen/default/Windows.winmd").to_vec(),
+            &std::include_bytes!("../../../libs/bindgen/default/Windows.winmd")[..],
         )
         .expect("invalid winmd"),
         metadata::File::new(
-            std::include_bytes!("../../../libs/bindgen/default/Windows.Win32.winmd").to_vec(),
+            &std::include_bytes!("../../../libs/bindgen/default/Windows.Win32.winmd")[..],
         )
         .expect("invalid winmd"),
         metadata::File::new(
-            std::include_bytes!("../../../libs/bindgen/default/Windows.Wdk.winmd").to_vec(),
+            &std::include_bytes!("../../../libs/bindgen/default/Windows.Wdk.winmd")[..],
         )
         .expect("invalid winmd"),
     ]
@@ -30,7 +30,7 @@ pub fn libraries() -> BTreeMap<String, BTreeMap<String, CallingConvention>> {
 
     let files = default_metadata();
     let reader = metadata::Reader::new(files);
-    combine_libraries(reader, &mut libraries);
+    combine_libraries(&reader, &mut libraries);
     libraries
 }
 
@@ -81,6 +81,13 @@ pub fn crates<P: AsRef<Path>>(path: P) -> Vec<(String, String)> {
 fn find<P: AsRef<Path>>(path: P, regex: &Regex) -> Vec<(String, String)> {
     let mut names = vec![];
 
+    // Crates that declare their own workspace, such as the fuzz targets, aren't part of this workspace.
+    if let Ok(text) = std::fs::read_to_string(path.as_ref().join("Cargo.toml")) {
+        if text.lines().any(|line| line.trim() == "[workspace]") {
+            return names;
+        }
+    }
+
     if let Ok(files) = std::fs::read_dir(path) {
         for file in files.filter_map(|file| file.ok()) {
             if let Ok(file_type) = file.file_type() {
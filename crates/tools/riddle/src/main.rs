@@ -7,11 +7,15 @@ fn main() {
 
 Options:
   --in  <path>          Path to files and directories containing .winmd and .rdl files
-  --out <path>          Path to .winmd, .rdl, or .rs file to generate
+  --out <path>          Path to .winmd, .rdl, .rs, .h, or .idl file to generate
   --filter <namespace>  Namespaces to include or !exclude in output
   --config <key=value>  Override a configuration value
   --format              Format .rdl files only
-  --etc <path>          File containing command line options
+  --diff <path>         Compare the --in metadata with this metadata and report changes as JSON
+  --check-compat <path> Check that the --in metadata is a compatible version of this metadata
+  --list [query]        List namespaces, the items in a namespace, the members of an item, or uses=<type>
+  --cache <path>        Skip generating the output if nothing changed since this cache file was written
+  --etc <path>          File containing command line options or a .toml config file
 "#
         );
     } else {